@@ -0,0 +1,375 @@
+use digginggame::components::CHUNK_AREA;
+use digginggame::error::PersistenceError;
+use digginggame::managers::migrations;
+use digginggame::managers::persistence::{
+    ChunkSaveData, HEADER_FILE, PersistenceManager, SAVE_VERSION, SaveData,
+};
+use digginggame::managers::validation;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+const USAGE: &str = "\
+Usage: editor [COMMAND]
+
+Without a command the interactive editor opens.
+
+Commands:
+  info <save>                  Summarise a save
+  validate <save>              Check its version, schema and every chunk
+  diff <save> <save>           Compare player state and chunks of two saves
+  export-json <save> [file]    Write a save and all its regions as JSON (default: stdout)
+  import-json <file> <save>    Upgrade, validate and write JSON as a save
+  recompress <save> <level>    Rewrite a save's files at a zstd compression level
+
+<save> is a name in saves/, e.g. \"savegame\" or \"old.dat\".";
+
+/// `validate` found problems or `diff` found differences.
+const EXIT_CHECK_FAILED: i32 = 1;
+/// The command could not run.
+const EXIT_ERROR: i32 = 2;
+/// Values longer than this are summarised in a diff.
+const DIFF_VALUE_MAX_CHARS: usize = 60;
+
+/// Runs a command without a terminal and returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let res = match args.as_slice() {
+        ["info", save] => info(save).map(|()| 0),
+        ["validate", save] => validate(save).map(exit_code),
+        ["diff", a, b] => diff(a, b).map(exit_code),
+        ["export-json", save] => export_json(save, None).map(|()| 0),
+        ["export-json", save, file] => export_json(save, Some(file)).map(|()| 0),
+        ["import-json", file, save] => import_json(file, save).map(|()| 0),
+        ["recompress", save, level] => {
+            let range = zstd::compression_level_range();
+            match level.parse::<i32>() {
+                Ok(level) if range.contains(&level) => recompress(save, level).map(|()| 0),
+                _ => {
+                    eprintln!(
+                        "error: the level must be a number from {} to {}",
+                        range.start(),
+                        range.end()
+                    );
+                    return EXIT_ERROR;
+                }
+            }
+        }
+        ["help" | "-h" | "--help"] => {
+            println!("{USAGE}");
+            return 0;
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return EXIT_ERROR;
+        }
+    };
+    res.unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        EXIT_ERROR
+    })
+}
+
+const fn exit_code(is_ok: bool) -> i32 {
+    if is_ok { 0 } else { EXIT_CHECK_FAILED }
+}
+
+fn info(save: &str) -> Result<(), PersistenceError> {
+    let header = migrations::decode_value(&fs::read(PersistenceManager::header_path(save))?)?;
+    let version = migrations::stored_version(&header)?;
+    let mut data = migrations::upgrade(header)?;
+    data.modified_macrogrids
+        .extend(PersistenceManager::read_region_files(save)?);
+    let is_directory = PersistenceManager::save_dir(save)
+        .join(HEADER_FILE)
+        .is_file();
+    let chunk_count: usize = data
+        .modified_macrogrids
+        .iter()
+        .map(|region| region.chunks.len())
+        .sum();
+
+    println!("save:       {save}");
+    println!(
+        "format:     {}",
+        if is_directory {
+            "save directory"
+        } else {
+            "single file"
+        }
+    );
+    if version < SAVE_VERSION {
+        println!("version:    {version} (loads as {SAVE_VERSION})");
+    } else {
+        println!("version:    {version}");
+    }
+    println!(
+        "seeds:      {} / {}",
+        data.world_seed_main, data.world_seed_ore
+    );
+    println!(
+        "player:     ({}, {}), ${}, fuel {}/{}",
+        data.player_x, data.player_y, data.player_money, data.player_fuel, data.player_max_fuel
+    );
+    println!(
+        "levels:     drill {}, tank {}, engine {}, cargo {}, heat {}",
+        data.player_drill_level,
+        data.player_tank_level,
+        data.player_engine_level,
+        data.player_cargo_level,
+        data.player_heat_resistance_level
+    );
+    println!(
+        "items:      {} cargo stacks, {} storage stacks, {} warp gates",
+        data.player_cargo.len(),
+        data.player_storage.len(),
+        data.player_warp_gates.len()
+    );
+    println!(
+        "progress:   {:.0}s played, deepest {}m",
+        data.play_time_seconds, data.max_depth
+    );
+    println!(
+        "world:      {} modified chunks in {} regions, {} chunks visited",
+        chunk_count,
+        data.modified_macrogrids.len(),
+        data.visited_chunks.len()
+    );
+    println!("disk:       {} bytes with backups", disk_size(save));
+    Ok(())
+}
+
+/// Prints every problem with a save; an unreadable file is a problem, not an error.
+fn validate(save: &str) -> Result<bool, PersistenceError> {
+    let mut problems: Vec<String> = Vec::new();
+    let header = PersistenceManager::header_path(save);
+    let mut chunk_count = 0;
+
+    match fs::read(&header)
+        .map_err(PersistenceError::from)
+        .and_then(|compressed| migrations::decode_value(&compressed))
+        .and_then(|value| {
+            let version = migrations::stored_version(&value)?;
+            if version < SAVE_VERSION {
+                println!(
+                    "{}: version {version}, loads as {SAVE_VERSION}",
+                    header.display()
+                );
+            }
+            migrations::upgrade(value)
+        }) {
+        Ok(mut data) => {
+            // Single-file saves carry their regions inline.
+            let regions = std::mem::take(&mut data.modified_macrogrids);
+            if let Err(e) = validation::validate_save(&data) {
+                problems.push(format!("{}: {e}", header.display()));
+            }
+            for region in &regions {
+                chunk_count += region.chunks.len();
+                problems.extend(
+                    validation::region_problems(region)
+                        .into_iter()
+                        .map(|e| format!("{}: {e}", header.display())),
+                );
+            }
+        }
+        Err(e) => problems.push(format!("{}: {e}", header.display())),
+    }
+
+    let save_dir = PersistenceManager::save_dir(save);
+    for path in PersistenceManager::region_files(save) {
+        match PersistenceManager::read_region_file(&path) {
+            Ok(region) => {
+                chunk_count += region.chunks.len();
+                if PersistenceManager::region_path(&save_dir, region.mgx, region.mgy) != path {
+                    problems.push(format!(
+                        "{}: holds region ({}, {})",
+                        path.display(),
+                        region.mgx,
+                        region.mgy
+                    ));
+                }
+                problems.extend(
+                    validation::region_problems(&region)
+                        .into_iter()
+                        .map(|e| format!("{}: {e}", path.display())),
+                );
+            }
+            Err(e) => problems.push(format!("{}: {e}", path.display())),
+        }
+    }
+
+    for problem in &problems {
+        println!("{problem}");
+    }
+    if problems.is_empty() {
+        println!("{save}: valid, {chunk_count} modified chunks");
+    } else {
+        println!("{save}: {} problems", problems.len());
+    }
+    Ok(problems.is_empty())
+}
+
+/// Prints how `b` differs from `a`, field by field and chunk by chunk.
+fn diff(a: &str, b: &str) -> Result<bool, PersistenceError> {
+    let (data_a, data_b) = (
+        PersistenceManager::read_whole_save(a)?,
+        PersistenceManager::read_whole_save(b)?,
+    );
+    let mut differences = 0;
+
+    let (fields_a, fields_b) = (header_fields(&data_a)?, header_fields(&data_b)?);
+    let keys: BTreeSet<&String> = fields_a.keys().chain(fields_b.keys()).collect();
+    for key in keys {
+        let (value_a, value_b) = (fields_a.get(key), fields_b.get(key));
+        if value_a != value_b {
+            println!("{key}: {} -> {}", summarize(value_a), summarize(value_b));
+            differences += 1;
+        }
+    }
+
+    let (chunks_a, chunks_b) = (chunks_of(&data_a), chunks_of(&data_b));
+    let coords: BTreeSet<&(i32, i32)> = chunks_a.keys().chain(chunks_b.keys()).collect();
+    for &(cx, cy) in coords {
+        let line = match (chunks_a.get(&(cx, cy)), chunks_b.get(&(cx, cy))) {
+            (Some(chunk_a), Some(chunk_b)) => {
+                let changed = changed_blocks(chunk_a, chunk_b);
+                let is_renamed = serde_json::to_value(&chunk_a.named_blocks)?
+                    != serde_json::to_value(&chunk_b.named_blocks)?;
+                match (changed, is_renamed) {
+                    (0, false) => continue,
+                    (0, true) => "block names differ".to_string(),
+                    (n, false) => format!("{n} blocks differ"),
+                    (n, true) => format!("{n} blocks and block names differ"),
+                }
+            }
+            (Some(_), None) => format!("only in {a}"),
+            _ => format!("only in {b}"),
+        };
+        println!("chunk ({cx}, {cy}): {line}");
+        differences += 1;
+    }
+
+    if differences == 0 {
+        println!("{a} and {b} are the same");
+    } else {
+        println!("{differences} differences");
+    }
+    Ok(differences == 0)
+}
+
+/// Everything but the chunks, as JSON fields.
+fn header_fields(data: &SaveData) -> Result<BTreeMap<String, Value>, PersistenceError> {
+    let Value::Object(map) = serde_json::to_value(data)? else {
+        return Err(PersistenceError::InvalidFormat);
+    };
+    Ok(map
+        .into_iter()
+        .filter(|(key, _)| key != "modified_macrogrids")
+        .collect())
+}
+
+fn summarize(value: Option<&Value>) -> String {
+    match value {
+        None => "(none)".to_string(),
+        Some(Value::Array(items)) if items.len() > 4 => format!("[{} items]", items.len()),
+        Some(value) => {
+            let text = value.to_string();
+            if text.chars().count() > DIFF_VALUE_MAX_CHARS {
+                let cut: String = text.chars().take(DIFF_VALUE_MAX_CHARS).collect();
+                format!("{cut}...")
+            } else {
+                text
+            }
+        }
+    }
+}
+
+fn chunks_of(data: &SaveData) -> BTreeMap<(i32, i32), &ChunkSaveData> {
+    data.modified_macrogrids
+        .iter()
+        .flat_map(|region| &region.chunks)
+        .map(|chunk| ((chunk.cx, chunk.cy), chunk))
+        .collect()
+}
+
+/// Blocks whose type or liquid level differ between two encodings of a chunk.
+fn changed_blocks(a: &ChunkSaveData, b: &ChunkSaveData) -> usize {
+    let (blocks_a, blocks_b) = (expand(a), expand(b));
+    let common = blocks_a
+        .iter()
+        .zip(&blocks_b)
+        .filter(|(x, y)| x != y)
+        .count();
+    common + blocks_a.len().abs_diff(blocks_b.len())
+}
+
+/// `(type_id, level)` of each block from the RLE runs.
+fn expand(chunk: &ChunkSaveData) -> Vec<(u32, u32)> {
+    let mut blocks = Vec::with_capacity(CHUNK_AREA);
+    for run in chunk.blocks.chunks_exact(3) {
+        let count = usize::try_from(run[2]).unwrap_or(usize::MAX);
+        let count = count.min(CHUNK_AREA.saturating_sub(blocks.len()));
+        blocks.extend(std::iter::repeat_n((run[0], run[1]), count));
+    }
+    blocks
+}
+
+fn export_json(save: &str, file: Option<&str>) -> Result<(), PersistenceError> {
+    let data = PersistenceManager::read_whole_save(save)?;
+    let json = serde_json::to_string_pretty(&data)?;
+    match file {
+        Some(file) => {
+            fs::write(file, json)?;
+            println!(
+                "exported {save} with {} regions to {file}",
+                data.modified_macrogrids.len()
+            );
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+/// Regions in the JSON replace their files; the save's other regions are kept.
+fn import_json(file: &str, save: &str) -> Result<(), PersistenceError> {
+    let value: Value = serde_json::from_str(&fs::read_to_string(file)?)?;
+    let data = migrations::upgrade(value)?;
+    let region_count = data.modified_macrogrids.len();
+    PersistenceManager::write_save(save, data)?;
+    println!("imported {file} into {save} with {region_count} regions");
+    Ok(())
+}
+
+fn recompress(save: &str, level: i32) -> Result<(), PersistenceError> {
+    let before = disk_size(save);
+    let count = PersistenceManager::recompress(save, level)?;
+    println!(
+        "recompressed {count} files at level {level}: {before} -> {} bytes with backups",
+        disk_size(save)
+    );
+    Ok(())
+}
+
+/// Bytes taken by a save directory, or by a single-file save.
+fn disk_size(save: &str) -> u64 {
+    fn size_of(path: &Path) -> u64 {
+        if path.is_dir() {
+            fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| size_of(&entry.path()))
+                .sum()
+        } else {
+            fs::metadata(path).map_or(0, |metadata| metadata.len())
+        }
+    }
+    let save_dir = PersistenceManager::save_dir(save);
+    if save_dir.join(HEADER_FILE).is_file() {
+        size_of(&save_dir)
+    } else {
+        size_of(&PersistenceManager::header_path(save))
+    }
+}
@@ -1,22 +1,33 @@
+mod cli;
+mod panes;
+mod world_view;
+
 use anyhow::{Context, Result};
 use ratatui::crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{
     Frame, Terminal,
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Tabs,
+    },
 };
-use std::{fs, io, path::Path, time::Duration};
+use std::{io, time::Duration};
 use tui_textarea::TextArea;
 
-use digginggame::managers::persistence::SaveData;
-
-const SAVE_DIR: &str = "saves";
+use digginggame::components::WarpGate;
+use digginggame::managers::migrations;
+use digginggame::managers::persistence::{PersistenceManager, SaveData};
+use panes::Pane;
+use world_view::WorldView;
 
 enum AppMode {
     FileList,
@@ -31,6 +42,14 @@ struct App<'a> {
     list_state: ListState,
     textarea: TextArea<'a>,
     current_filename: Option<String>,
+    data: Option<SaveData>,
+    pane: Pane,
+    table_state: TableState,
+    column: usize,
+    /// Text typed into the selected cell, while it is being edited.
+    cell_input: Option<String>,
+    status: String,
+    world_view: Option<WorldView>,
 }
 
 impl App<'_> {
@@ -41,23 +60,22 @@ impl App<'_> {
             list_state: ListState::default(),
             textarea: TextArea::default(),
             current_filename: None,
+            data: None,
+            pane: Pane::Player,
+            table_state: TableState::default(),
+            column: 0,
+            cell_input: None,
+            status: String::new(),
+            world_view: None,
         }
     }
 
     fn refresh_file_list(&mut self) {
-        self.files.clear();
-        if let Ok(entries) = fs::read_dir(SAVE_DIR) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(name) = entry
-                    .file_name()
-                    .to_str()
-                    .filter(|_| path.extension().is_some_and(|ext| ext == "dat"))
-                {
-                    self.files.push(name.to_string());
-                }
-            }
-        }
+        self.files = PersistenceManager::list_save_slots()
+            .into_iter()
+            .map(|slot| slot.name)
+            .collect();
+        self.files.sort();
         if self.list_state.selected().is_none() && !self.files.is_empty() {
             self.list_state.select(Some(0));
         }
@@ -66,18 +84,14 @@ impl App<'_> {
     fn load_selected_file(&mut self) {
         if let Some(idx) = self.list_state.selected().filter(|&i| i < self.files.len()) {
             let filename = &self.files[idx];
-            self.current_filename = Some(filename.clone());
-
-            let path = Path::new(SAVE_DIR).join(filename);
-            match Self::read_and_decode(&path) {
-                Ok(json_content) => {
-                    self.textarea = TextArea::from(json_content.lines());
-                    self.textarea
-                        .set_block(Block::default().borders(Borders::ALL).title(format!(
-                            " Editing: {filename} (Ctrl+S to Save, Esc to Cancel) "
-                        )));
-                    self.textarea
-                        .set_line_number_style(Style::default().fg(Color::DarkGray));
+            // The save as the game would load it, upgraded to the current version.
+            match PersistenceManager::read_save(filename) {
+                Ok(data) => {
+                    self.current_filename = Some(filename.clone());
+                    self.data = Some(data);
+                    self.world_view = None;
+                    self.status.clear();
+                    self.open_pane(Pane::Player);
                     self.mode = AppMode::Editing;
                 }
                 Err(e) => {
@@ -87,61 +101,304 @@ impl App<'_> {
         }
     }
 
-    fn read_and_decode(path: &Path) -> Result<String> {
-        let compressed = fs::read(path).context("Failed to read file")?;
-        let decompressed = zstd::decode_all(&compressed[..]).context("Failed to decompress")?;
-        // First deserialize to SaveData to validate structure (optional, but good practice)
-        // Or directly to Value to allow flexible editing.
-        // Let's decode to Value to preserve formatting as much as possible,
-        // but decoding to SaveData ensures we are editing what we think we are.
-        // However, user might want to see pretty JSON.
-        let data: serde_json::Value =
-            serde_json::from_slice(&decompressed).context("Invalid JSON")?;
-        let pretty_json = serde_json::to_string_pretty(&data).context("Failed to format JSON")?;
-        Ok(pretty_json)
+    /// Switches to `pane`. The raw view is filled from the current save on the way in and
+    /// parsed back on the way out; invalid JSON keeps it open. The map is regenerated on
+    /// the way in only if the seeds or settings changed, and hands its painted regions
+    /// back to the save on the way out.
+    fn open_pane(&mut self, pane: Pane) {
+        if self.pane == Pane::Map {
+            self.write_back_map();
+        }
+        if self.pane == Pane::Raw && pane != Pane::Raw && self.data.is_some() {
+            match self.parse_raw() {
+                Ok(data) => self.data = Some(data),
+                Err(e) => {
+                    self.status = format!("{e:#}");
+                    return;
+                }
+            }
+        }
+        if pane == Pane::Raw
+            && let Some(data) = &self.data
+        {
+            let json = serde_json::to_string_pretty(data).unwrap_or_default();
+            self.textarea = TextArea::from(json.lines());
+            self.textarea
+                .set_block(Block::default().borders(Borders::ALL));
+            self.textarea
+                .set_line_number_style(Style::default().fg(Color::DarkGray));
+        }
+        if pane == Pane::Map {
+            self.prepare_world_view();
+        }
+        self.pane = pane;
+        self.column = pane.first_column();
+        self.cell_input = None;
+        self.table_state.select(Some(0));
+    }
+
+    /// Brings the map's world up to date with the save, regenerating it only if the seeds
+    /// or settings changed.
+    fn prepare_world_view(&mut self) {
+        let (Some(filename), Some(data)) = (&self.current_filename, &self.data) else {
+            return;
+        };
+        if let Some(view) = self
+            .world_view
+            .as_mut()
+            .filter(|view| view.is_generated_from(data))
+        {
+            view.reapply(data);
+        } else {
+            let view = WorldView::open(filename, data);
+            self.world_view = Some(match &self.world_view {
+                Some(previous) => view.with_position_of(previous),
+                None => view,
+            });
+        }
     }
 
+    /// Makes the world agree with an edit of the warp gate list: the block of `old` is dug
+    /// out and `new` is built with its name, then the changed regions go back into the
+    /// save. Otherwise the game would find the old gate block again on load and the new
+    /// gate would lead nowhere.
+    fn rebuild_gate(&mut self, old: Option<&WarpGate>, new: Option<&WarpGate>) {
+        self.prepare_world_view();
+        if let (Some(view), Some(data)) = (&mut self.world_view, &mut self.data) {
+            if let Some(gate) = old {
+                view.remove_gate(gate);
+            }
+            if let Some(gate) = new {
+                view.place_gate(gate);
+            }
+            view.write_back(data);
+        }
+    }
+
+    fn write_back_map(&mut self) {
+        if let (Some(view), Some(data)) = (&self.world_view, &mut self.data) {
+            view.write_back(data);
+        }
+    }
+
+    /// Runs the raw JSON through the game's migrations, as a loaded save would be.
+    fn parse_raw(&self) -> Result<SaveData> {
+        let content = self.textarea.lines().join("\n");
+        let value = serde_json::from_str(&content).context("Invalid JSON")?;
+        migrations::upgrade(value).context("Invalid Save Data Structure")
+    }
+
+    /// Validates the save the way the game does, then writes it the way the game saves.
     fn save_current_file(&mut self) {
-        if let Some(filename) = &self.current_filename {
-            let content = self.textarea.lines().join("\n");
-            let path = Path::new(SAVE_DIR).join(filename);
+        if self.pane == Pane::Raw {
+            match self.parse_raw() {
+                Ok(data) => self.data = Some(data),
+                Err(e) => {
+                    self.mode = AppMode::Error(format!("{e:#}"));
+                    return;
+                }
+            }
+        } else if self.pane == Pane::Map {
+            self.write_back_map();
+        }
+        if let (Some(filename), Some(data)) = (&self.current_filename, &self.data) {
+            let res = PersistenceManager::write_save(filename, data.clone())
+                .context("Failed to write save");
+            self.mode = match res {
+                Ok(()) => AppMode::Success("Saved successfully! Press Enter.".to_string()),
+                Err(e) => AppMode::Error(format!("{e:#}")),
+            };
+        }
+    }
 
-            // Validate JSON
-            match serde_json::from_str::<SaveData>(&content) {
-                Ok(data) => {
-                    // Re-serialize to minified JSON for storage (or standard format)
-                    match serde_json::to_vec(&data) {
-                        Ok(json_bytes) => {
-                            // Compress
-                            match zstd::encode_all(&json_bytes[..], 0) {
-                                Ok(compressed) => {
-                                    // Write
-                                    if let Err(e) = fs::write(&path, compressed) {
-                                        self.mode =
-                                            AppMode::Error(format!("Failed to write file: {e}"));
-                                    } else {
-                                        self.mode = AppMode::Success(
-                                            "Saved successfully! Press Enter.".to_string(),
-                                        );
-                                    }
-                                }
-                                Err(e) => {
-                                    self.mode = AppMode::Error(format!("Compression failed: {e}"));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            self.mode = AppMode::Error(format!("Serialization failed: {e}"));
-                        }
+    fn row_count(&self) -> usize {
+        self.data
+            .as_ref()
+            .map_or(0, |data| self.pane.rows(data).len())
+    }
+
+    fn selected_row(&self) -> usize {
+        self.table_state.selected().unwrap_or(0)
+    }
+
+    fn move_selection(&mut self, is_down: bool) {
+        let count = self.row_count();
+        if count == 0 {
+            return;
+        }
+        let row = self.selected_row().min(count - 1);
+        let next = if is_down {
+            (row + 1) % count
+        } else {
+            (row + count - 1) % count
+        };
+        self.table_state.select(Some(next));
+    }
+
+    fn move_column(&mut self, is_right: bool) {
+        let columns = self.pane.headers().len();
+        if is_right && self.column + 1 < columns {
+            self.column += 1;
+        } else if !is_right && self.column > self.pane.first_column() {
+            self.column -= 1;
+        }
+    }
+
+    /// Starts typing into the selected cell, or flips it if it is a flag.
+    fn begin_cell_edit(&mut self) {
+        let (row, column) = (self.selected_row(), self.column);
+        let Some(current) = self
+            .data
+            .as_ref()
+            .and_then(|data| self.pane.rows(data).get(row).cloned())
+            .and_then(|cells| cells.get(column).cloned())
+        else {
+            return;
+        };
+        if self.pane.is_flag(column) {
+            let flipped = (current != "true").to_string();
+            self.commit_cell(&flipped);
+        } else {
+            self.cell_input = Some(current);
+        }
+    }
+
+    /// Applies `input` to the selected cell. Changing how the world is generated under
+    /// chunks that were already dug and saved gets a warning, as they keep their blocks.
+    /// A renamed or moved gate is rebuilt in the world.
+    fn commit_cell(&mut self, input: &str) {
+        let (pane, row, column) = (self.pane, self.selected_row(), self.column);
+        let has_saved_chunks = self.has_saved_chunks();
+        let Some(data) = &mut self.data else {
+            return;
+        };
+        let cell = |data: &SaveData| {
+            pane.rows(data)
+                .get(row)
+                .and_then(|cells| cells.get(column).cloned())
+        };
+        let before = cell(data);
+        let gate_before = data.player_warp_gates.get(row).cloned();
+        if let Err(e) = pane.set_cell(data, row, column, input) {
+            self.status = format!("Invalid value: {e}");
+            return;
+        }
+        let is_changed = cell(data) != before;
+        let gate_after = data.player_warp_gates.get(row).cloned();
+
+        self.status = if pane.is_generation_row(row) && is_changed && has_saved_chunks {
+            "Warning: the saved chunks keep the blocks of the old world and will sit on top \
+             of the new one"
+                .to_string()
+        } else {
+            String::new()
+        };
+        if pane == Pane::WarpGates && is_changed {
+            self.rebuild_gate(gate_before.as_ref(), gate_after.as_ref());
+        }
+    }
+
+    /// Whether the open save holds chunks dug in its world, inline or in region files.
+    fn has_saved_chunks(&self) -> bool {
+        self.data
+            .as_ref()
+            .is_some_and(|data| !data.modified_macrogrids.is_empty())
+            || self
+                .current_filename
+                .as_deref()
+                .is_some_and(|name| !PersistenceManager::region_files(name).is_empty())
+    }
+
+    fn handle_cell_key(&mut self, key: KeyEvent) {
+        let Some(input) = &mut self.cell_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                let input = std::mem::take(input);
+                self.cell_input = None;
+                self.commit_cell(&input);
+            }
+            KeyCode::Esc => self.cell_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_table_key(&mut self, key: KeyEvent) {
+        let (pane, row) = (self.pane, self.selected_row());
+        let is_shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Up | KeyCode::Down if is_shift && pane.is_list() => {
+                if let Some(data) = &mut self.data {
+                    let moved = pane.move_row(data, row, key.code == KeyCode::Down);
+                    self.table_state.select(Some(moved));
+                }
+            }
+            KeyCode::Down => self.move_selection(true),
+            KeyCode::Up => self.move_selection(false),
+            KeyCode::Right => self.move_column(true),
+            KeyCode::Left => self.move_column(false),
+            KeyCode::Enter => self.begin_cell_edit(),
+            KeyCode::Char('a') if pane.is_list() => {
+                if let Some(data) = &mut self.data {
+                    let added = pane.add_row(data, row);
+                    self.table_state.select(Some(added));
+                    if pane == Pane::WarpGates {
+                        let gate = data.player_warp_gates.get(added).cloned();
+                        self.rebuild_gate(None, gate.as_ref());
                     }
                 }
-                Err(e) => {
-                    self.mode = AppMode::Error(format!("Invalid Save Data Structure: {e}"));
+            }
+            KeyCode::Char('d') | KeyCode::Delete if pane.is_list() => {
+                if let Some(data) = &mut self.data {
+                    let gate = data.player_warp_gates.get(row).cloned();
+                    pane.delete_row(data, row);
+                    if pane == Pane::WarpGates {
+                        self.rebuild_gate(gate.as_ref(), None);
+                    }
                 }
+                let count = self.row_count();
+                self.table_state
+                    .select(Some(row.min(count.saturating_sub(1))));
             }
+            _ => {}
         }
     }
 
+    fn handle_map_key(&mut self, key: KeyEvent) {
+        let Some(view) = &mut self.world_view else {
+            return;
+        };
+        let is_fast = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Left => view.pan(-1, 0, is_fast),
+            KeyCode::Right => view.pan(1, 0, is_fast),
+            KeyCode::Up => view.pan(0, -1, is_fast),
+            KeyCode::Down => view.pan(0, 1, is_fast),
+            KeyCode::Char('+' | '=') => view.zoom_in(),
+            KeyCode::Char('-') => view.zoom_out(),
+            KeyCode::Char(']') => view.cycle_brush(true),
+            KeyCode::Char('[') => view.cycle_brush(false),
+            KeyCode::Char(' ') | KeyCode::Enter => view.paint(),
+            KeyCode::Char('x') | KeyCode::Delete | KeyCode::Backspace => view.erase(),
+            KeyCode::Char('p') => view.jump_to_player(),
+            _ => {}
+        }
+    }
+
+    fn close_save(&mut self) {
+        self.mode = AppMode::FileList;
+        self.current_filename = None;
+        self.data = None;
+        self.pane = Pane::Player;
+        self.cell_input = None;
+        self.world_view = None;
+    }
+
     fn next_file(&mut self) {
         if self.files.is_empty() {
             return;
@@ -178,6 +435,12 @@ impl App<'_> {
 }
 
 fn main() -> Result<()> {
+    // Commands run headless, e.g. in CI.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -223,23 +486,34 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     _ => {} // Ignore other keys
                 },
                 AppMode::Editing => {
-                    // Check for Ctrl+S
-                    if key.code == KeyCode::Char('s')
+                    if app.cell_input.is_some() {
+                        app.handle_cell_key(key);
+                    } else if key.code == KeyCode::Char('s')
                         && key.modifiers.contains(KeyModifiers::CONTROL)
                     {
                         app.save_current_file();
                     } else if key.code == KeyCode::Esc {
-                        app.mode = AppMode::FileList;
-                        app.current_filename = None;
-                    } else {
-                        // Forward to textarea
-                        // Convert crossterm key to tui-textarea key
+                        app.close_save();
+                    } else if key.code == KeyCode::Tab {
+                        app.open_pane(app.pane.next());
+                    } else if key.code == KeyCode::BackTab {
+                        app.open_pane(app.pane.previous());
+                    } else if app.pane == Pane::Raw {
                         app.textarea.input(key);
+                    } else if app.pane == Pane::Map {
+                        app.handle_map_key(key);
+                    } else {
+                        app.handle_table_key(key);
                     }
                 }
                 AppMode::Error(_) | AppMode::Success(_) => {
                     if key.code == KeyCode::Enter || key.code == KeyCode::Esc {
-                        app.mode = AppMode::Editing; // Return to editing
+                        // Return to editing, or to the list if the save never opened
+                        app.mode = if app.data.is_some() {
+                            AppMode::Editing
+                        } else {
+                            AppMode::FileList
+                        };
                     }
                 }
             }
@@ -277,9 +551,7 @@ fn ui(f: &mut Frame, app: &mut App) {
 
             f.render_stateful_widget(list, chunks[0], &mut app.list_state);
         }
-        AppMode::Editing => {
-            f.render_widget(&app.textarea, chunks[0]);
-        }
+        AppMode::Editing => draw_editor(f, app, chunks[0]),
         AppMode::Error(msg) => {
             let p = Paragraph::new(format!("Error: {msg}\n\nPress Enter to continue.")).block(
                 Block::default()
@@ -300,3 +572,89 @@ fn ui(f: &mut Frame, app: &mut App) {
         }
     }
 }
+
+fn draw_editor(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let filename = app.current_filename.as_deref().unwrap_or_default();
+    let tabs = Tabs::new(Pane::ALL.iter().map(|pane| pane.title()))
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Editing: {filename} (Ctrl+S to Save, Esc to Close) "
+        )))
+        .select(app.pane.index())
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Yellow),
+        );
+    f.render_widget(tabs, chunks[0]);
+
+    if app.pane == Pane::Raw {
+        f.render_widget(&app.textarea, chunks[1]);
+    } else if app.pane == Pane::Map {
+        if let Some(view) = &mut app.world_view {
+            view.render(f, chunks[1]);
+            let problems = view.take_problems();
+            if !problems.is_empty() {
+                app.status = problems.join("; ");
+            }
+        }
+    } else if let Some(data) = &app.data {
+        let selected = app.selected_row();
+        let rows = app
+            .pane
+            .rows(data)
+            .into_iter()
+            .enumerate()
+            .map(|(i, cells)| {
+                Row::new(cells.into_iter().enumerate().map(|(column, text)| {
+                    if i != selected || column != app.column {
+                        return Cell::from(text);
+                    }
+                    match &app.cell_input {
+                        Some(input) => Cell::from(format!("{input}_"))
+                            .style(Style::default().fg(Color::Black).bg(Color::Yellow)),
+                        None => Cell::from(text)
+                            .style(Style::default().add_modifier(Modifier::REVERSED)),
+                    }
+                }))
+            });
+        let widths = vec![Constraint::Fill(1); app.pane.headers().len()];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(app.pane.headers().iter().copied())
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::default().borders(Borders::ALL))
+            .row_highlight_style(Style::default().fg(Color::Yellow))
+            .highlight_symbol(">>");
+        f.render_stateful_widget(table, chunks[1], &mut app.table_state);
+    }
+
+    let help = if app.cell_input.is_some() {
+        "Enter: apply  Esc: discard"
+    } else if app.pane == Pane::Raw {
+        "Tab/Shift+Tab: switch pane (applies the JSON)"
+    } else if app.pane == Pane::Map {
+        "Arrows: pan (Shift: faster)  +/-: zoom  [/]: brush  Space: paint  x: erase  p: player"
+    } else if app.pane.is_list() {
+        "Tab: pane  Arrows: select  Enter: edit  a: add  d: delete  Shift+Up/Down: move"
+    } else {
+        "Tab: pane  Up/Down: select  Enter: edit"
+    };
+    let status = Paragraph::new(vec![
+        Line::from(Span::styled(
+            app.status.as_str(),
+            Style::default().fg(Color::Red),
+        )),
+        Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))),
+    ]);
+    f.render_widget(status, chunks[2]);
+}
@@ -0,0 +1,407 @@
+use digginggame::components::WarpGate;
+use digginggame::constants::BLOCK_SIZE;
+use digginggame::managers::block::BLOCK_MANAGER;
+use digginggame::managers::persistence::{ItemStack, SaveData};
+use digginggame::managers::world::settings::Abundance;
+use num_traits::Float;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A tab of the save editor. Every pane but `Map` and `Raw` is a table over part of the
+/// save.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pane {
+    Player,
+    Cargo,
+    Storage,
+    WarpGates,
+    World,
+    Map,
+    Raw,
+}
+
+const PLAYER_FIELDS: [&str; 16] = [
+    "x",
+    "y",
+    "vx",
+    "vy",
+    "money",
+    "fuel",
+    "max_fuel",
+    "max_cargo",
+    "max_storage",
+    "drill_level",
+    "tank_level",
+    "engine_level",
+    "cargo_level",
+    "heat_resistance_level",
+    "play_time_seconds",
+    "max_depth",
+];
+
+const WORLD_FIELDS: [&str; 8] = [
+    "seed_main",
+    "seed_ore",
+    "ore_richness",
+    "cave_density",
+    "liquid_abundance",
+    "depth_limit",
+    "starting_money",
+    "autosave_minutes",
+];
+
+impl Pane {
+    pub const ALL: [Self; 7] = [
+        Self::Player,
+        Self::Cargo,
+        Self::Storage,
+        Self::WarpGates,
+        Self::World,
+        Self::Map,
+        Self::Raw,
+    ];
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Player => "Player",
+            Self::Cargo => "Cargo",
+            Self::Storage => "Storage",
+            Self::WarpGates => "Warp Gates",
+            Self::World => "World",
+            Self::Map => "Map",
+            Self::Raw => "Raw JSON",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|&p| p == self).unwrap_or(0)
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub const fn headers(self) -> &'static [&'static str] {
+        match self {
+            Self::Player | Self::World => &["Field", "Value"],
+            Self::Cargo | Self::Storage => &["Item", "Count", "Natural", "Auto-stored"],
+            Self::WarpGates => &["Name", "X", "Y"],
+            Self::Map | Self::Raw => &[],
+        }
+    }
+
+    /// First editable column; forms only edit their value.
+    pub const fn first_column(self) -> usize {
+        match self {
+            Self::Player | Self::World => 1,
+            _ => 0,
+        }
+    }
+
+    /// Panes whose rows can be added, deleted and moved.
+    pub const fn is_list(self) -> bool {
+        matches!(self, Self::Cargo | Self::Storage | Self::WarpGates)
+    }
+
+    /// Columns toggled with Enter instead of typed into.
+    pub const fn is_flag(self, column: usize) -> bool {
+        matches!(self, Self::Cargo | Self::Storage) && column >= 2
+    }
+
+    /// Whether `row` is a seed or setting the world is generated from, up to the depth
+    /// limit.
+    pub const fn is_generation_row(self, row: usize) -> bool {
+        matches!(self, Self::World) && row <= 5
+    }
+
+    pub fn rows(self, data: &SaveData) -> Vec<Vec<String>> {
+        match self {
+            Self::Player => PLAYER_FIELDS
+                .iter()
+                .enumerate()
+                .map(|(i, field)| vec![(*field).to_string(), player_value(data, i)])
+                .collect(),
+            Self::Cargo => stack_rows(&data.player_cargo),
+            Self::Storage => stack_rows(&data.player_storage),
+            Self::WarpGates => data
+                .player_warp_gates
+                .iter()
+                .map(|gate| vec![gate.name.clone(), gate.x.to_string(), gate.y.to_string()])
+                .collect(),
+            Self::World => WORLD_FIELDS
+                .iter()
+                .enumerate()
+                .map(|(i, field)| vec![(*field).to_string(), world_value(data, i)])
+                .collect(),
+            Self::Map | Self::Raw => Vec::new(),
+        }
+    }
+
+    /// Parses `input` into the cell, leaving the save untouched if it does not fit. Gates
+    /// snap to the corner of the block they land in, where the game keeps them.
+    pub fn set_cell(
+        self,
+        data: &mut SaveData,
+        row: usize,
+        column: usize,
+        input: &str,
+    ) -> Result<(), String> {
+        match self {
+            Self::Player => set_player_value(data, row, input),
+            Self::Cargo => set_stack_cell(&mut data.player_cargo, row, column, input),
+            Self::Storage => set_stack_cell(&mut data.player_storage, row, column, input),
+            Self::WarpGates => {
+                let gate = data.player_warp_gates.get_mut(row).ok_or("no such gate")?;
+                match column {
+                    0 if input.trim().is_empty() => return Err("a gate needs a name".to_string()),
+                    0 => input.trim().clone_into(&mut gate.name),
+                    1 => gate.x = snap_to_block(parse_float(input, f32::MIN)?),
+                    _ => gate.y = snap_to_block(parse_float(input, f32::MIN)?),
+                }
+                Ok(())
+            }
+            Self::World => set_world_value(data, row, input),
+            Self::Map | Self::Raw => Ok(()),
+        }
+    }
+
+    /// Adds a row after `row`: a stack of dirt, or a gate where the player stands.
+    pub fn add_row(self, data: &mut SaveData, row: usize) -> usize {
+        let stack = ItemStack {
+            item_type: "dirt".to_string(),
+            count: 1,
+            is_natural: true,
+            is_auto_stored: false,
+        };
+        let insert = |len: usize| (row + 1).min(len);
+        match self {
+            Self::Cargo => {
+                let at = insert(data.player_cargo.len());
+                data.player_cargo.insert(at, stack);
+                at
+            }
+            Self::Storage => {
+                let at = insert(data.player_storage.len());
+                data.player_storage.insert(at, stack);
+                at
+            }
+            Self::WarpGates => {
+                let at = insert(data.player_warp_gates.len());
+                let gate = WarpGate {
+                    x: (data.player_x / BLOCK_SIZE).round() * BLOCK_SIZE,
+                    y: (data.player_y / BLOCK_SIZE).round() * BLOCK_SIZE,
+                    name: format!("Gate {}", data.player_warp_gates.len() + 1),
+                };
+                data.player_warp_gates.insert(at, gate);
+                at
+            }
+            _ => row,
+        }
+    }
+
+    pub fn delete_row(self, data: &mut SaveData, row: usize) {
+        fn remove<T>(list: &mut Vec<T>, row: usize) {
+            if row < list.len() {
+                list.remove(row);
+            }
+        }
+        match self {
+            Self::Cargo => remove(&mut data.player_cargo, row),
+            Self::Storage => remove(&mut data.player_storage, row),
+            Self::WarpGates => remove(&mut data.player_warp_gates, row),
+            _ => {}
+        }
+    }
+
+    /// Swaps `row` with the row below it, or above, returning where it ended up.
+    pub fn move_row(self, data: &mut SaveData, row: usize, is_down: bool) -> usize {
+        fn swap<T>(list: &mut [T], row: usize, is_down: bool) -> usize {
+            let target = if is_down {
+                row + 1
+            } else {
+                row.wrapping_sub(1)
+            };
+            if row < list.len() && target < list.len() {
+                list.swap(row, target);
+                target
+            } else {
+                row
+            }
+        }
+        match self {
+            Self::Cargo => swap(&mut data.player_cargo, row, is_down),
+            Self::Storage => swap(&mut data.player_storage, row, is_down),
+            Self::WarpGates => swap(&mut data.player_warp_gates, row, is_down),
+            _ => row,
+        }
+    }
+}
+
+fn player_value(data: &SaveData, index: usize) -> String {
+    match index {
+        0 => data.player_x.to_string(),
+        1 => data.player_y.to_string(),
+        2 => data.player_vx.to_string(),
+        3 => data.player_vy.to_string(),
+        4 => data.player_money.to_string(),
+        5 => data.player_fuel.to_string(),
+        6 => data.player_max_fuel.to_string(),
+        7 => data.player_max_cargo.to_string(),
+        8 => data.player_max_storage.to_string(),
+        9 => data.player_drill_level.to_string(),
+        10 => data.player_tank_level.to_string(),
+        11 => data.player_engine_level.to_string(),
+        12 => data.player_cargo_level.to_string(),
+        13 => data.player_heat_resistance_level.to_string(),
+        14 => data.play_time_seconds.to_string(),
+        _ => data.max_depth.to_string(),
+    }
+}
+
+fn set_player_value(data: &mut SaveData, index: usize, input: &str) -> Result<(), String> {
+    match index {
+        0 => data.player_x = parse_float(input, f32::MIN)?,
+        1 => data.player_y = parse_float(input, f32::MIN)?,
+        2 => data.player_vx = parse_float(input, f32::MIN)?,
+        3 => data.player_vy = parse_float(input, f32::MIN)?,
+        4 => data.player_money = parse_int(input, 0)?,
+        5 => data.player_fuel = parse_float(input, 0.0)?,
+        6 => data.player_max_fuel = parse_float(input, 0.0)?,
+        7 => data.player_max_cargo = parse_int(input, 0)?,
+        8 => data.player_max_storage = parse_int(input, 0)?,
+        9 => data.player_drill_level = parse_int(input, 1)?,
+        10 => data.player_tank_level = parse_int(input, 1)?,
+        11 => data.player_engine_level = parse_int(input, 1)?,
+        12 => data.player_cargo_level = parse_int(input, 1)?,
+        13 => data.player_heat_resistance_level = parse_int(input, 1)?,
+        14 => data.play_time_seconds = parse_float(input, 0.0)?,
+        _ => data.max_depth = parse_int(input, 0)?,
+    }
+    Ok(())
+}
+
+fn world_value(data: &SaveData, index: usize) -> String {
+    let settings = &data.world_settings;
+    match index {
+        0 => data.world_seed_main.to_string(),
+        1 => data.world_seed_ore.to_string(),
+        2 => abundance_name(settings.ore_richness).to_string(),
+        3 => abundance_name(settings.cave_density).to_string(),
+        4 => abundance_name(settings.liquid_abundance).to_string(),
+        5 => settings.depth_limit.to_string(),
+        6 => settings.starting_money.to_string(),
+        _ => data.autosave_minutes.to_string(),
+    }
+}
+
+fn set_world_value(data: &mut SaveData, index: usize, input: &str) -> Result<(), String> {
+    let settings = &mut data.world_settings;
+    match index {
+        0 => data.world_seed_main = parse_int(input, 0)?,
+        1 => data.world_seed_ore = parse_int(input, 0)?,
+        2 => settings.ore_richness = parse_abundance(input)?,
+        3 => settings.cave_density = parse_abundance(input)?,
+        4 => settings.liquid_abundance = parse_abundance(input)?,
+        5 => settings.depth_limit = parse_int(input, 1)?,
+        6 => settings.starting_money = parse_int(input, 0)?,
+        _ => data.autosave_minutes = parse_int(input, 0)?,
+    }
+    Ok(())
+}
+
+fn snap_to_block(position: f32) -> f32 {
+    (position / BLOCK_SIZE).floor() * BLOCK_SIZE
+}
+
+fn stack_rows(stacks: &[ItemStack]) -> Vec<Vec<String>> {
+    stacks
+        .iter()
+        .map(|stack| {
+            vec![
+                stack.item_type.clone(),
+                stack.count.to_string(),
+                stack.is_natural.to_string(),
+                stack.is_auto_stored.to_string(),
+            ]
+        })
+        .collect()
+}
+
+fn set_stack_cell(
+    stacks: &mut [ItemStack],
+    row: usize,
+    column: usize,
+    input: &str,
+) -> Result<(), String> {
+    let stack = stacks.get_mut(row).ok_or("no such stack")?;
+    match column {
+        0 => {
+            let item_type = input.trim();
+            if !BLOCK_MANAGER.is_known_item_type(item_type) {
+                return Err(format!("\"{item_type}\" is not a known item type"));
+            }
+            item_type.clone_into(&mut stack.item_type);
+        }
+        1 => stack.count = parse_int(input, 1)?,
+        2 => stack.is_natural = parse_value(input)?,
+        _ => stack.is_auto_stored = parse_value(input)?,
+    }
+    Ok(())
+}
+
+const fn abundance_name(abundance: Abundance) -> &'static str {
+    match abundance {
+        Abundance::Scarce => "scarce",
+        Abundance::Normal => "normal",
+        Abundance::Abundant => "abundant",
+    }
+}
+
+fn parse_abundance(input: &str) -> Result<Abundance, String> {
+    [Abundance::Scarce, Abundance::Normal, Abundance::Abundant]
+        .into_iter()
+        .find(|&a| abundance_name(a).eq_ignore_ascii_case(input.trim()))
+        .ok_or_else(|| "expected scarce, normal or abundant".to_string())
+}
+
+fn parse_value<T>(input: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    input
+        .trim()
+        .parse()
+        .map_err(|e| format!("\"{}\": {e}", input.trim()))
+}
+
+fn parse_int<T>(input: &str, min: T) -> Result<T, String>
+where
+    T: FromStr + PartialOrd + Display,
+    T::Err: Display,
+{
+    let value: T = parse_value(input)?;
+    if value < min {
+        return Err(format!("must be at least {min}"));
+    }
+    Ok(value)
+}
+
+fn parse_float<T>(input: &str, min: T) -> Result<T, String>
+where
+    T: Float + FromStr + Display,
+    T::Err: Display,
+{
+    let value: T = parse_value(input)?;
+    if !value.is_finite() {
+        return Err("must be a finite number".to_string());
+    }
+    if value < min {
+        return Err(format!("must be at least {min}"));
+    }
+    Ok(value)
+}
@@ -0,0 +1,346 @@
+use digginggame::components::{BlockType, WarpGate};
+use digginggame::constants::{BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS};
+use digginggame::managers::block::BLOCK_MANAGER;
+use digginggame::managers::persistence::{HEADER_FILE, PersistenceManager, SaveData};
+use digginggame::managers::world::WorldManager;
+use digginggame::managers::world::settings::WorldSettings;
+use num_traits::ToPrimitive;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::collections::HashSet;
+
+/// Blocks per half cell at each zoom level.
+const ZOOM_LEVELS: [i32; 4] = [1, 2, 4, 8];
+/// Cells a held Shift pans by.
+const FAST_PAN_CELLS: i32 = 8;
+/// A full cell of liquid, as the game places it.
+const FULL_LIQUID_LEVEL: u8 = 8;
+const UNLOADED_COLOR: Color = Color::Rgb(10, 10, 10);
+const EMPTY_COLOR: Color = Color::Rgb(20, 20, 40);
+
+/// The world of an open save, regenerated from its seeds with its saved chunks on top,
+/// drawn two blocks to a terminal cell in the colours of the in-game map.
+pub struct WorldView {
+    world: WorldManager,
+    /// Seeds and settings the world was generated from.
+    generation: (u32, u32, WorldSettings),
+    /// Block under the cursor, which the view is centred on.
+    cursor: (i32, i32),
+    player: (i32, i32),
+    zoom: usize,
+    /// Block types the brush can paint.
+    palette: Vec<BlockType>,
+    brush: usize,
+    /// Gate blocks dug out or painted over here. Their gates leave the save's list on
+    /// write-back, as breaking a gate in the game removes it.
+    removed_gates: HashSet<(i32, i32)>,
+}
+
+impl WorldView {
+    /// Generates the world of save `name` the way the game loads it: region files are read
+    /// as their chunks come into view, and `data.modified_macrogrids` is laid over them.
+    pub fn open(name: &str, data: &SaveData) -> Self {
+        let mut world = WorldManager::new();
+        world.seed(
+            data.world_seed_main,
+            data.world_seed_ore,
+            data.world_settings,
+        );
+        let save_dir = PersistenceManager::save_dir(name);
+        if save_dir.join(HEADER_FILE).is_file() {
+            world.region_dir = Some(save_dir);
+        }
+        world.apply_modifications(data.modified_macrogrids.clone());
+
+        let player = (
+            (data.player_x / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+            (data.player_y / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+        );
+        // Warp gates are placed with a name from the warp gate pane, not painted.
+        let palette = BLOCK_MANAGER
+            .block_types()
+            .into_iter()
+            .filter(|&bt| bt != BlockType::Air && bt != BlockType::WarpGate)
+            .collect();
+        Self {
+            world,
+            generation: generation_of(data),
+            cursor: player,
+            player,
+            zoom: 0,
+            palette,
+            brush: 0,
+            removed_gates: HashSet::new(),
+        }
+    }
+
+    /// Whether `data` still generates the world shown here.
+    pub fn is_generated_from(&self, data: &SaveData) -> bool {
+        self.generation == generation_of(data)
+    }
+
+    /// Lays `data.modified_macrogrids` over the world again after the save was edited in
+    /// another pane, keeping everything generated so far.
+    pub fn reapply(&mut self, data: &SaveData) {
+        self.world
+            .apply_modifications(data.modified_macrogrids.clone());
+    }
+
+    /// Keeps the cursor, zoom and brush of `other`, a view of the same save.
+    #[must_use]
+    pub fn with_position_of(mut self, other: &Self) -> Self {
+        self.cursor = other.cursor;
+        self.zoom = other.zoom;
+        self.brush = other.brush.min(self.palette.len().saturating_sub(1));
+        self
+    }
+
+    /// Puts every region changed here into `data.modified_macrogrids`, RLE encoded and
+    /// whole, so saving writes them over their region files, and drops the gates whose
+    /// blocks are gone from `data.player_warp_gates`.
+    pub fn write_back(&self, data: &mut SaveData) {
+        data.modified_macrogrids = self.world.dirty_region_save_data();
+        data.player_warp_gates
+            .retain(|gate| !self.removed_gates.contains(&gate_block(gate)));
+    }
+
+    /// Problems met reading the save's region files since the last call.
+    pub fn take_problems(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.world.region_problems)
+    }
+
+    const fn blocks_per_pixel(&self) -> i32 {
+        ZOOM_LEVELS[self.zoom]
+    }
+
+    /// Moves the cursor by whole cells, each twice as many blocks tall as wide.
+    pub fn pan(&mut self, dx: i32, dy: i32, is_fast: bool) {
+        let step = self.blocks_per_pixel() * if is_fast { FAST_PAN_CELLS } else { 1 };
+        self.cursor.0 += dx * step;
+        self.cursor.1 += dy * step * 2;
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = self.zoom.saturating_sub(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom + 1).min(ZOOM_LEVELS.len() - 1);
+    }
+
+    pub fn cycle_brush(&mut self, is_forward: bool) {
+        let count = self.palette.len().max(1);
+        self.brush = if is_forward {
+            (self.brush + 1) % count
+        } else {
+            (self.brush + count - 1) % count
+        };
+    }
+
+    pub fn jump_to_player(&mut self) {
+        self.cursor = self.player;
+    }
+
+    /// Paints the block under the cursor with the brush, at full hit points and as a full
+    /// cell of liquid.
+    pub fn paint(&mut self) {
+        let Some(&block_type) = self.palette.get(self.brush) else {
+            return;
+        };
+        self.edit_cursor_block(|block| {
+            block.block_type = block_type;
+            block.max_hp = block_type.get_base_hardness();
+            block.current_hp = block.max_hp;
+            block.liquid_level = if block_type.is_liquid() {
+                FULL_LIQUID_LEVEL
+            } else {
+                0
+            };
+        });
+    }
+
+    /// Digs out the block under the cursor, as breaking it would.
+    pub fn erase(&mut self) {
+        let (bx, by) = self.cursor;
+        self.edit_block(bx, by, dig_out);
+    }
+
+    /// Builds `gate` into the world the way the game places one: a full gate block
+    /// carrying the gate's name.
+    pub fn place_gate(&mut self, gate: &WarpGate) {
+        let (bx, by) = gate_block(gate);
+        self.edit_block(bx, by, |block| {
+            block.block_type = BlockType::WarpGate;
+            block.max_hp = BlockType::WarpGate.get_base_hardness();
+            block.current_hp = block.max_hp;
+            block.liquid_level = 0;
+        });
+        self.world.set_block_name(bx, by, Some(gate.name.clone()));
+        self.removed_gates.remove(&(bx, by));
+    }
+
+    /// Digs out the block of `gate` if a gate still stands there.
+    pub fn remove_gate(&mut self, gate: &WarpGate) {
+        let (bx, by) = gate_block(gate);
+        let (cx, cy) = chunk_of(bx, by);
+        self.world.ensure_chunk_exists_and_generated(cx, cy);
+        if self
+            .world
+            .get_block(bx, by)
+            .is_some_and(|block| block.block_type == BlockType::WarpGate)
+        {
+            self.edit_block(bx, by, dig_out);
+        }
+    }
+
+    fn edit_cursor_block(&mut self, edit: impl FnOnce(&mut digginggame::components::Block)) {
+        let (bx, by) = self.cursor;
+        self.edit_block(bx, by, edit);
+    }
+
+    fn edit_block(
+        &mut self,
+        bx: i32,
+        by: i32,
+        edit: impl FnOnce(&mut digginggame::components::Block),
+    ) {
+        let (cx, cy) = chunk_of(bx, by);
+        self.world.ensure_chunk_exists_and_generated(cx, cy);
+        let Some(mut block) = self.world.get_block(bx, by) else {
+            return;
+        };
+        if block.block_type == BlockType::WarpGate {
+            self.removed_gates.insert((bx, by));
+        }
+        edit(&mut block);
+        block.last_damage_time = None;
+        self.world.set_block(&block);
+        // A painted-over gate loses its name.
+        self.world.set_block_name(bx, by, None);
+        self.world.mark_chunk_modified(cx, cy);
+    }
+
+    /// Colour of block `(bx, by)` on the map; chunks still generating come out dark.
+    fn color_at(&self, bx: i32, by: i32) -> Color {
+        self.world
+            .get_block(bx, by)
+            .map_or(UNLOADED_COLOR, |block| {
+                if block.is_broken() {
+                    EMPTY_COLOR
+                } else {
+                    let color = block.block_type.get_map_color();
+                    let [r, g, b] = [color.r, color.g, color.b]
+                        .map(|c| (c * 255.0).round().to_u8().unwrap_or(0));
+                    Color::Rgb(r, g, b)
+                }
+            })
+    }
+
+    fn block_key(&self, bx: i32, by: i32) -> String {
+        self.world
+            .get_block(bx, by)
+            .and_then(|block| block.block_type.get_data().map(|d| d.key.clone()))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            " ({}, {}) {} | brush: {} | 1:{} ",
+            self.cursor.0,
+            self.cursor.1,
+            self.block_key(self.cursor.0, self.cursor.1),
+            self.palette
+                .get(self.brush)
+                .and_then(|bt| bt.get_data())
+                .map_or("-", |d| d.key.as_str()),
+            self.blocks_per_pixel(),
+        ));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let z = self.blocks_per_pixel();
+        let (width, height) = (i32::from(inner.width), i32::from(inner.height));
+        let left = self.cursor.0 - width / 2 * z;
+        let top = self.cursor.1 - height * z;
+        self.request_view(left, top, width * z, height * 2 * z);
+
+        let contains = |(x, y): (i32, i32), px: i32, py: i32| {
+            (px..px + z).contains(&x) && (py..py + 2 * z).contains(&y)
+        };
+        let lines: Vec<Line> = (0..height)
+            .map(|row| {
+                let py = top + row * 2 * z;
+                Line::from(
+                    (0..width)
+                        .map(|column| {
+                            let px = left + column * z;
+                            let upper = self.color_at(px, py);
+                            let lower = self.color_at(px, py + z);
+                            if contains(self.cursor, px, py) {
+                                Span::styled(
+                                    "+",
+                                    Style::default()
+                                        .fg(Color::Yellow)
+                                        .bg(upper)
+                                        .add_modifier(Modifier::BOLD),
+                                )
+                            } else if contains(self.player, px, py) {
+                                Span::styled("@", Style::default().fg(Color::White).bg(upper))
+                            } else {
+                                Span::styled("▀", Style::default().fg(upper).bg(lower))
+                            }
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// Installs the chunks generated so far and queues the rest of the view.
+    fn request_view(&mut self, left: i32, top: i32, width: i32, height: i32) {
+        self.world.receive_generated_chunks();
+        let (start_x, start_y) = chunk_of(left, top);
+        let (end_x, end_y) = chunk_of(left + width, top + height);
+        for cx in start_x..=end_x {
+            for cy in start_y..=end_y {
+                self.world.request_chunk(cx, cy, true);
+            }
+        }
+    }
+}
+
+const fn generation_of(data: &SaveData) -> (u32, u32, WorldSettings) {
+    (
+        data.world_seed_main,
+        data.world_seed_ore,
+        data.world_settings,
+    )
+}
+
+/// Empties `block` the way breaking it does.
+const fn dig_out(block: &mut digginggame::components::Block) {
+    block.block_type = BlockType::Air;
+    block.current_hp = 0;
+    block.liquid_level = 0;
+}
+
+/// Block holding `gate`, whose position is the world position of the block's corner.
+fn gate_block(gate: &WarpGate) -> (i32, i32) {
+    (
+        (gate.x / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+        (gate.y / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+    )
+}
+
+fn chunk_of(bx: i32, by: i32) -> (i32, i32) {
+    (
+        bx.div_euclid(CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(1)),
+        by.div_euclid(CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(1)),
+    )
+}
@@ -6,6 +6,8 @@ use crate::utils::get_item_weight;
 
 use ::rand::Rng;
 use macroquad::prelude::*;
+use num_traits::ToPrimitive;
+use std::collections::{BTreeSet, HashMap};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct BlockPos {
@@ -99,79 +101,216 @@ impl Particle {
 
 pub use crate::managers::block::BlockType;
 
-#[derive(Clone, Debug)]
+/// A block read out of its chunk. It is a copy: change the fields and hand it back to
+/// `WorldManager::set_block` (or `Chunk::set_block`) to store them.
+#[derive(Clone, Copy, Debug)]
 pub struct Block {
     pub x: f32,
     pub y: f32,
     pub max_hp: i32,
     pub current_hp: i32,
-    pub is_broken: bool,
-    pub is_modified: bool,
-    pub sprite_rect: Option<Rect>,
     pub block_type: BlockType,
     pub back_type: BlockType,
-    pub name: Option<String>,
     pub last_damage_time: Option<f64>,
     pub liquid_level: u8,
 }
 
 impl Block {
+    /// Air, and blocks without hit points, can be walked through and built into.
     #[must_use]
-    pub fn new(
-        x: f32,
-        y: f32,
-        max_hp: i32,
-        sprite_rect: Option<Rect>,
-        block_type: BlockType,
-    ) -> Self {
-        let liquid_level = if block_type.is_liquid() { 8 } else { 0 };
-        Self {
-            x,
-            y,
-            max_hp,
-            current_hp: max_hp,
-            is_broken: max_hp == 0 || block_type == BlockType::Air,
-            is_modified: false,
-            sprite_rect,
-            block_type,
-            back_type: block_type,
-            name: None,
-            last_damage_time: None,
-            liquid_level,
-        }
+    pub fn is_broken(&self) -> bool {
+        self.block_type == BlockType::Air || self.max_hp == 0
+    }
+
+    #[must_use]
+    pub fn sprite_rect(&self) -> Option<Rect> {
+        self.block_type.get_sprite()
+    }
+
+    /// World block coordinates of this block.
+    #[must_use]
+    pub fn coords(&self) -> (i32, i32) {
+        (
+            (self.x / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+            (self.y / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+        )
     }
 }
 
+/// Hit points of a block that has been drilled but not broken.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockDamage {
+    pub current_hp: i32,
+    pub last_damage_time: Option<f64>,
+}
+
+pub const CHUNK_AREA: usize = CHUNK_SIZE_X_BLOCKS * CHUNK_SIZE_Y_BLOCKS;
+
+/// A chunk's blocks as palette indices plus per-cell liquid levels and hit points, with
+/// damage and names kept on the side for the few cells that have them.
+/// Cells are stored column by column, at `rel_x * CHUNK_SIZE_Y_BLOCKS + rel_y`.
 pub struct Chunk {
-    pub blocks: Vec<Vec<Block>>, // Changed to 2D Vec, easier to instantiate
+    pub cx: i32,
+    pub cy: i32,
+    palette: Vec<BlockType>,
+    front: Vec<u16>,
+    back: Vec<u16>,
+    liquid_levels: Vec<u8>,
+    max_hp: Vec<i32>,
+    damage: HashMap<u16, BlockDamage>,
+    names: HashMap<u16, String>,
     pub is_generated: bool,
     pub is_modified_in_session: bool,
 }
 
 impl Chunk {
     #[must_use]
-    pub const fn new(_cx: i32, _cy: i32) -> Self {
+    pub fn new(cx: i32, cy: i32) -> Self {
         Self {
-            blocks: Vec::new(),
+            cx,
+            cy,
+            palette: Vec::new(),
+            front: Vec::new(),
+            back: Vec::new(),
+            liquid_levels: Vec::new(),
+            max_hp: Vec::new(),
+            damage: HashMap::new(),
+            names: HashMap::new(),
             is_generated: false,
             is_modified_in_session: false,
         }
     }
 
-    pub fn get_block(&mut self, rel_x: usize, rel_y: usize) -> Option<&mut Block> {
-        if !self.is_generated {
-            return None;
+    /// An all-air chunk for the generator to fill in.
+    #[must_use]
+    pub fn generated(cx: i32, cy: i32) -> Self {
+        Self {
+            palette: vec![BlockType::Air],
+            front: vec![0; CHUNK_AREA],
+            back: vec![0; CHUNK_AREA],
+            liquid_levels: vec![0; CHUNK_AREA],
+            max_hp: vec![0; CHUNK_AREA],
+            is_generated: true,
+            ..Self::new(cx, cy)
         }
-        if rel_x < CHUNK_SIZE_X_BLOCKS && rel_y < CHUNK_SIZE_Y_BLOCKS {
-            Some(&mut self.blocks[rel_x][rel_y])
+    }
+
+    fn index(&self, rel_x: usize, rel_y: usize) -> Option<usize> {
+        (self.is_generated && rel_x < CHUNK_SIZE_X_BLOCKS && rel_y < CHUNK_SIZE_Y_BLOCKS)
+            .then_some(rel_x * CHUNK_SIZE_Y_BLOCKS + rel_y)
+    }
+
+    fn palette_index(&mut self, block_type: BlockType) -> u16 {
+        let index = self
+            .palette
+            .iter()
+            .position(|&t| t == block_type)
+            .unwrap_or_else(|| {
+                self.palette.push(block_type);
+                self.palette.len() - 1
+            });
+        index.to_u16().unwrap_or(0)
+    }
+
+    fn block_at_index(&self, index: usize) -> Block {
+        let rel_x = index / CHUNK_SIZE_Y_BLOCKS;
+        let rel_y = index % CHUNK_SIZE_Y_BLOCKS;
+        let origin_bx = self.cx * CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0);
+        let origin_by = self.cy * CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0);
+        let damage = self.damage.get(&index.to_u16().unwrap_or(0));
+        let max_hp = self.max_hp[index];
+        Block {
+            x: (origin_bx + rel_x.to_i32().unwrap_or(0))
+                .to_f32()
+                .unwrap_or(0.0)
+                * BLOCK_SIZE,
+            y: (origin_by + rel_y.to_i32().unwrap_or(0))
+                .to_f32()
+                .unwrap_or(0.0)
+                * BLOCK_SIZE,
+            max_hp,
+            current_hp: damage.map_or(max_hp, |d| d.current_hp),
+            block_type: self.palette[usize::from(self.front[index])],
+            back_type: self.palette[usize::from(self.back[index])],
+            last_damage_time: damage.and_then(|d| d.last_damage_time),
+            liquid_level: self.liquid_levels[index],
+        }
+    }
+
+    #[must_use]
+    pub fn get_block(&self, rel_x: usize, rel_y: usize) -> Option<Block> {
+        self.index(rel_x, rel_y).map(|i| self.block_at_index(i))
+    }
+
+    /// Only the type of a block, without building the whole view.
+    #[must_use]
+    pub fn block_type(&self, rel_x: usize, rel_y: usize) -> Option<BlockType> {
+        self.index(rel_x, rel_y)
+            .map(|i| self.palette[usize::from(self.front[i])])
+    }
+
+    /// Stores `block` in cell `(rel_x, rel_y)`. Air keeps no hit points or damage.
+    pub fn set_block(&mut self, rel_x: usize, rel_y: usize, block: &Block) {
+        let Some(index) = self.index(rel_x, rel_y) else {
+            return;
+        };
+        self.front[index] = self.palette_index(block.block_type);
+        self.back[index] = self.palette_index(block.back_type);
+        self.liquid_levels[index] = block.liquid_level;
+
+        let key = index.to_u16().unwrap_or(0);
+        if block.block_type == BlockType::Air {
+            self.max_hp[index] = 0;
+            self.damage.remove(&key);
         } else {
-            None
+            self.max_hp[index] = block.max_hp;
+            if block.current_hp == block.max_hp && block.last_damage_time.is_none() {
+                self.damage.remove(&key);
+            } else {
+                self.damage.insert(
+                    key,
+                    BlockDamage {
+                        current_hp: block.current_hp,
+                        last_damage_time: block.last_damage_time,
+                    },
+                );
+            }
         }
     }
+
+    #[must_use]
+    pub fn name(&self, rel_x: usize, rel_y: usize) -> Option<&str> {
+        let index = self.index(rel_x, rel_y)?.to_u16()?;
+        self.names.get(&index).map(String::as_str)
+    }
+
+    pub fn set_name(&mut self, rel_x: usize, rel_y: usize, name: Option<String>) {
+        let Some(index) = self.index(rel_x, rel_y).and_then(|i| i.to_u16()) else {
+            return;
+        };
+        match name {
+            Some(name) => self.names.insert(index, name),
+            None => self.names.remove(&index),
+        };
+    }
+
+    /// Every block of the chunk, column by column.
+    pub fn blocks(&self) -> impl Iterator<Item = Block> + '_ {
+        let count = if self.is_generated { CHUNK_AREA } else { 0 };
+        (0..count).map(|i| self.block_at_index(i))
+    }
+
+    /// Mends blocks left alone for `heal_after` seconds since they were last drilled.
+    pub fn heal_damage(&mut self, now: f64, heal_after: f64) {
+        self.damage.retain(|_, d| {
+            d.last_damage_time
+                .is_none_or(|last_time| now - last_time < heal_after)
+        });
+    }
 }
 
 pub struct MacroGrid {
-    pub chunks: std::collections::HashMap<BlockPos, Chunk>,
+    pub chunks: HashMap<BlockPos, Chunk>,
 }
 
 impl Default for MacroGrid {
@@ -184,7 +323,7 @@ impl MacroGrid {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            chunks: std::collections::HashMap::new(),
+            chunks: HashMap::new(),
         }
     }
 }
@@ -218,6 +357,8 @@ pub struct Player {
     pub cargo_level: i32,
     pub heat_resistance_level: i32,
     pub warp_gates: Vec<WarpGate>,
+    /// Block ids of the fossils and artifacts catalogued so far.
+    pub collection: BTreeSet<u32>,
 }
 
 impl Player {
@@ -245,6 +386,7 @@ impl Player {
             cargo_level: 1,
             heat_resistance_level: 1,
             warp_gates: Vec::new(),
+            collection: BTreeSet::new(),
         }
     }
 
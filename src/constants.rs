@@ -48,11 +48,74 @@ pub const SELECTION_ENLARGE_AMOUNT: f32 = 1.0; // How much the selection sprite
 
 // World Generation Constants
 pub const SURFACE_Y_LEVEL: i32 = 7;
-pub const NOISE_SCALE_ORE: f64 = 0.0286;
-pub const ORE_THRESHOLD: f64 = 0.364;
 
 pub const HARDNESS_DEPTH_MULTIPLIER: f64 = 0.05; // 5% increase per block depth
 
+// Tectonic Plates (TGIP Phase 1)
+pub const PLATE_SIZE_MACROGRIDS: f32 = 5.0; // Average plate width in macrogrid cells
+pub const PLATE_VERTICAL_STRETCH: f32 = 4.0; // Plates are taller than wide, so boundaries are steep
+pub const CONTINENTAL_PLATE_RATIO: f32 = 0.6;
+pub const MOUNTAIN_UPLIFT_BLOCKS: f32 = 40.0;
+pub const VOLCANIC_ARC_UPLIFT_BLOCKS: f32 = 20.0;
+pub const TRENCH_DEPTH_BLOCKS: f32 = 24.0;
+pub const RIFT_DEPTH_BLOCKS: f32 = 12.0;
+pub const SPAWN_FLAT_RADIUS_BLOCKS: f32 = 24.0; // Terrain is kept flat around the home gate
+pub const SPAWN_FLAT_BLEND_BLOCKS: f32 = 48.0;
+
+// Surface Heightmap (TGIP Phase 2)
+pub const NOISE_SCALE_HILLS: f64 = 0.012;
+pub const HILL_OCTAVES: u32 = 4;
+pub const HILL_AMPLITUDE_BLOCKS: f32 = 14.0;
+pub const NOISE_SCALE_VALLEY: f64 = 0.004;
+pub const VALLEY_DEPTH_BLOCKS: f32 = 10.0;
+pub const NOISE_SCALE_CLIFF: f64 = 0.006;
+pub const CLIFF_THRESHOLD: f64 = 0.25; // Stretches above this are terraced into cliffs
+pub const CLIFF_STEP_BLOCKS: f32 = 6.0;
+
+// Erosion (TGIP Phase 4)
+pub const EROSION_PREPARE_RADIUS_MACROGRIDS: i32 = 2; // Macrogrids eroded around spawn at world creation
+
+// Climate (TGIP Phases 3 and 10)
+pub const LATITUDE_PERIOD_MACROGRIDS: f32 = 32.0; // Distance along X from one equator to the next
+pub const SPAWN_LATITUDE: f32 = 0.35; // 0.0 = equator, 1.0 = pole
+pub const EQUATOR_TEMPERATURE: f32 = 30.0;
+pub const POLE_TEMPERATURE: f32 = -12.0;
+pub const LAPSE_RATE: f32 = 0.3; // Degrees lost per block of elevation
+pub const OCEAN_HUMIDITY: f32 = 0.9;
+pub const INLAND_HUMIDITY: f32 = 0.3;
+pub const RAIN_SHADOW_REACH_MACROGRIDS: i32 = 3; // How far upwind mountains block moisture
+pub const RAIN_SHADOW_STRENGTH: f32 = 0.6;
+pub const OROGRAPHIC_BONUS: f32 = 0.25; // Extra humidity on slopes facing the wind
+pub const NOISE_SCALE_CLIMATE: f64 = 0.003;
+pub const CLIMATE_NOISE_TEMPERATURE: f32 = 4.0;
+pub const CLIMATE_NOISE_HUMIDITY: f32 = 0.12;
+
+// Aquifers (TGIP Phase 11)
+pub const PUMP_COST: i32 = 300;
+pub const PUMP_TICK_INTERVAL: u32 = 10; // Frames between pump strokes
+pub const PUMP_DRAIN_PER_TICK: u32 = 2; // Liquid levels removed per stroke from each side
+
+// Chunk Streaming
+pub const GENERATION_WORKERS_MAX: usize = 4; // Upper bound on chunk generation threads
+pub const PREFETCH_LOOKAHEAD_FRAMES: f32 = 45.0; // How far ahead of the player's velocity chunks are prefetched
+pub const PREFETCH_MARGIN_CHUNKS: i32 = 1; // Extra ring of chunks prefetched around the view
+pub const PLAYER_SYNC_MARGIN_BLOCKS: f32 = 2.0; // Chunks this close to the player are generated immediately
+pub const CHUNK_MEMORY_CAP_MB: usize = 48; // Default budget for loaded chunks before the farthest, oldest are unloaded
+pub const EVICTION_TARGET_FRACTION: f32 = 0.8; // Unloading stops once usage falls to this share of the cap
+pub const EVICTION_DISTANCE_WEIGHT: f32 = 30.0; // Frames of idleness one chunk of distance counts as
+
+// Warp Gates
+pub const WARP_GATE_ITEM_TYPE: &str = "WarpGate"; // Cargo item the shop sells, placed as a named gate
+
+// Saving
+pub const AUTOSAVE_INTERVAL_PRESETS_MINUTES: [i32; 4] = [0, 2, 5, 10]; // 0 turns autosave off
+pub const AUTOSAVE_DEFAULT_MINUTES: i32 = 5;
+pub const AUTOSAVE_MIN_GAP_SECONDS: f64 = 30.0; // Surfacing or warping autosaves no more often than this
+pub const SAVE_BACKUP_COUNT: usize = 3; // Earlier versions kept of every save file
+pub const THUMBNAIL_WIDTH: usize = 24; // Pixels in the minimap stored with each save
+pub const THUMBNAIL_HEIGHT: usize = 16;
+pub const THUMBNAIL_STEP_BLOCKS: i32 = 2; // Blocks between the samples of neighbouring pixels
+
 // Particle Constants
 pub const GRAVITY: f32 = 0.19;
 pub const MAX_LIFESPAN_ON_GROUND_SEC: f64 = 5.0;
@@ -77,6 +140,9 @@ pub const PLAYER_TERMINAL_YVELOCITY: f32 = 2.25;
 pub const LIQUID_RESISTANCE: f32 = 0.8;
 pub const LIQUID_BUOYANCY: f32 = 0.15;
 
+// Falling Blocks (TGIP Phase 10)
+pub const MAX_FALL_BLOCKS: i32 = 64; // Furthest a falling block drops in one go
+
 // Sprites
 pub const SPRITE_SELECT_NORMAL: Rect = Rect {
     x: 24.0,
@@ -105,3 +171,7 @@ pub const SURFACE_TEMPERATURE: f32 = 25.0;
 pub const TEMPERATURE_GRADIENT: f32 = 0.1; // Degrees increase per block depth
 pub const TEMPERATURE_DEBUFF_THRESHOLD: f32 = 35.0;
 pub const HEAT_RESISTANCE_STEP: f32 = 5.0; // How many degrees each resistance level mitigates
+pub const HEAT_RADIUS_BLOCKS: i32 = 5; // How far the heat of lava and other hot blocks reaches
+pub const TEMPERATURE_CACHE_MAX: usize = 4096; // Block temperatures remembered before the cache starts over
+pub const OCEANIC_GRADIENT_BONUS: f32 = 1.0; // Extra gradient under thin oceanic crust, as a fraction of the base
+pub const BOUNDARY_GRADIENT_BONUS: f32 = 0.8; // Extra gradient at full plate boundary stress
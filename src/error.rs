@@ -12,6 +12,16 @@ pub enum PersistenceError {
     Serialization(serde_json::Error),
     Decompression(std::io::Error), // zstd のエラーも含む
     InvalidFormat,
+    /// Written by a newer version of the game than this one.
+    TooNew {
+        version: u32,
+    },
+    /// Readable JSON whose contents make no sense as a save.
+    Corrupt(String),
+    /// A save slot name with characters a file name should not have.
+    InvalidName(String),
+    /// Another save already uses the name.
+    SlotExists(String),
 }
 
 impl fmt::Display for GameError {
@@ -29,6 +39,24 @@ impl fmt::Display for PersistenceError {
             Self::Serialization(e) => write!(f, "Serialization error: {e}"),
             Self::Decompression(e) => write!(f, "Decompression error: {e}"),
             Self::InvalidFormat => write!(f, "Invalid save file format"),
+            Self::TooNew { version } => write!(
+                f,
+                "Save version {version} is newer than this game supports ({})",
+                crate::managers::persistence::SAVE_VERSION
+            ),
+            Self::Corrupt(reason) => write!(f, "Corrupt save: {reason}"),
+            Self::InvalidName(name) => write!(f, "Invalid save name: \"{name}\""),
+            Self::SlotExists(name) => write!(f, "A save named \"{name}\" already exists"),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) | Self::Decompression(e) => Some(e),
+            Self::Serialization(e) => Some(e),
+            _ => None,
         }
     }
 }
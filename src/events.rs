@@ -1,9 +1,22 @@
+use crate::managers::world::settings::WorldOption;
+
 pub enum GameEvent {
     OpenSaveSelection,
     LoadSave(String),
     StartNewGameSetup,
     ConfirmNewGame(String),
+    FocusSeedInput(bool),
+    CycleWorldOption(WorldOption),
     SaveGame,
+    CycleAutosave,
+    RestoreBackup(String),
+    SelectSave(String),
+    CycleSaveSort,
+    StartRenameSave,
+    ConfirmRenameSave(String),
+    CancelRenameSave,
+    DuplicateSave,
+    DeleteSave,
     QuitGame,
     ReturnToTitle,
     ReturnToTitleFromSaveSelect,
@@ -13,6 +26,7 @@ pub enum GameEvent {
     UpgradeCargo,
     UpgradeHeatResistance,
     BuyWarpGate,
+    BuyPump,
     StartPlaceWarpGate,
     ConfirmWarpGateName(String),
     OpenWarpMenu,
@@ -13,7 +13,24 @@ impl Game {
             GameEvent::ConfirmNewGame(name) => {
                 handlers::menu::confirm_new_game(self, &name, game_renderer);
             }
+            GameEvent::FocusSeedInput(is_seed) => handlers::menu::focus_seed_input(self, is_seed),
+            GameEvent::CycleWorldOption(option) => {
+                handlers::menu::cycle_world_option(self, option);
+            }
             GameEvent::SaveGame => handlers::menu::save_game(self),
+            GameEvent::CycleAutosave => handlers::menu::cycle_autosave(self),
+            GameEvent::RestoreBackup(name) => {
+                handlers::menu::restore_backup(self, &name, game_renderer);
+            }
+            GameEvent::SelectSave(name) => handlers::save_select::select_save(self, name),
+            GameEvent::CycleSaveSort => handlers::save_select::cycle_save_sort(self),
+            GameEvent::StartRenameSave => handlers::save_select::start_rename_save(self),
+            GameEvent::ConfirmRenameSave(name) => {
+                handlers::save_select::confirm_rename_save(self, &name, game_renderer);
+            }
+            GameEvent::CancelRenameSave => handlers::save_select::cancel_rename_save(self),
+            GameEvent::DuplicateSave => handlers::save_select::duplicate_save(self, game_renderer),
+            GameEvent::DeleteSave => handlers::save_select::delete_save(self, game_renderer),
             GameEvent::QuitGame => handlers::menu::quit_game(),
             GameEvent::ReturnToTitle => handlers::menu::return_to_title(self, game_renderer),
             GameEvent::ReturnToTitleFromSaveSelect => {
@@ -27,6 +44,7 @@ impl Game {
                 handlers::shop::upgrade_heat_resistance(self, game_renderer);
             }
             GameEvent::BuyWarpGate => handlers::shop::buy_warp_gate(self, game_renderer),
+            GameEvent::BuyPump => handlers::shop::buy_pump(self, game_renderer),
             GameEvent::StartPlaceWarpGate => handlers::warp::start_place_warp_gate(self),
             GameEvent::ConfirmWarpGateName(name) => {
                 handlers::warp::confirm_warp_gate_name(self, name, game_renderer);
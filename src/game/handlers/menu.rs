@@ -1,12 +1,16 @@
+use crate::constants::{
+    AUTOSAVE_DEFAULT_MINUTES, AUTOSAVE_INTERVAL_PRESETS_MINUTES, AUTOSAVE_MIN_GAP_SECONDS,
+};
 use crate::game::{Game, GameState, UIOverlay};
 use crate::managers::PersistenceManager;
+use crate::managers::world::settings::{WorldOption, WorldSettings, next_preset, seeds_from_text};
 use crate::render::game_renderer::GameRenderer;
+use macroquad::prelude::get_time;
 
 pub fn open_save_selection(game: &mut Game) {
-    game.save_files = PersistenceManager::list_save_files();
+    super::save_select::refresh_save_slots(game);
     if game.save_files.is_empty() {
-        game.state = GameState::NewGameInput;
-        game.input_buffer.clear();
+        start_new_game_setup(game);
     } else {
         game.state = GameState::SaveSelect;
     }
@@ -21,21 +25,38 @@ pub fn load_save(game: &mut Game, filename: String) {
 pub fn start_new_game_setup(game: &mut Game) {
     game.state = GameState::NewGameInput;
     game.input_buffer.clear();
+    game.seed_input.clear();
+    game.is_editing_seed = false;
+    game.new_world_settings = WorldSettings::default();
+}
+
+pub const fn focus_seed_input(game: &mut Game, is_seed: bool) {
+    game.is_editing_seed = is_seed;
+}
+
+pub fn cycle_world_option(game: &mut Game, option: WorldOption) {
+    game.new_world_settings.cycle(option);
 }
 
 pub fn confirm_new_game(game: &mut Game, name: &str, renderer: &GameRenderer) {
-    let mut filename = name.to_string();
-    if !std::path::Path::new(&filename)
-        .extension()
-        .is_some_and(|ext| ext.eq_ignore_ascii_case("dat"))
-    {
-        filename.push_str(".dat");
-    }
-    game.current_save_name = filename;
+    // Worlds are saved as directories; a typed `.dat` suffix is dropped.
+    game.current_save_name = name.strip_suffix(".dat").unwrap_or(name).to_string();
     game.state = GameState::Playing;
     game.input_buffer.clear();
     game.reset_player_state();
-    game.world_manager.seed(::rand::random(), ::rand::random());
+    let settings = game.new_world_settings;
+    game.player_manager.player.money = settings.starting_money;
+    let (seed_main, seed_ore) = if game.seed_input.trim().is_empty() {
+        (::rand::random(), ::rand::random())
+    } else {
+        seeds_from_text(game.seed_input.trim())
+    };
+    game.seed_input.clear();
+    game.autosave_minutes = AUTOSAVE_DEFAULT_MINUTES;
+    game.last_save_time = get_time();
+    game.play_time = 0.0;
+    game.max_depth = 0;
+    game.world_manager.seed(seed_main, seed_ore, settings);
     game.world_manager.generate_visible_chunks(0.0, 0.0);
 
     // Add Initial Warp Gate Registry
@@ -59,9 +80,49 @@ pub fn confirm_new_game(game: &mut Game, name: &str, renderer: &GameRenderer) {
 }
 
 pub fn save_game(game: &mut Game) {
+    if game.persistence_manager.is_saving {
+        return;
+    }
     let data = game.make_save_data();
+    let replace_regions = game.world_manager.needs_full_save();
+    game.world_manager
+        .begin_region_save(PersistenceManager::save_dir(&game.current_save_name));
+    let meta = game.make_save_meta();
     game.persistence_manager
-        .save_game(game.current_save_name.clone(), data);
+        .save_game(game.current_save_name.clone(), data, meta, replace_regions);
+    game.last_save_time = get_time();
+}
+
+/// Saves without being asked once the autosave interval has passed, or at a milestone
+/// such as surfacing or warping if the last save is `AUTOSAVE_MIN_GAP_SECONDS` old.
+pub fn autosave(game: &mut Game, is_milestone: bool) {
+    if game.autosave_minutes <= 0
+        || game.state != GameState::Playing
+        || game.persistence_manager.is_saving
+    {
+        return;
+    }
+    let since_save = get_time() - game.last_save_time;
+    if since_save >= f64::from(game.autosave_minutes) * 60.0
+        || (is_milestone && since_save >= AUTOSAVE_MIN_GAP_SECONDS)
+    {
+        save_game(game);
+        game.is_autosaving = true;
+    }
+}
+
+pub fn cycle_autosave(game: &mut Game) {
+    game.autosave_minutes = next_preset(&AUTOSAVE_INTERVAL_PRESETS_MINUTES, game.autosave_minutes);
+}
+
+pub fn restore_backup(game: &mut Game, name: &str, renderer: &GameRenderer) {
+    let (t, msg) = match PersistenceManager::restore_backup(name) {
+        Ok(count) => ("success", format!("Restored {count} file(s) from backup")),
+        Err(e) => ("error", e.to_string()),
+    };
+    game.notification_manager
+        .add_notification(&msg, t, renderer.get_font());
+    super::save_select::refresh_save_slots(game);
 }
 
 pub fn quit_game() {
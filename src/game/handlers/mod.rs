@@ -2,5 +2,6 @@ pub mod gameplay;
 pub mod inventory;
 pub mod map;
 pub mod menu;
+pub mod save_select;
 pub mod shop;
 pub mod warp;
@@ -0,0 +1,100 @@
+use crate::game::Game;
+use crate::managers::PersistenceManager;
+use crate::render::game_renderer::GameRenderer;
+
+/// Re-reads the save slots, keeping the selection if the slot still exists.
+pub fn refresh_save_slots(game: &mut Game) {
+    game.save_files = PersistenceManager::list_save_slots();
+    game.save_sort.sort(&mut game.save_files);
+    let selected = game
+        .selected_save
+        .take()
+        .filter(|name| game.save_files.iter().any(|slot| &slot.name == name))
+        .or_else(|| game.save_files.first().map(|slot| slot.name.clone()));
+    match selected {
+        Some(name) => select_save(game, name),
+        None => game.is_selected_save_damaged = false,
+    }
+}
+
+pub fn select_save(game: &mut Game, name: String) {
+    game.is_selected_save_damaged = PersistenceManager::is_save_damaged(&name);
+    game.selected_save = Some(name);
+    game.is_renaming_save = false;
+    game.is_confirming_delete = false;
+}
+
+pub fn cycle_save_sort(game: &mut Game) {
+    game.save_sort = game.save_sort.next();
+    game.save_sort.sort(&mut game.save_files);
+}
+
+pub fn start_rename_save(game: &mut Game) {
+    let Some(name) = &game.selected_save else {
+        return;
+    };
+    game.input_buffer = name.strip_suffix(".dat").unwrap_or(name).to_string();
+    game.is_renaming_save = true;
+    game.is_confirming_delete = false;
+}
+
+pub fn cancel_rename_save(game: &mut Game) {
+    game.is_renaming_save = false;
+    game.input_buffer.clear();
+}
+
+pub fn confirm_rename_save(game: &mut Game, new_name: &str, renderer: &GameRenderer) {
+    let Some(name) = game.selected_save.clone() else {
+        return;
+    };
+    match PersistenceManager::rename_save(&name, new_name.trim()) {
+        Ok(renamed) => {
+            game.selected_save = Some(renamed);
+            cancel_rename_save(game);
+            refresh_save_slots(game);
+        }
+        Err(e) => {
+            game.notification_manager
+                .add_notification(&e.to_string(), "error", renderer.get_font())
+        }
+    }
+}
+
+pub fn duplicate_save(game: &mut Game, renderer: &GameRenderer) {
+    let Some(name) = game.selected_save.clone() else {
+        return;
+    };
+    let copy_name = PersistenceManager::unused_copy_name(&name);
+    match PersistenceManager::duplicate_save(&name, &copy_name) {
+        Ok(copy) => {
+            game.selected_save = Some(copy);
+            refresh_save_slots(game);
+        }
+        Err(e) => {
+            game.notification_manager
+                .add_notification(&e.to_string(), "error", renderer.get_font())
+        }
+    }
+}
+
+/// Deletes the selected slot on the second press; the first only asks for confirmation.
+pub fn delete_save(game: &mut Game, renderer: &GameRenderer) {
+    let Some(name) = game.selected_save.clone() else {
+        return;
+    };
+    if !game.is_confirming_delete {
+        game.is_confirming_delete = true;
+        return;
+    }
+    match PersistenceManager::delete_save(&name) {
+        Ok(()) => {
+            game.selected_save = None;
+            game.is_confirming_delete = false;
+            refresh_save_slots(game);
+        }
+        Err(e) => {
+            game.notification_manager
+                .add_notification(&e.to_string(), "error", renderer.get_font())
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Game;
+use crate::constants::{PUMP_COST, WARP_GATE_ITEM_TYPE};
 use crate::render::game_renderer::GameRenderer;
 
 pub fn upgrade_drill(game: &mut Game, renderer: &GameRenderer) {
@@ -107,7 +108,7 @@ pub fn buy_warp_gate(game: &mut Game, renderer: &GameRenderer) {
                 .player
                 .cargo
                 .push(crate::components::OwnedItem {
-                    item_type: "WarpGate".to_string(),
+                    item_type: WARP_GATE_ITEM_TYPE.to_string(),
                     is_natural: false,
                     is_auto_stored: false,
                 });
@@ -129,6 +130,36 @@ pub fn buy_warp_gate(game: &mut Game, renderer: &GameRenderer) {
     }
 }
 
+pub fn buy_pump(game: &mut Game, renderer: &GameRenderer) {
+    if game.player_manager.player.money >= PUMP_COST {
+        if game.player_manager.player.cargo.len() < game.player_manager.player.max_cargo as usize {
+            game.player_manager.player.money -= PUMP_COST;
+            game.player_manager
+                .player
+                .cargo
+                .push(crate::components::OwnedItem {
+                    item_type: "pump".to_string(),
+                    is_natural: false,
+                    is_auto_stored: false,
+                });
+            game.notification_manager.add_notification(
+                "Pump Purchased!",
+                "success",
+                renderer.get_font(),
+            );
+        } else {
+            game.notification_manager
+                .add_notification("Cargo Full!", "error", renderer.get_font());
+        }
+    } else {
+        game.notification_manager.add_notification(
+            "Not enough money!",
+            "error",
+            renderer.get_font(),
+        );
+    }
+}
+
 use crate::game::UIOverlay;
 pub fn open_shop(game: &mut Game) {
     game.ui_overlay = UIOverlay::Shop;
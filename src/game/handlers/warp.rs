@@ -1,5 +1,5 @@
 use crate::components::BlockPos;
-use crate::constants::BLOCK_SIZE;
+use crate::constants::{BLOCK_SIZE, WARP_GATE_ITEM_TYPE};
 use crate::game::{Game, GameState, UIOverlay};
 use crate::render::game_renderer::GameRenderer;
 
@@ -15,7 +15,7 @@ pub fn confirm_warp_gate_name(game: &mut Game, name: String, renderer: &GameRend
         .player
         .cargo
         .iter()
-        .position(|it| it.item_type == "WarpGate")
+        .position(|it| it.item_type == WARP_GATE_ITEM_TYPE)
     {
         game.player_manager.player.cargo.remove(pos);
     }
@@ -41,19 +41,18 @@ pub fn confirm_warp_gate_name(game: &mut Game, name: String, renderer: &GameRend
         });
 
     // We also need to set the block in the world!
-    if let Some((cx, cy, _, _, block)) = game.world_manager.get_block_at_world_coords(wx, wy) {
+    if let Some((cx, cy, rel_x, rel_y, mut block)) =
+        game.world_manager.get_block_at_world_coords(wx, wy)
+        && let Some(chunk) = game.world_manager.get_chunk_mut(cx, cy)
+    {
         let bt = crate::components::BlockType::WarpGate;
         block.block_type = bt;
-        block.sprite_rect = bt.get_sprite();
         block.max_hp = bt.get_base_hardness();
         block.current_hp = block.max_hp;
-        block.is_broken = false;
-        block.is_modified = true;
-        block.name = Some(name);
-
-        if let Some(chunk) = game.world_manager.get_chunk_mut(cx, cy) {
-            chunk.is_modified_in_session = true;
-        }
+        block.last_damage_time = None;
+        chunk.set_block(rel_x, rel_y, &block);
+        chunk.set_name(rel_x, rel_y, Some(name));
+        game.world_manager.mark_chunk_modified(cx, cy);
     }
     game.warp_placement_target = None;
 
@@ -81,6 +80,7 @@ pub fn teleport_to_warp(game: &mut Game, idx: usize, renderer: &GameRenderer) {
             "success",
             renderer.get_font(),
         );
+        super::menu::autosave(game, true);
     }
 }
 
@@ -93,13 +93,12 @@ pub fn sync_warp_gates(game: &mut Game) {
             if !chunk.is_generated {
                 continue;
             }
-            for row in &chunk.blocks {
-                for block in row {
-                    if !block.is_broken
-                        && block.block_type == crate::components::BlockType::WarpGate
-                    {
-                        discovered.push((block.x, block.y, block.name.clone()));
-                    }
+            for block in chunk.blocks() {
+                if !block.is_broken() && block.block_type == crate::components::BlockType::WarpGate
+                {
+                    let (bx, by) = block.coords();
+                    let name = game.world_manager.block_name(bx, by).map(str::to_string);
+                    discovered.push((block.x, block.y, name));
                 }
             }
         }
@@ -1,5 +1,9 @@
 use crate::components::Camera;
-use crate::constants::{PLAYER_INITIAL_X, PLAYER_INITIAL_Y, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::constants::{
+    AUTOSAVE_DEFAULT_MINUTES, PLAYER_INITIAL_X, PLAYER_INITIAL_Y, SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use crate::managers::persistence::{SaveSlot, SaveSort};
+use crate::managers::world::settings::WorldSettings;
 use crate::managers::{
     ItemManager, LanguageManager, NotificationManager, ParticleManager, PersistenceManager,
     PlayerManager, WorldManager,
@@ -31,6 +35,7 @@ pub enum UIOverlay {
     Inventory,
     Warehouse,
     Map,
+    Collection,
 }
 
 pub struct Game {
@@ -50,9 +55,29 @@ pub struct Game {
     pub on_surface: bool,
 
     // Save/Load State
-    pub save_files: Vec<String>,
+    pub save_files: Vec<SaveSlot>,
+    pub save_sort: SaveSort,
+    /// Slot picked on the save-select screen, by name.
+    pub selected_save: Option<String>,
+    /// Whether the selected slot's header is damaged but has a backup.
+    pub is_selected_save_damaged: bool,
+    pub is_renaming_save: bool,
+    pub is_confirming_delete: bool,
     pub current_save_name: String,
+    /// Minutes between autosaves, 0 for off.
+    pub autosave_minutes: i32,
+    /// `get_time()` of the last save, whether asked for or automatic.
+    pub last_save_time: f64,
+    pub is_autosaving: bool,
+    /// Seconds played in this world, across sessions.
+    pub play_time: f64,
+    /// Deepest block row the player has reached.
+    pub max_depth: i32,
     pub input_buffer: String,
+    /// Seed typed on the new-game screen; empty for a random world.
+    pub seed_input: String,
+    pub is_editing_seed: bool,
+    pub new_world_settings: WorldSettings,
     pub warehouse_quantity: usize, // 1, 10, 100, or 0 for ALL
     pub selected_item_index: usize,
 
@@ -87,8 +112,21 @@ impl Game {
             ui_overlay: UIOverlay::None,
             on_surface: true,
             save_files: Vec::new(),
-            current_save_name: "savegame.dat".to_string(),
+            save_sort: SaveSort::default(),
+            selected_save: None,
+            is_selected_save_damaged: false,
+            is_renaming_save: false,
+            is_confirming_delete: false,
+            current_save_name: "savegame".to_string(),
+            autosave_minutes: AUTOSAVE_DEFAULT_MINUTES,
+            last_save_time: 0.0,
+            is_autosaving: false,
+            play_time: 0.0,
+            max_depth: 0,
             input_buffer: String::new(),
+            seed_input: String::new(),
+            is_editing_seed: false,
+            new_world_settings: WorldSettings::default(),
             warehouse_quantity: 1,
             selected_item_index: 0,
             map_zoom: 1.0,
@@ -131,13 +169,20 @@ impl Game {
             }
         }
 
+        for problem in std::mem::take(&mut self.world_manager.region_problems) {
+            self.notification_manager
+                .add_notification(&problem, "error", game_renderer.get_font());
+        }
         self.notification_manager.update();
 
         if let Some(res) = self.persistence_manager.check_save_status() {
+            self.world_manager.finish_region_save(res.is_ok());
             let (t, msg) = match res {
+                Ok(_) if self.is_autosaving => ("info", "Autosaved".to_string()),
                 Ok(msg) => ("success", msg),
                 Err(msg) => ("error", msg),
             };
+            self.is_autosaving = false;
             self.notification_manager
                 .add_notification(&msg, t, game_renderer.get_font());
         }
@@ -213,7 +258,7 @@ impl Game {
         self.ui_overlay = UIOverlay::None;
 
         self.world_manager.reset();
-        self.current_save_name = "savegame.dat".to_string();
+        self.current_save_name = "savegame".to_string();
         self.input_buffer = String::new();
         self.notification_manager.add_notification(
             "Returned to Title Screen",
@@ -1,10 +1,7 @@
 use super::Game;
-use crate::components::BlockPos;
 use crate::components::OwnedItem;
-use crate::constants::{CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, MACROGRID_SIZE_CHUNKS};
-use crate::managers::persistence::{
-    BlockSaveData, ChunkSaveData, ItemStack, SAVE_VERSION, SaveData,
-};
+use crate::constants::BLOCK_SIZE;
+use crate::managers::persistence::{AquiferSaveData, ItemStack, SAVE_VERSION, SaveData, SaveMeta};
 use num_traits::ToPrimitive;
 
 impl Game {
@@ -31,123 +28,20 @@ impl Game {
 
     #[must_use]
     pub fn make_save_data(&self) -> SaveData {
-        let mut modified_macrogrids: Vec<crate::managers::persistence::MacroGridSaveData> =
-            Vec::new();
-
-        for (BlockPos { x: mgx, y: mgy }, macrogrid) in &self.world_manager.macrogrids {
-            let mut chunks_in_mg: Vec<ChunkSaveData> = Vec::new();
-
-            for (
-                BlockPos {
-                    x: rel_cx,
-                    y: rel_cy,
-                },
-                chunk,
-            ) in &macrogrid.chunks
-            {
-                if chunk.is_modified_in_session {
-                    let cx = mgx * MACROGRID_SIZE_CHUNKS.to_i32().unwrap_or(0) + rel_cx;
-
-                    let cy = mgy * MACROGRID_SIZE_CHUNKS.to_i32().unwrap_or(0) + rel_cy;
-
-                    let mut rle_blocks: Vec<u32> = Vec::new();
-
-                    let mut named_blocks: Vec<BlockSaveData> = Vec::new();
-
-                    let mut last_type_id: Option<u32> = None;
-                    let mut last_level: u8 = 0;
-                    let mut current_count: u32 = 0;
-
-                    // Scan row-major (y then x) for better horizontal RLE runs
-                    for by in 0..CHUNK_SIZE_Y_BLOCKS {
-                        for bx in 0..CHUNK_SIZE_X_BLOCKS {
-                            let block = &chunk.blocks[bx][by];
-                            let type_id = block.block_type.to_id();
-                            let level = block.liquid_level;
-                            let index: u32 = (bx * CHUNK_SIZE_Y_BLOCKS + by)
-                                .to_u32()
-                                .unwrap_or(0)
-                                .to_u32()
-                                .unwrap_or(0);
-
-                            if let Some(name) = &block.name {
-                                named_blocks.push(BlockSaveData {
-                                    i: index,
-                                    t: block.block_type,
-                                    n: Some(name.clone()),
-                                });
-                            }
-
-                            if let (Some(l_id), l_lvl) = (last_type_id, last_level) {
-                                if l_id == type_id && l_lvl == level && current_count < u32::MAX {
-                                    current_count += 1;
-                                } else {
-                                    rle_blocks.push(l_id);
-                                    rle_blocks.push(u32::from(l_lvl));
-                                    rle_blocks.push(current_count);
-
-                                    last_type_id = Some(type_id);
-                                    last_level = level;
-                                    current_count = 1;
-                                }
-                            } else {
-                                last_type_id = Some(type_id);
-                                last_level = level;
-                                current_count = 1;
-                            }
-                        }
-                    }
-
-                    if let Some(l_id) = last_type_id {
-                        rle_blocks.push(l_id);
-                        rle_blocks.push(u32::from(last_level));
-                        rle_blocks.push(current_count);
-                    }
-
-                    chunks_in_mg.push(ChunkSaveData {
-                        cx,
-
-                        cy,
-
-                        blocks: rle_blocks,
-
-                        named_blocks,
-                    });
-                }
-            }
-
-            if !chunks_in_mg.is_empty() {
-                modified_macrogrids.push(crate::managers::persistence::MacroGridSaveData {
-                    mgx: *mgx,
-
-                    mgy: *mgy,
-
-                    chunks: chunks_in_mg,
-                });
-            }
-        }
-
-        // Add pending modifications (chunks that were not loaded in this session)
-
-        for chunk_data in self.world_manager.pending_modifications.values() {
-            let (mg_coords, _) =
-                crate::utils::chunk_to_macrogrid_coords(chunk_data.cx, chunk_data.cy);
-
-            if let Some(mg_data) = modified_macrogrids
-                .iter_mut()
-                .find(|mg| mg.mgx == mg_coords.x && mg.mgy == mg_coords.y)
-            {
-                mg_data.chunks.push(chunk_data.clone());
-            } else {
-                modified_macrogrids.push(crate::managers::persistence::MacroGridSaveData {
-                    mgx: mg_coords.x,
-
-                    mgy: mg_coords.y,
-
-                    chunks: vec![chunk_data.clone()],
-                });
-            }
-        }
+        // Only regions changed since the last save are written; the rest stay on disk.
+        let modified_macrogrids = self.world_manager.dirty_region_save_data();
+
+        let mut aquifer_reserves: Vec<AquiferSaveData> = self
+            .world_manager
+            .aquifer_reserves
+            .iter()
+            .map(|(pos, &reserve)| AquiferSaveData {
+                x: pos.x,
+                y: pos.y,
+                reserve,
+            })
+            .collect();
+        aquifer_reserves.sort_unstable_by(|a, b| a.x.cmp(&b.x).then(a.y.cmp(&b.y)));
 
         SaveData {
             version: SAVE_VERSION,
@@ -182,15 +76,63 @@ impl Game {
 
             player_cargo_level: self.player_manager.player.cargo_level,
 
+            player_heat_resistance_level: self.player_manager.player.heat_resistance_level,
+
+            player_vx: self.player_manager.player.vx,
+
+            player_vy: self.player_manager.player.vy,
+
             player_warp_gates: self.player_manager.player.warp_gates.clone(),
 
             world_seed_main: self.world_manager.world_seed_main,
 
             world_seed_ore: self.world_manager.world_seed_ore,
 
+            world_settings: *self.world_manager.settings(),
+
             visited_chunks: self.world_manager.visited_chunks.clone(),
 
             modified_macrogrids,
+
+            aquifer_reserves,
+
+            autosave_minutes: self.autosave_minutes,
+
+            play_time_seconds: self.play_time,
+
+            max_depth: self.max_depth,
+
+            collection: self
+                .player_manager
+                .player
+                .collection
+                .iter()
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Summary shown for this world on the save-select screen.
+    #[must_use]
+    pub fn make_save_meta(&self) -> SaveMeta {
+        let player = &self.player_manager.player;
+        let block_x = ((player.x + player.width / 2.0) / BLOCK_SIZE)
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+        let block_y = ((player.y + player.height / 2.0) / BLOCK_SIZE)
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+        SaveMeta {
+            display_name: self.current_save_name.clone(),
+            play_time_seconds: self.play_time,
+            last_saved: 0,
+            money: player.money,
+            max_depth: self.max_depth,
+            world_seed_main: self.world_manager.world_seed_main,
+            world_seed_ore: self.world_manager.world_seed_ore,
+            thumbnail: self.world_manager.thumbnail(block_x, block_y),
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::Game;
 use crate::components::BlockType;
 use crate::constants::{
-    BLOCK_SIZE, CAMERA_DEADZONE_RADIUS, SCREEN_HEIGHT, SCREEN_WIDTH, SURFACE_Y_LEVEL,
+    BLOCK_SIZE, CAMERA_DEADZONE_RADIUS, FRAME_TIME, SCREEN_HEIGHT, SCREEN_WIDTH,
 };
 use crate::game::UIOverlay;
 use crate::render::game_renderer::GameRenderer;
@@ -17,8 +17,20 @@ pub fn handle_gameplay_update(game: &mut Game, game_renderer: &GameRenderer) {
 
     update_camera(game);
 
-    game.on_surface = game.player_manager.player.y
-        < (SURFACE_Y_LEVEL.to_f32().unwrap_or(0.0)).mul_add(BLOCK_SIZE, 8.0);
+    let player = &game.player_manager.player;
+    let column = ((player.x + player.width / 2.0) / BLOCK_SIZE)
+        .floor()
+        .to_i32()
+        .unwrap_or(0);
+    let surface_y = game.world_manager.surface_height_at(column);
+    game.play_time += f64::from(FRAME_TIME);
+    game.max_depth = game.max_depth.max(
+        game.world_manager
+            .depth_below_surface(player.x + player.width / 2.0, player.y),
+    );
+    let was_on_surface = game.on_surface;
+    game.on_surface = player.y < (surface_y.to_f32().unwrap_or(0.0)).mul_add(BLOCK_SIZE, 8.0);
+    crate::game::handlers::menu::autosave(game, game.on_surface && !was_on_surface);
 
     update_world(game);
 
@@ -50,6 +62,11 @@ fn update_ui_state(game: &mut Game) {
         game.clear_inputs();
     }
 
+    if game.is_key_pressed_buffered(KeyCode::C) && game.ui_overlay == UIOverlay::None {
+        game.ui_overlay = UIOverlay::Collection;
+        game.clear_inputs();
+    }
+
     if game.is_key_pressed_buffered(KeyCode::M) && game.ui_overlay == UIOverlay::None {
         game.ui_overlay = UIOverlay::Map;
         game.map_view_x = game.player_manager.player.x;
@@ -80,10 +97,16 @@ fn update_camera(game: &mut Game) {
 }
 
 fn update_world(game: &mut Game) {
-    game.world_manager
-        .generate_visible_chunks(game.camera.x, game.camera.y);
+    let player = &game.player_manager.player;
+    game.world_manager.stream_chunks(
+        game.camera.x,
+        game.camera.y,
+        player.rect(),
+        vec2(player.vx, player.vy),
+    );
     game.world_manager
         .update_liquids(game.camera.x, game.camera.y);
+    game.world_manager.update_pumps();
     game.world_manager.update();
 }
 
@@ -134,7 +157,7 @@ fn update_interaction_preview(game: &mut Game, world_mx: f32, world_my: f32) {
                 preview_sprite = Some(sprite);
                 is_valid = true;
             }
-        } else if block.is_broken {
+        } else if block.is_broken() {
             // Placing a block
             preview_sprite = Some(sprite);
             let block_rect = Rect::new(block.x, block.y, BLOCK_SIZE, BLOCK_SIZE);
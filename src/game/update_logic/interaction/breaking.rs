@@ -1,8 +1,10 @@
 use crate::Game;
 use crate::components::{BlockPos, BlockType, Particle};
 use crate::constants::{BLOCK_SIZE, HEAT_RESISTANCE_STEP, TEMPERATURE_DEBUFF_THRESHOLD};
+use crate::managers::collection::{CatalogueResult, catalogue};
+use crate::managers::world::WorldManager;
 use crate::render::game_renderer::GameRenderer;
-use crate::utils::{get_temperature, world_to_chunk_coords};
+use crate::utils::world_to_chunk_coords;
 use ::rand::Rng;
 use macroquad::prelude::*;
 use num_traits::ToPrimitive;
@@ -20,13 +22,28 @@ pub fn handle_block_interaction(
     game.world_manager
         .ensure_chunk_exists_and_generated(target_cx, target_cy);
 
+    let player = &game.player_manager.player;
+    let player_column = ((player.x + player.width / 2.0) / BLOCK_SIZE)
+        .floor()
+        .to_i32()
+        .unwrap_or(0);
+    let player_row = (player.y / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
+    let player_temperature = game.world_manager.temperature_at(player_column, player_row);
+
+    // Frozen ground softens once the block reaches its thaw point, e.g. next to lava.
+    let target_temperature = game.world_manager.temperature_at(
+        (world_mx / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+        (world_my / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+    );
+
     let mut should_mark_modified = false;
     let mut liquid_to_activate = Vec::new();
+    let mut broken_at = None;
 
-    if let Some((_, _, _, _, block)) = game
+    if let Some((_, _, _, _, mut block)) = game
         .world_manager
         .get_block_at_world_coords(world_mx, world_my)
-        .filter(|(_, _, _, _, b)| !b.is_broken)
+        .filter(|(_, _, _, _, b)| !b.is_broken())
     {
         if block.block_type.is_liquid() {
             let old_type = block.block_type;
@@ -62,10 +79,7 @@ pub fn handle_block_interaction(
                 block.liquid_level -= 1;
                 if block.liquid_level == 0 {
                     block.block_type = BlockType::Air;
-                    block.is_broken = true;
-                    block.sprite_rect = None;
                 }
-                block.is_modified = true;
                 should_mark_modified = true;
 
                 liquid_to_activate.push((bx, by - 1));
@@ -75,12 +89,11 @@ pub fn handle_block_interaction(
                 liquid_to_activate.push((bx, by));
             }
         } else if block.max_hp != -1 {
-            let temp = get_temperature(game.player_manager.player.y);
             let resistance = (game.player_manager.player.heat_resistance_level - 1)
                 .to_f32()
                 .unwrap_or(0.0)
                 * HEAT_RESISTANCE_STEP;
-            let effective_temp = temp - resistance;
+            let effective_temp = player_temperature - resistance;
 
             let mut drill_power = game
                 .player_manager
@@ -95,8 +108,14 @@ pub fn handle_block_interaction(
                 drill_power -= excess;
             }
 
+            let mut damage = drill_power.max(1.0);
+            if let Some(thaw) = block.block_type.get_thaw()
+                && target_temperature >= thaw.temperature
+            {
+                damage /= thaw.hardness_multiplier.max(f32::EPSILON);
+            }
             {
-                block.current_hp -= drill_power.max(1.0).to_i32().unwrap_or(0);
+                block.current_hp -= damage.to_i32().unwrap_or(0);
             }
             block.last_damage_time = Some(get_time());
 
@@ -104,7 +123,7 @@ pub fn handle_block_interaction(
                 should_mark_modified = true;
 
                 // Break block
-                let old_sprite_rect = block.sprite_rect;
+                let old_sprite_rect = block.sprite_rect();
                 let old_block_type = block.block_type;
                 let block_x = block.x;
                 let block_y = block.y;
@@ -124,8 +143,6 @@ pub fn handle_block_interaction(
                 }
 
                 block.current_hp = 0;
-                block.is_broken = true;
-                block.is_modified = true;
                 block.block_type = BlockType::Air;
 
                 let bx = (block_x / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
@@ -134,6 +151,7 @@ pub fn handle_block_interaction(
                 liquid_to_activate.push((bx, by + 1));
                 liquid_to_activate.push((bx - 1, by));
                 liquid_to_activate.push((bx + 1, by));
+                broken_at = Some((bx, by));
 
                 spawn_break_particles(
                     &mut game.particle_manager,
@@ -143,7 +161,40 @@ pub fn handle_block_interaction(
                     game_renderer,
                 );
 
-                if let Some(it) = old_block_type.get_data().and_then(|d| d.item_type.clone())
+                // Fossils and artifacts go to the collection log instead of the cargo.
+                if let Some(result) =
+                    catalogue(&mut game.player_manager.player.collection, old_block_type)
+                {
+                    let name = old_block_type.get_data().map_or_else(String::new, |d| {
+                        game.lang_manager
+                            .get_string(&format!("block.{}.name", d.key))
+                    });
+                    let (message, kind) = match result {
+                        CatalogueResult::Duplicate => {
+                            (format!("{name} already catalogued"), "info")
+                        }
+                        CatalogueResult::New(set) => (
+                            format!(
+                                "Catalogued: {name} ({}/{})",
+                                set.found_count(&game.player_manager.player.collection),
+                                set.items.len()
+                            ),
+                            "success",
+                        ),
+                        CatalogueResult::SetCompleted(set) => {
+                            game.player_manager.player.money += set.bonus;
+                            let set_name = game
+                                .lang_manager
+                                .get_string(&format!("collection.{}.name", set.key));
+                            (format!("{set_name} complete! +${}", set.bonus), "success")
+                        }
+                    };
+                    game.notification_manager.add_notification(
+                        &message,
+                        kind,
+                        game_renderer.get_font(),
+                    );
+                } else if let Some(it) = old_block_type.get_data().and_then(|d| d.item_type.clone())
                     && let Some(rect) = old_sprite_rect
                 {
                     game.item_manager
@@ -151,6 +202,14 @@ pub fn handle_block_interaction(
                 }
             }
         }
+        game.world_manager.set_block(&block);
+    }
+
+    // Sand and other loose blocks above the hole fall into it.
+    if let Some((bx, by)) = broken_at {
+        for (fx, fy) in game.world_manager.drop_falling_blocks(bx, by) {
+            WorldManager::activate_neighbors(fx, fy, &mut game.world_manager.active_liquids);
+        }
     }
 
     for (lx, ly) in liquid_to_activate {
@@ -159,10 +218,28 @@ pub fn handle_block_interaction(
             .insert(BlockPos::new(lx, ly));
     }
 
-    if should_mark_modified
-        && let Some(chunk) = game.world_manager.get_chunk_mut(target_cx, target_cy)
-    {
-        chunk.is_modified_in_session = true;
+    // Opening into an aquifer lets its reservoir pour in through `update_liquids`.
+    if let Some((bx, by)) = broken_at {
+        let world = &game.world_manager;
+        let breached = [(0, -1), (0, 1), (-1, 0), (1, 0)].iter().any(|&(dx, dy)| {
+            world
+                .get_block(bx + dx, by + dy)
+                .is_some_and(|b| b.block_type == BlockType::Water)
+                && world
+                    .aquifer_at(bx + dx, by + dy)
+                    .is_some_and(|reservoir| world.aquifer_reserve(reservoir) > 0)
+        });
+        if breached {
+            game.notification_manager.add_notification(
+                "Aquifer Breached!",
+                "error",
+                game_renderer.get_font(),
+            );
+        }
+    }
+
+    if should_mark_modified {
+        game.world_manager.mark_chunk_modified(target_cx, target_cy);
     }
 }
 
@@ -12,12 +12,13 @@ pub fn try_interact_functional_block(
     if let Some((_, _, _, _, block)) = game
         .world_manager
         .get_block_at_world_coords(world_mx, world_my)
-        && !block.is_broken
+        && !block.is_broken()
         && block.block_type == BlockType::WarpGate
     {
         let block_x = block.x;
         let block_y = block.y;
-        let block_name = block.name.clone();
+        let (bx, by) = block.coords();
+        let block_name = game.world_manager.block_name(bx, by).map(str::to_string);
 
         if !game
             .player_manager
@@ -2,8 +2,9 @@ use super::breaking::spawn_break_particles;
 use super::functional::try_interact_functional_block;
 use crate::Game;
 use crate::components::{BlockPos, BlockType};
-use crate::constants::{BLOCK_SIZE, HARDNESS_DEPTH_MULTIPLIER, SURFACE_Y_LEVEL};
+use crate::constants::{BLOCK_SIZE, HARDNESS_DEPTH_MULTIPLIER};
 use crate::events::GameEvent;
+use crate::managers::world::WorldManager;
 use crate::render::game_renderer::GameRenderer;
 use crate::utils::world_to_chunk_coords;
 use macroquad::prelude::*;
@@ -50,10 +51,15 @@ fn try_place_block(
     }
 
     let player_rect = game.player_manager.player.rect();
+    let surface_y = game
+        .world_manager
+        .surface_height_at((world_mx / BLOCK_SIZE).floor().to_i32().unwrap_or(0));
 
     let mut liquid_to_activate = Vec::new();
+    let mut placed_pump = None;
+    let mut placed_falling = None;
 
-    if let Some((_, _, _, _, block)) = game
+    if let Some((_, _, _, _, mut block)) = game
         .world_manager
         .get_block_at_world_coords(world_mx, world_my)
     {
@@ -66,15 +72,16 @@ fn try_place_block(
         let mut placed = false;
 
         if bt.is_liquid() {
-            if (block.is_broken || block.block_type == bt) && block.liquid_level < 8 {
-                block.is_broken = false;
-                block.is_modified = true;
+            if (block.is_broken() || block.block_type == bt) && block.liquid_level < 8 {
+                if block.block_type != bt {
+                    block.max_hp = bt.get_base_hardness();
+                    block.current_hp = block.max_hp;
+                }
                 block.block_type = bt;
-                block.sprite_rect = bt.get_sprite();
                 block.liquid_level += 1;
                 placed = true;
             }
-        } else if (block.is_broken
+        } else if (block.is_broken()
             || block.block_type == BlockType::Air
             || block.block_type.is_liquid())
             && (!will_be_solid || !block_rect.overlaps(&player_rect))
@@ -86,25 +93,24 @@ fn try_place_block(
             }
 
             // Place standard block
-            block.is_broken = false;
-            block.is_modified = true;
             block.block_type = bt;
-            block.sprite_rect = bt.get_sprite();
 
             let hp = bt.get_base_hardness();
             let y_block = (block_y / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
-            let depth = (y_block - SURFACE_Y_LEVEL).max(0).to_f64().unwrap_or(0.0);
+            let depth = (y_block - surface_y).max(0).to_f64().unwrap_or(0.0);
             let multiplier = 1.0 + depth * HARDNESS_DEPTH_MULTIPLIER;
             block.max_hp = (hp.to_f64().unwrap_or(0.0) * multiplier)
                 .floor()
                 .to_i32()
                 .unwrap_or(0);
             block.current_hp = block.max_hp;
+            block.last_damage_time = None;
             block.liquid_level = 0;
             placed = true;
         }
 
         if placed {
+            game.world_manager.set_block(&block);
             let bx = (block_x / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
             let by = (block_y / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
             liquid_to_activate.push((bx, by - 1));
@@ -112,18 +118,22 @@ fn try_place_block(
             liquid_to_activate.push((bx - 1, by));
             liquid_to_activate.push((bx + 1, by));
             liquid_to_activate.push((bx, by));
+            if bt == BlockType::Pump {
+                placed_pump = Some(BlockPos::new(bx, by));
+            }
+            if bt.falls() {
+                placed_falling = Some((bx, by + 1));
+            }
 
             spawn_break_particles(
                 &mut game.particle_manager,
                 block_x,
                 block_y,
-                block.sprite_rect,
+                block.sprite_rect(),
                 game_renderer,
             );
 
-            if let Some(chunk) = game.world_manager.get_chunk_mut(cx, cy) {
-                chunk.is_modified_in_session = true;
-            }
+            game.world_manager.mark_chunk_modified(cx, cy);
 
             game.player_manager
                 .player
@@ -137,4 +147,13 @@ fn try_place_block(
             .active_liquids
             .insert(BlockPos::new(lx, ly));
     }
+    if let Some(pos) = placed_pump {
+        game.world_manager.pumps.insert(pos);
+    }
+    // A loose block placed over a gap drops straight down.
+    if let Some((bx, by)) = placed_falling {
+        for (fx, fy) in game.world_manager.drop_falling_blocks(bx, by) {
+            WorldManager::activate_neighbors(fx, fy, &mut game.world_manager.active_liquids);
+        }
+    }
 }
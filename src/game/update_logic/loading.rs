@@ -1,6 +1,9 @@
+use crate::components::BlockPos;
 use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
 use crate::game::{Game, GameState, UIOverlay};
+use crate::managers::persistence::{HEADER_FILE, PersistenceManager};
 use crate::render::game_renderer::GameRenderer;
+use macroquad::prelude::get_time;
 
 pub fn handle_loading(game: &mut Game, game_renderer: &GameRenderer) {
     if let Some(res) = game.persistence_manager.check_load_status() {
@@ -19,7 +22,18 @@ pub fn handle_loading(game: &mut Game, game_renderer: &GameRenderer) {
                 game.player_manager.player.tank_level = data.player_tank_level;
                 game.player_manager.player.engine_level = data.player_engine_level;
                 game.player_manager.player.cargo_level = data.player_cargo_level;
+                game.player_manager.player.heat_resistance_level =
+                    data.player_heat_resistance_level;
+                game.player_manager.player.vx = data.player_vx;
+                game.player_manager.player.vy = data.player_vy;
+                game.player_manager.player.old_x = data.player_x;
+                game.player_manager.player.old_y = data.player_y;
                 game.player_manager.player.warp_gates = data.player_warp_gates;
+                game.player_manager.player.collection = data.collection.into_iter().collect();
+                game.autosave_minutes = data.autosave_minutes;
+                game.play_time = data.play_time_seconds;
+                game.max_depth = data.max_depth;
+                game.last_save_time = get_time();
 
                 // Expand stacked items
                 let mut cargo = Vec::new();
@@ -52,10 +66,23 @@ pub fn handle_loading(game: &mut Game, game_renderer: &GameRenderer) {
                 game.camera.y = game.player_manager.player.y - SCREEN_HEIGHT / 2.0
                     + game.player_manager.player.height / 2.0;
 
-                game.world_manager
-                    .seed(data.world_seed_main, data.world_seed_ore);
+                game.world_manager.seed(
+                    data.world_seed_main,
+                    data.world_seed_ore,
+                    data.world_settings,
+                );
 
+                // Region files of a save directory are read as their chunks load.
+                let save_dir = PersistenceManager::save_dir(&game.current_save_name);
+                if save_dir.join(HEADER_FILE).is_file() {
+                    game.world_manager.region_dir = Some(save_dir);
+                }
                 game.world_manager.visited_chunks = data.visited_chunks;
+                game.world_manager.aquifer_reserves = data
+                    .aquifer_reserves
+                    .iter()
+                    .map(|a| (BlockPos::new(a.x, a.y), a.reserve))
+                    .collect();
 
                 // Apply modifications first (populates pending_modifications for lazy loading)
                 game.world_manager
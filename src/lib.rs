@@ -20,6 +20,7 @@
 
 pub mod components;
 pub mod constants;
+pub mod error;
 pub mod events;
 pub mod game;
 pub mod managers;
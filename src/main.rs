@@ -146,9 +146,18 @@ async fn main() {
 fn process_text_input(game: &mut Game) -> Vec<GameEvent> {
     use crate::game::GameState;
     let mut events = Vec::new();
-    if game.state == GameState::NewGameInput || game.state == GameState::WarpPlace {
+    let is_renaming_save = game.state == GameState::SaveSelect && game.is_renaming_save;
+    if game.state == GameState::NewGameInput
+        || game.state == GameState::WarpPlace
+        || is_renaming_save
+    {
+        let is_editing_seed = game.state == GameState::NewGameInput && game.is_editing_seed;
         while let Some(c) = get_char_pressed() {
-            if (game.state == GameState::NewGameInput
+            if is_editing_seed {
+                if (32..=126).contains(&u32::from(c)) {
+                    game.seed_input.push(c);
+                }
+            } else if ((game.state == GameState::NewGameInput || is_renaming_save)
                 && (c.is_alphanumeric() || c == '_' || c == '-'))
                 || (game.state == GameState::WarpPlace
                     && (u32::from(c) >= 32 && u32::from(c) <= 126))
@@ -157,11 +166,20 @@ fn process_text_input(game: &mut Game) -> Vec<GameEvent> {
             }
         }
         if is_key_pressed(KeyCode::Backspace) {
-            game.input_buffer.pop();
+            if is_editing_seed {
+                game.seed_input.pop();
+            } else {
+                game.input_buffer.pop();
+            }
+        }
+        if game.state == GameState::NewGameInput && is_key_pressed(KeyCode::Tab) {
+            events.push(GameEvent::FocusSeedInput(!is_editing_seed));
         }
         if is_key_pressed(KeyCode::Enter) {
             if game.state == GameState::NewGameInput {
                 events.push(GameEvent::ConfirmNewGame(game.input_buffer.clone()));
+            } else if is_renaming_save {
+                events.push(GameEvent::ConfirmRenameSave(game.input_buffer.clone()));
             } else {
                 events.push(GameEvent::ConfirmWarpGateName(game.input_buffer.clone()));
             }
@@ -1,4 +1,6 @@
-use macroquad::prelude::{BLACK, Color, Rect};
+use crate::constants::WARP_GATE_ITEM_TYPE;
+use crate::managers::resources::{NATURAL_RESOURCES, ResourceState};
+use macroquad::prelude::{BLACK, Color, Rect, WHITE};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -13,11 +15,13 @@ impl BlockType {
     pub const GRASS: Self = Self(2);
     pub const STONE: Self = Self(3);
     pub const INDESTRUCTIBLE: Self = Self(4);
+    pub const LAVA: Self = Self(30);
     pub const COAL: Self = Self(100);
     pub const OIL_SHALE: Self = Self(101);
     pub const LIMESTONE: Self = Self(102);
     pub const WATER: Self = Self(107);
     pub const WARP_GATE: Self = Self(500);
+    pub const PUMP: Self = Self(501);
 
     pub const Air: Self = Self::AIR;
     pub const Dirt: Self = Self::DIRT;
@@ -28,7 +32,9 @@ impl BlockType {
     pub const Limestone: Self = Self::LIMESTONE;
     pub const Water: Self = Self::WATER;
     pub const Indestructible: Self = Self::INDESTRUCTIBLE;
+    pub const Lava: Self = Self::LAVA;
     pub const WarpGate: Self = Self::WARP_GATE;
+    pub const Pump: Self = Self::PUMP;
 
     #[must_use]
     pub const fn to_id(self) -> u32 {
@@ -75,6 +81,26 @@ impl BlockType {
         BLOCK_MANAGER.get_map_color(self)
     }
 
+    #[must_use]
+    pub fn get_tint(&self) -> Color {
+        BLOCK_MANAGER.get_tint(self)
+    }
+
+    #[must_use]
+    pub fn falls(&self) -> bool {
+        self.get_data().is_some_and(|d| d.falls)
+    }
+
+    #[must_use]
+    pub fn get_thaw(&self) -> Option<ThawData> {
+        self.get_data().and_then(|d| d.thaw)
+    }
+
+    #[must_use]
+    pub fn get_heat(&self) -> Option<f32> {
+        self.get_data().and_then(|d| d.heat)
+    }
+
     #[must_use]
     pub fn from_item_type(item_type: &str) -> Option<Self> {
         BLOCK_MANAGER.get_by_item_type(item_type)
@@ -97,6 +123,25 @@ pub struct BlockData {
     pub tick_interval: u32,
     #[serde(default = "default_map_color")]
     pub map_color: [u8; 3],
+    /// Colour multiplied onto the sprite, so variants can share one atlas tile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tint: Option<[u8; 3]>,
+    /// Granular blocks drop into any space that opens up below them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub falls: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thaw: Option<ThawData>,
+    /// Degrees the block adds to the rock around it, fading out over `HEAT_RADIUS_BLOCKS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heat: Option<f32>,
+}
+
+/// Frozen ground that softens once its temperature reaches `temperature`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ThawData {
+    pub temperature: f32,
+    /// Scales the hardness the drill works against once thawed.
+    pub hardness_multiplier: f32,
 }
 
 const fn default_tick_interval() -> u32 {
@@ -146,7 +191,7 @@ impl BlockManager {
     }
 
     fn load_blocks(&mut self) {
-        let categories = ["solid", "liquid", "gas", "special"];
+        let categories = ["solid", "liquid", "gas", "special", "biome", "archaeology"];
         for category in categories {
             let path = format!("data/blocks/{category}.json");
             if let Ok(content) = fs::read_to_string(path)
@@ -163,6 +208,23 @@ impl BlockManager {
                 }
             }
         }
+        self.load_natural_resources();
+    }
+
+    /// Registers every natural resource that `data/blocks` does not define by hand.
+    fn load_natural_resources(&mut self) {
+        for resource in NATURAL_RESOURCES.iter() {
+            if self.blocks.contains_key(&resource.id) {
+                continue;
+            }
+            self.item_type_to_id
+                .entry(resource.item_type.clone())
+                .or_insert(resource.id);
+            if resource.state == ResourceState::Liquid {
+                self.liquid_ids.insert(resource.id);
+            }
+            self.blocks.insert(resource.id, resource.to_block_data());
+        }
     }
 
     #[must_use]
@@ -177,6 +239,13 @@ impl BlockManager {
             .map(|&id| BlockType::from_id(id))
     }
 
+    /// Whether `item_type` can be in cargo: the item of a block or the warp gate the shop
+    /// sells.
+    #[must_use]
+    pub fn is_known_item_type(&self, item_type: &str) -> bool {
+        item_type == WARP_GATE_ITEM_TYPE || self.item_type_to_id.contains_key(item_type)
+    }
+
     #[must_use]
     pub fn is_solid(&self, block_type: &BlockType) -> bool {
         self.get_data(block_type).is_some_and(|d| d.is_solid)
@@ -210,8 +279,23 @@ impl BlockManager {
         })
     }
 
+    #[must_use]
+    pub fn get_tint(&self, block_type: &BlockType) -> Color {
+        self.get_data(block_type)
+            .and_then(|d| d.tint)
+            .map_or(WHITE, |[r, g, b]| Color::from_rgba(r, g, b, 255))
+    }
+
     #[must_use]
     pub fn get_weight(&self, block_type: &BlockType) -> i32 {
         self.get_data(block_type).map_or(0, |d| d.weight)
     }
+
+    /// Every registered block type, in id order.
+    #[must_use]
+    pub fn block_types(&self) -> Vec<BlockType> {
+        let mut ids: Vec<u32> = self.blocks.keys().copied().collect();
+        ids.sort_unstable();
+        ids.into_iter().map(BlockType::from_id).collect()
+    }
 }
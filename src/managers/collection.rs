@@ -0,0 +1,70 @@
+use crate::components::BlockType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::sync::LazyLock;
+
+const COLLECTIONS_PATH: &str = "data/collections.json";
+
+pub static COLLECTION_SETS: LazyLock<Vec<CollectionSet>> = LazyLock::new(|| {
+    fs::read_to_string(COLLECTIONS_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+});
+
+/// A group of fossils or artifacts for the collection log (TGIP Phase 11).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollectionSet {
+    pub key: String,
+    /// Block ids of the finds that make up the set.
+    pub items: Vec<u32>,
+    /// Money paid once the last find of the set is catalogued.
+    pub bonus: i32,
+}
+
+impl CollectionSet {
+    #[must_use]
+    pub fn found_count(&self, collection: &BTreeSet<u32>) -> usize {
+        self.items
+            .iter()
+            .filter(|id| collection.contains(id))
+            .count()
+    }
+
+    #[must_use]
+    pub fn is_complete(&self, collection: &BTreeSet<u32>) -> bool {
+        self.found_count(collection) == self.items.len()
+    }
+}
+
+/// What cataloguing a find added to the log.
+pub enum CatalogueResult {
+    /// Already in the log.
+    Duplicate,
+    /// A new find, with the set it belongs to.
+    New(&'static CollectionSet),
+    /// New, and the last missing find of a set.
+    SetCompleted(&'static CollectionSet),
+}
+
+/// The set `block_type` belongs to, if it is a collectible find.
+#[must_use]
+pub fn collection_set_of(block_type: BlockType) -> Option<&'static CollectionSet> {
+    COLLECTION_SETS
+        .iter()
+        .find(|set| set.items.contains(&block_type.to_id()))
+}
+
+/// Records a broken find in `collection`. Returns `None` for blocks that are not collectible.
+pub fn catalogue(collection: &mut BTreeSet<u32>, block_type: BlockType) -> Option<CatalogueResult> {
+    let set = collection_set_of(block_type)?;
+    if !collection.insert(block_type.to_id()) {
+        return Some(CatalogueResult::Duplicate);
+    }
+    Some(if set.is_complete(collection) {
+        CatalogueResult::SetCompleted(set)
+    } else {
+        CatalogueResult::New(set)
+    })
+}
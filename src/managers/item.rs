@@ -32,7 +32,7 @@ impl ItemManager {
             .push(Item::new(x, y, item_type, sprite_rect, weight, is_natural));
     }
 
-    pub fn update(&mut self, player: &mut Player, blocks: &[&Block]) {
+    pub fn update(&mut self, player: &mut Player, blocks: &[Block]) {
         let player_rect = player.rect();
 
         for item in &mut self.items {
@@ -54,7 +54,7 @@ impl ItemManager {
             item_rect_x.h -= 0.2;
 
             for block in blocks {
-                if block.is_broken || !block.block_type.is_solid() {
+                if block.is_broken() || !block.block_type.is_solid() {
                     continue;
                 }
                 let block_rect = Rect::new(block.x, block.y, BLOCK_SIZE, BLOCK_SIZE);
@@ -78,7 +78,7 @@ impl ItemManager {
             let mut _on_ground = false;
 
             for block in blocks {
-                if block.is_broken || !block.block_type.is_solid() {
+                if block.is_broken() || !block.block_type.is_solid() {
                     continue;
                 }
                 let block_rect = Rect::new(block.x, block.y, BLOCK_SIZE, BLOCK_SIZE);
@@ -1,4 +1,5 @@
 use crate::constants::{DEFAULT_LANGUAGE, LANG_FOLDER};
+use crate::managers::resources::NATURAL_RESOURCES;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
@@ -78,12 +79,29 @@ impl LanguageManager {
                     self.translations.insert(k, s);
                 }
             }
+            self.add_resource_names(lang_code);
             self.current_lang_code = lang_code.to_string();
             return true;
         }
         false
     }
 
+    /// Names generated resources from `resources.json` unless the language file already does.
+    fn add_resource_names(&mut self, lang_code: &str) {
+        for resource in NATURAL_RESOURCES.iter() {
+            let name = if lang_code.starts_with("ja") {
+                &resource.name_ja
+            } else {
+                &resource.name_en
+            };
+            for key in [&resource.key, &resource.item_type] {
+                self.translations
+                    .entry(format!("block.{key}.name"))
+                    .or_insert_with(|| name.clone());
+            }
+        }
+    }
+
     #[must_use]
     pub fn get_string(&self, key: &str) -> String {
         self.translations
@@ -0,0 +1,291 @@
+use crate::components::Player;
+use crate::constants::{AUTOSAVE_DEFAULT_MINUTES, BLOCK_SIZE};
+use crate::error::PersistenceError;
+use crate::managers::persistence::{SAVE_VERSION, SaveData};
+use crate::managers::world::generation::WorldGenerator;
+use crate::utils::chunk_to_macrogrid_coords;
+use num_traits::ToPrimitive;
+use serde_json::{Map, Value, json};
+
+type Migration = fn(&mut Map<String, Value>) -> Result<(), PersistenceError>;
+
+/// `MIGRATIONS[n]` upgrades a save from version `n + 1` to `n + 2`.
+const MIGRATIONS: [Migration; 5] = [v1_to_v2, v2_to_v3, v3_to_v4, v4_to_v5, v5_to_v6];
+
+/// Decompresses and decodes a save header or single-file save of any known version.
+///
+/// # Errors
+///
+/// Fails if the data is not zstd, not JSON, from a newer game, or not a save.
+pub fn decode_save(compressed: &[u8]) -> Result<SaveData, PersistenceError> {
+    upgrade(decode_value(compressed)?)
+}
+
+/// Decompresses a save header or single-file save into JSON as it was written.
+///
+/// # Errors
+///
+/// Fails if the data is not zstd or not JSON.
+pub fn decode_value(compressed: &[u8]) -> Result<Value, PersistenceError> {
+    let decompressed = zstd::decode_all(compressed).map_err(PersistenceError::Decompression)?;
+    Ok(serde_json::from_slice(&decompressed)?)
+}
+
+/// The version a save was written with.
+///
+/// # Errors
+///
+/// Fails if the save is not a JSON object or its version is not a positive number.
+pub fn stored_version(value: &Value) -> Result<u32, PersistenceError> {
+    let Value::Object(map) = value else {
+        return Err(PersistenceError::InvalidFormat);
+    };
+    // The first saves carried no version at all.
+    match map.get("version") {
+        None => Ok(1),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|&v| v > 0)
+            .ok_or_else(|| PersistenceError::Corrupt(format!("bad version {v}"))),
+    }
+}
+
+/// Brings a save of any older version up to `SAVE_VERSION` one step at a time, then
+/// deserializes it.
+///
+/// # Errors
+///
+/// Fails if the save is not a JSON object, is from a newer game, or does not hold the
+/// fields its version should.
+pub fn upgrade(mut value: Value) -> Result<SaveData, PersistenceError> {
+    let mut version = stored_version(&value)?;
+    let Value::Object(map) = &mut value else {
+        return Err(PersistenceError::InvalidFormat);
+    };
+    if version > SAVE_VERSION {
+        return Err(PersistenceError::TooNew { version });
+    }
+
+    while version < SAVE_VERSION {
+        MIGRATIONS[version as usize - 1](map)?;
+        version += 1;
+        map.insert("version".to_string(), json!(version));
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Version 1 listed modified chunks flat; version 2 groups them by macrogrid.
+fn v1_to_v2(map: &mut Map<String, Value>) -> Result<(), PersistenceError> {
+    map.remove("is_save_file");
+    let chunks = match map.remove("modified_chunks") {
+        None => Vec::new(),
+        Some(Value::Array(chunks)) => chunks,
+        Some(_) => {
+            return Err(PersistenceError::Corrupt(
+                "modified_chunks is not a list".to_string(),
+            ));
+        }
+    };
+
+    let mut macrogrids: Vec<Value> = Vec::new();
+    for chunk in chunks {
+        let coord = |key: &str| {
+            chunk
+                .get(key)
+                .and_then(Value::as_i64)
+                .and_then(|c| i32::try_from(c).ok())
+                .ok_or_else(|| PersistenceError::Corrupt(format!("chunk without {key}")))
+        };
+        let (mg_coords, _) = chunk_to_macrogrid_coords(coord("cx")?, coord("cy")?);
+        let (mgx, mgy) = (json!(mg_coords.x), json!(mg_coords.y));
+        if let Some(macrogrid) = macrogrids
+            .iter_mut()
+            .find(|mg| mg["mgx"] == mgx && mg["mgy"] == mgy)
+            && let Some(Value::Array(chunks)) = macrogrid.get_mut("chunks")
+        {
+            chunks.push(chunk);
+        } else {
+            macrogrids.push(json!({ "mgx": mgx, "mgy": mgy, "chunks": [chunk] }));
+        }
+    }
+    map.insert("modified_macrogrids".to_string(), Value::Array(macrogrids));
+    Ok(())
+}
+
+/// Version 3 moved chunks into region files of a save directory. Single-file saves keep
+/// their macrogrids inline and are split into regions on their next save.
+#[allow(clippy::unnecessary_wraps)]
+const fn v2_to_v3(_map: &mut Map<String, Value>) -> Result<(), PersistenceError> {
+    Ok(())
+}
+
+/// Version 4 saves the player state that used to be dropped, at its starting values.
+#[allow(clippy::unnecessary_wraps)]
+fn v3_to_v4(map: &mut Map<String, Value>) -> Result<(), PersistenceError> {
+    map.entry("player_heat_resistance_level")
+        .or_insert(json!(1));
+    map.entry("player_vx").or_insert(json!(0.0));
+    map.entry("player_vy").or_insert(json!(0.0));
+    Ok(())
+}
+
+/// Version 5 keeps each world's autosave interval.
+#[allow(clippy::unnecessary_wraps)]
+fn v4_to_v5(map: &mut Map<String, Value>) -> Result<(), PersistenceError> {
+    map.entry("autosave_minutes")
+        .or_insert(json!(AUTOSAVE_DEFAULT_MINUTES));
+    Ok(())
+}
+
+/// Version 6 tracks play time and the deepest the player has been below the surface;
+/// older saves start the depth at how far down the player stands.
+#[allow(clippy::unnecessary_wraps)]
+fn v5_to_v6(map: &mut Map<String, Value>) -> Result<(), PersistenceError> {
+    let depth = player_depth(map).unwrap_or(0);
+    map.entry("play_time_seconds").or_insert(json!(0.0));
+    map.entry("max_depth").or_insert(json!(depth));
+    Ok(())
+}
+
+/// Blocks between the ground and the player of an older save, counted as the HUD does.
+fn player_depth(map: &Map<String, Value>) -> Option<i32> {
+    let position = |key: &str| map.get(key).and_then(Value::as_f64)?.to_f32();
+    let seed = |key: &str| u32::try_from(map.get(key).and_then(Value::as_u64)?).ok();
+    let settings = map
+        .get("world_settings")
+        .and_then(|settings| serde_json::from_value(settings.clone()).ok())
+        .unwrap_or_default();
+    let generator =
+        WorldGenerator::with_settings(seed("world_seed_main")?, seed("world_seed_ore")?, settings);
+
+    let player = Player::new(position("player_x")?, position("player_y")?);
+    let column = ((player.x + player.width / 2.0) / BLOCK_SIZE)
+        .floor()
+        .to_i32()?;
+    let row = (player.y / BLOCK_SIZE).floor().to_i32()?;
+    Some((row - generator.surface_height_at(column)).max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::CHUNK_AREA;
+
+    fn flat_chunk(cx: i32, cy: i32) -> Value {
+        json!({ "cx": cx, "cy": cy, "blocks": [1, 0, CHUNK_AREA], "named_blocks": [] })
+    }
+
+    /// A save as the first version wrote it: no version and one flat list of chunks.
+    fn v1_save() -> Value {
+        json!({
+            "camera_x": 0.0,
+            "camera_y": 0.0,
+            "player_x": 48.0,
+            "player_y": 400.0,
+            "player_money": 120,
+            "player_fuel": 50.0,
+            "player_max_fuel": 100.0,
+            "player_max_cargo": 10,
+            "player_max_storage": 50,
+            "player_drill_level": 2,
+            "player_tank_level": 1,
+            "player_engine_level": 1,
+            "player_cargo_level": 1,
+            "player_warp_gates": [],
+            "player_cargo": [],
+            "player_storage": [],
+            "world_seed_main": 5,
+            "world_seed_ore": 9,
+            "is_save_file": true,
+            "modified_chunks": [flat_chunk(0, 0), flat_chunk(-1, 0), flat_chunk(1, 2)],
+        })
+    }
+
+    #[test]
+    fn upgrades_v1_save_to_current_version() {
+        let data = upgrade(v1_save()).expect("v1 save upgrades");
+
+        assert_eq!(data.version, SAVE_VERSION);
+        assert_eq!(data.player_money, 120);
+        assert_eq!(data.player_heat_resistance_level, 1);
+        assert_eq!(data.autosave_minutes, AUTOSAVE_DEFAULT_MINUTES);
+        assert!(data.play_time_seconds.abs() < f64::EPSILON);
+        // The player's centre is in column 6 and its top in row 50.
+        let surface_y = WorldGenerator::new(5, 9).surface_height_at(6);
+        assert_eq!(data.max_depth, (50 - surface_y).max(0));
+    }
+
+    #[test]
+    fn groups_v1_chunks_by_macrogrid() {
+        let data = upgrade(v1_save()).expect("v1 save upgrades");
+
+        let mut regions: Vec<_> = data
+            .modified_macrogrids
+            .iter()
+            .map(|region| {
+                let chunks = region.chunks.iter().map(|c| (c.cx, c.cy)).collect();
+                (region.mgx, region.mgy, chunks)
+            })
+            .collect();
+        regions.sort_unstable();
+        assert_eq!(
+            regions,
+            vec![(-1, 0, vec![(-1, 0)]), (0, 0, vec![(0, 0), (1, 2)])]
+        );
+    }
+
+    #[test]
+    fn upgraded_save_upgrades_again_unchanged() {
+        let data = upgrade(v1_save()).expect("v1 save upgrades");
+        let value = serde_json::to_value(&data).expect("save serializes");
+
+        assert_eq!(stored_version(&value).ok(), Some(SAVE_VERSION));
+        let again = upgrade(value).expect("current save loads");
+        assert_eq!(
+            again.modified_macrogrids.len(),
+            data.modified_macrogrids.len()
+        );
+        assert_eq!(again.max_depth, data.max_depth);
+    }
+
+    #[test]
+    fn rejects_saves_from_newer_versions() {
+        let mut value = v1_save();
+        value["version"] = json!(SAVE_VERSION + 1);
+
+        assert!(matches!(
+            upgrade(value),
+            Err(PersistenceError::TooNew { version }) if version == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn rejects_v1_chunks_that_are_not_a_list() {
+        let mut value = v1_save();
+        value["modified_chunks"] = json!({ "cx": 0, "cy": 0 });
+
+        assert!(matches!(upgrade(value), Err(PersistenceError::Corrupt(_))));
+    }
+
+    #[test]
+    fn reads_stored_versions() {
+        assert_eq!(stored_version(&json!({})).ok(), Some(1));
+        assert_eq!(stored_version(&json!({ "version": 4 })).ok(), Some(4));
+        for bad in [
+            json!({ "version": 0 }),
+            json!({ "version": -2 }),
+            json!({ "version": "6" }),
+        ] {
+            assert!(matches!(
+                stored_version(&bad),
+                Err(PersistenceError::Corrupt(_))
+            ));
+        }
+        assert!(matches!(
+            stored_version(&json!([])),
+            Err(PersistenceError::InvalidFormat)
+        ));
+    }
+}
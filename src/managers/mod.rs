@@ -1,10 +1,14 @@
 pub mod block;
+pub mod collection;
 pub mod item;
 pub mod language;
+pub mod migrations;
 pub mod notification;
 pub mod particle;
 pub mod persistence;
 pub mod player;
+pub mod resources;
+pub mod validation;
 pub mod world;
 
 pub use item::ItemManager;
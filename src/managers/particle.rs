@@ -27,7 +27,7 @@ impl ParticleManager {
         self.active_particles.extend(new_particles);
     }
 
-    pub fn update(&mut self, collidable_blocks: &[&Block], camera: &Camera) {
+    pub fn update(&mut self, collidable_blocks: &[Block], camera: &Camera) {
         // Keep collidable_blocks for now
         for particle in &mut self.active_particles {
             if !particle.alive {
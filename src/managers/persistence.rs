@@ -1,12 +1,23 @@
 use crate::components::WarpGate;
+use crate::constants::SAVE_BACKUP_COUNT;
+use crate::error::PersistenceError;
+use crate::managers::world::settings::WorldSettings;
+use crate::managers::{migrations, validation};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub const SAVE_VERSION: u32 = 2;
+/// Older saves are upgraded step by step on load; see `migrations`.
+pub const SAVE_VERSION: u32 = 6;
 pub const SAVE_DIR: &str = "saves";
+/// Player and world state of a save directory; chunks live in its region files.
+pub const HEADER_FILE: &str = "world.dat";
+pub const REGIONS_DIR: &str = "regions";
+/// Plain JSON summary of a save directory, read on its own to list saves.
+pub const META_FILE: &str = "meta.json";
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BlockSaveData {
@@ -41,6 +52,14 @@ pub struct ItemStack {
     pub is_auto_stored: bool,
 }
 
+/// Water left in an aquifer reservoir the player has drawn from.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AquiferSaveData {
+    pub x: i32,
+    pub y: i32,
+    pub reserve: u32,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SaveData {
     pub version: u32,
@@ -57,14 +76,108 @@ pub struct SaveData {
     pub player_tank_level: i32,
     pub player_engine_level: i32,
     pub player_cargo_level: i32,
+    pub player_heat_resistance_level: i32,
+    pub player_vx: f32,
+    pub player_vy: f32,
     pub player_warp_gates: Vec<WarpGate>,
     pub player_cargo: Vec<ItemStack>,
     pub player_storage: Vec<ItemStack>,
     pub world_seed_main: u32,
     pub world_seed_ore: u32,
+    /// Saves from before world settings existed load with the defaults they were made with.
+    #[serde(default)]
+    pub world_settings: WorldSettings,
     #[serde(default, with = "visited_chunks_format")]
     pub visited_chunks: std::collections::HashSet<crate::components::BlockPos>,
+    /// Regions to write. Single-file saves from before region files hold every modified
+    /// region here; a save directory's header leaves it empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub modified_macrogrids: Vec<MacroGridSaveData>,
+    #[serde(default)]
+    pub aquifer_reserves: Vec<AquiferSaveData>,
+    /// Minutes between autosaves, 0 for off.
+    pub autosave_minutes: i32,
+    pub play_time_seconds: f64,
+    /// Deepest the player has been below the ground surface, in blocks.
+    pub max_depth: i32,
+    /// Block ids of the catalogued fossils and artifacts.
+    #[serde(default)]
+    pub collection: Vec<u32>,
+}
+
+/// Summary of a save for the save-select screen, kept beside the header so listing saves
+/// never decodes a world.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SaveMeta {
+    pub display_name: String,
+    pub play_time_seconds: f64,
+    /// Seconds since the Unix epoch.
+    pub last_saved: u64,
+    pub money: i32,
+    /// Deepest the player has been below the ground surface, in blocks.
+    pub max_depth: i32,
+    pub world_seed_main: u32,
+    pub world_seed_ore: u32,
+    /// `THUMBNAIL_WIDTH` by `THUMBNAIL_HEIGHT` RGB pixels around the player, row by row.
+    pub thumbnail: Vec<u8>,
+}
+
+/// A world on the save-select screen. `name` is its directory, or file for saves from
+/// before save directories; those have only a name and a date in `meta`.
+#[derive(Clone, Debug)]
+pub struct SaveSlot {
+    pub name: String,
+    pub meta: SaveMeta,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SaveSort {
+    #[default]
+    LastSaved,
+    Name,
+    PlayTime,
+    Money,
+    Depth,
+}
+
+impl SaveSort {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::LastSaved => Self::Name,
+            Self::Name => Self::PlayTime,
+            Self::PlayTime => Self::Money,
+            Self::Money => Self::Depth,
+            Self::Depth => Self::LastSaved,
+        }
+    }
+
+    #[must_use]
+    pub const fn lang_key(self) -> &'static str {
+        match self {
+            Self::LastSaved => "save_select.sort.last_saved",
+            Self::Name => "save_select.sort.name",
+            Self::PlayTime => "save_select.sort.play_time",
+            Self::Money => "save_select.sort.money",
+            Self::Depth => "save_select.sort.depth",
+        }
+    }
+
+    /// Orders `slots` by this key, newest, longest, richest or deepest first.
+    pub fn sort(self, slots: &mut [SaveSlot]) {
+        match self {
+            Self::LastSaved => slots.sort_by_key(|slot| std::cmp::Reverse(slot.meta.last_saved)),
+            Self::Name => slots.sort_by_cached_key(|slot| slot.meta.display_name.to_lowercase()),
+            Self::PlayTime => slots.sort_by(|a, b| {
+                b.meta
+                    .play_time_seconds
+                    .total_cmp(&a.meta.play_time_seconds)
+            }),
+            Self::Money => slots.sort_by_key(|slot| std::cmp::Reverse(slot.meta.money)),
+            Self::Depth => slots.sort_by_key(|slot| std::cmp::Reverse(slot.meta.max_depth)),
+        }
+    }
 }
 
 mod visited_chunks_format {
@@ -145,34 +258,359 @@ impl PersistenceManager {
         }
     }
 
-    pub fn list_save_files() -> Vec<String> {
-        let mut files = Vec::new();
-        let path = Path::new(SAVE_DIR);
-        if let Ok(entries) = fs::read_dir(path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(ext) = path.extension()
-                    && ext == "dat"
-                    && let Ok(compressed_content) = fs::read(&path)
-                    && let Ok(decompressed) = zstd::decode_all(&compressed_content[..])
-                    && let Ok(data) = serde_json::from_slice::<serde_json::Value>(&decompressed)
-                    && (data.get("version").is_some() || data.get("is_save_file").is_some())
-                    && let Some(file_name) = path.file_name()
-                    && let Some(name_str) = file_name.to_str()
-                {
-                    files.push(name_str.to_string());
+    /// Save directory of the world `name`. A `.dat` suffix from older single-file saves
+    /// is dropped, so converted worlds keep their name.
+    #[must_use]
+    pub fn save_dir(name: &str) -> PathBuf {
+        Path::new(SAVE_DIR).join(name.strip_suffix(".dat").unwrap_or(name))
+    }
+
+    #[must_use]
+    pub fn region_path(dir: &Path, mgx: i32, mgy: i32) -> PathBuf {
+        dir.join(REGIONS_DIR).join(format!("r.{mgx}.{mgy}.dat"))
+    }
+
+    /// Reads one region file, falling back to its backups if it cannot be decoded, or
+    /// `None` if the region was never saved. Chunks that fail validation are left out and
+    /// returned as problems, so one bad chunk does not cost the rest of the region.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the region was saved but neither its file nor any backup decodes.
+    pub fn load_region(
+        dir: &Path,
+        mgx: i32,
+        mgy: i32,
+    ) -> Result<Option<(MacroGridSaveData, Vec<PersistenceError>)>, PersistenceError> {
+        let path = Self::region_path(dir, mgx, mgy);
+        let mut last_error = None;
+        for candidate in std::iter::once(path.clone())
+            .chain((1..=SAVE_BACKUP_COUNT).map(|n| backup_path(&path, n)))
+            .filter(|candidate| candidate.is_file())
+        {
+            match Self::read_region_file(&candidate) {
+                Ok(mut region) => {
+                    let problems = validation::remove_invalid_chunks(&mut region);
+                    return Ok(Some((region, problems)));
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        last_error.map_or(Ok(None), Err)
+    }
+
+    /// Whether world `name` has a header that no longer decodes but a backup to restore.
+    #[must_use]
+    pub fn is_save_damaged(name: &str) -> bool {
+        let header = Self::save_dir(name).join(HEADER_FILE);
+        let is_readable =
+            fs::read(&header).is_ok_and(|compressed| migrations::decode_save(&compressed).is_ok());
+        !is_readable && (1..=SAVE_BACKUP_COUNT).any(|n| backup_path(&header, n).is_file())
+    }
+
+    /// Replaces the header of world `name` with its newest backup that decodes, and every
+    /// region file that does not decode with its newest good backup. The restored header
+    /// may be a save behind regions that were written intact. Returns the number of files
+    /// restored.
+    ///
+    /// # Errors
+    ///
+    /// Fails if no backup of the header decodes or a restored file cannot be written.
+    pub fn restore_backup(name: &str) -> Result<usize, PersistenceError> {
+        let dir = Self::save_dir(name);
+        let header = dir.join(HEADER_FILE);
+        let backup = (1..=SAVE_BACKUP_COUNT)
+            .filter_map(|n| fs::read(backup_path(&header, n)).ok())
+            .find(|compressed| migrations::decode_save(compressed).is_ok())
+            .ok_or_else(|| PersistenceError::Corrupt("no readable backup".to_string()))?;
+        write_atomically(&header, &backup)?;
+        let mut restored = 1;
+
+        for path in region_files_in(&dir) {
+            if decode_region(&path).is_some() {
+                continue;
+            }
+            if let Some(backup) = (1..=SAVE_BACKUP_COUNT)
+                .map(|n| backup_path(&path, n))
+                .find(|backup| decode_region(backup).is_some())
+            {
+                write_atomically(&path, &fs::read(backup)?)?;
+                restored += 1;
+            }
+        }
+        Ok(restored)
+    }
+
+    /// Every world in `SAVE_DIR`, read from the metadata files alone.
+    #[must_use]
+    pub fn list_save_slots() -> Vec<SaveSlot> {
+        let mut slots = Vec::new();
+        let Ok(entries) = fs::read_dir(SAVE_DIR) else {
+            return slots;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            if path.is_dir() {
+                // Worlds whose header was lost still show, so a backup can be restored.
+                let header = path.join(HEADER_FILE);
+                if !header.is_file() && !backup_path(&header, 1).is_file() {
+                    continue;
                 }
+                let meta = read_meta(&path).unwrap_or_else(|| SaveMeta {
+                    display_name: name.clone(),
+                    last_saved: modified_secs(&header),
+                    ..SaveMeta::default()
+                });
+                slots.push(SaveSlot { name, meta });
+            } else if path.extension().is_some_and(|ext| ext == "dat")
+                // Single-file saves already converted to a directory are hidden.
+                && !path.with_extension("").join(HEADER_FILE).is_file()
+            {
+                let meta = SaveMeta {
+                    display_name: name.clone(),
+                    last_saved: modified_secs(&path),
+                    ..SaveMeta::default()
+                };
+                slots.push(SaveSlot { name, meta });
             }
         }
-        files
+        slots
+    }
+
+    /// Removes world `name`, along with the single-file save it was converted from.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the files cannot be removed.
+    pub fn delete_save(name: &str) -> Result<(), PersistenceError> {
+        let dir = Self::save_dir(name);
+        if dir.is_dir() {
+            fs::remove_dir_all(&dir)?;
+        }
+        let legacy = dir.with_extension("dat");
+        if legacy.is_file() {
+            fs::remove_file(legacy)?;
+        }
+        Ok(())
     }
 
-    /// Saves the game state to a file.
+    /// Moves world `name` to `new_name`. Returns the slot's new name.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `new_name` is not a valid name or is taken, or the files cannot be moved.
+    pub fn rename_save(name: &str, new_name: &str) -> Result<String, PersistenceError> {
+        let (from, to) = Self::slot_paths(name, new_name)?;
+        if from.is_dir() {
+            retire_legacy_save(&from)?;
+        }
+        fs::rename(&from, &to)?;
+        if to.is_dir() {
+            Self::set_display_name(&to, new_name)?;
+        }
+        Ok(file_name_of(&to))
+    }
+
+    /// Copies world `name` to `new_name`. Returns the copy's name.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `new_name` is not a valid name or is taken, or the files cannot be copied.
+    pub fn duplicate_save(name: &str, new_name: &str) -> Result<String, PersistenceError> {
+        let (from, to) = Self::slot_paths(name, new_name)?;
+        if from.is_dir() {
+            retire_legacy_save(&from)?;
+            copy_dir(&from, &to)?;
+            Self::set_display_name(&to, new_name)?;
+        } else {
+            fs::copy(&from, &to)?;
+        }
+        Ok(file_name_of(&to))
+    }
+
+    /// The first of `base_copy`, `base_copy2`, ... that no world uses yet.
+    #[must_use]
+    pub fn unused_copy_name(name: &str) -> String {
+        let base = name.strip_suffix(".dat").unwrap_or(name);
+        (1..)
+            .map(|n| {
+                if n == 1 {
+                    format!("{base}_copy")
+                } else {
+                    format!("{base}_copy{n}")
+                }
+            })
+            .find(|candidate| !Self::is_name_taken(candidate))
+            .unwrap_or_default()
+    }
+
+    fn is_name_taken(name: &str) -> bool {
+        let dir = Self::save_dir(name);
+        dir.exists() || dir.with_extension("dat").exists()
+    }
+
+    /// Where slot `name` is now and where it would go as `new_name`.
+    fn slot_paths(name: &str, new_name: &str) -> Result<(PathBuf, PathBuf), PersistenceError> {
+        let new_name = new_name.strip_suffix(".dat").unwrap_or(new_name);
+        if new_name.is_empty()
+            || !new_name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(PersistenceError::InvalidName(new_name.to_string()));
+        }
+        if Self::is_name_taken(new_name) {
+            return Err(PersistenceError::SlotExists(new_name.to_string()));
+        }
+        let dir = Self::save_dir(name);
+        if dir.is_dir() {
+            Ok((dir, Self::save_dir(new_name)))
+        } else {
+            Ok((
+                Path::new(SAVE_DIR).join(name),
+                Self::save_dir(new_name).with_extension("dat"),
+            ))
+        }
+    }
+
+    fn set_display_name(dir: &Path, display_name: &str) -> Result<(), PersistenceError> {
+        let mut meta = read_meta(dir).unwrap_or_default();
+        display_name.clone_into(&mut meta.display_name);
+        write_atomically(&dir.join(META_FILE), &serde_json::to_vec(&meta)?)?;
+        Ok(())
+    }
+
+    /// Reads world `name` the way the game loads it: the header of a save directory or a
+    /// whole single-file save, upgraded to `SAVE_VERSION`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the save cannot be read, decoded or upgraded.
+    pub fn read_save(name: &str) -> Result<SaveData, PersistenceError> {
+        migrations::decode_save(&fs::read(Self::header_path(name))?)
+    }
+
+    /// The file holding the header of world `name`: `HEADER_FILE` of its save directory,
+    /// or the whole save for single-file saves.
+    #[must_use]
+    pub fn header_path(name: &str) -> PathBuf {
+        let dir = Self::save_dir(name);
+        if dir.join(HEADER_FILE).is_file() {
+            dir.join(HEADER_FILE)
+        } else {
+            Path::new(SAVE_DIR).join(name)
+        }
+    }
+
+    /// Region files of world `name`, sorted; none for single-file saves.
+    #[must_use]
+    pub fn region_files(name: &str) -> Vec<PathBuf> {
+        region_files_in(&Self::save_dir(name))
+    }
+
+    /// Decodes one region file as it is, without backups or validation.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file cannot be read, decompressed or parsed.
+    pub fn read_region_file(path: &Path) -> Result<MacroGridSaveData, PersistenceError> {
+        let compressed = fs::read(path)?;
+        let decompressed =
+            zstd::decode_all(&compressed[..]).map_err(PersistenceError::Decompression)?;
+        Ok(serde_json::from_slice(&decompressed)?)
+    }
+
+    /// Reads world `name` with every region in `modified_macrogrids`, as a single-file
+    /// save holds it.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the header or any region file cannot be read.
+    pub fn read_whole_save(name: &str) -> Result<SaveData, PersistenceError> {
+        let mut data = Self::read_save(name)?;
+        data.modified_macrogrids
+            .extend(Self::read_region_files(name)?);
+        Ok(data)
+    }
+
+    /// Every region file of world `name`, decoded as it is.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any region file cannot be read.
+    pub fn read_region_files(name: &str) -> Result<Vec<MacroGridSaveData>, PersistenceError> {
+        Self::region_files(name)
+            .iter()
+            .map(|path| Self::read_region_file(path))
+            .collect()
+    }
+
+    /// Rewrites the header and region files of world `name` at zstd `level`, leaving the
+    /// backups alone. Returns the number of files rewritten.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a file cannot be decompressed or written.
+    pub fn recompress(name: &str, level: i32) -> Result<usize, PersistenceError> {
+        let files: Vec<PathBuf> = std::iter::once(Self::header_path(name))
+            .chain(Self::region_files(name))
+            .collect();
+        for path in &files {
+            let decompressed =
+                zstd::decode_all(&fs::read(path)?[..]).map_err(PersistenceError::Decompression)?;
+            let compressed = zstd::encode_all(&decompressed[..], level)?;
+            write_atomically(path, &compressed)?;
+        }
+        Ok(files.len())
+    }
+
+    /// Writes `data` as world `name` outside the game, e.g. from the editor. It is
+    /// validated first and written like a game save, backups included. Regions in
+    /// `data.modified_macrogrids` replace their files; a single-file save becomes a
+    /// save directory. The metadata keeps its name and thumbnail.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `data` is not a valid save or cannot be written.
+    pub fn write_save(name: &str, mut data: SaveData) -> Result<(), PersistenceError> {
+        validation::validate_save(&data)?;
+        let dir = Self::save_dir(name);
+        let is_new_dir = !dir.join(HEADER_FILE).is_file();
+        let regions = std::mem::take(&mut data.modified_macrogrids);
+
+        let mut meta = read_meta(&dir).unwrap_or_else(|| SaveMeta {
+            display_name: file_name_of(&dir),
+            ..SaveMeta::default()
+        });
+        meta.play_time_seconds = data.play_time_seconds;
+        meta.money = data.player_money;
+        meta.max_depth = data.max_depth;
+        meta.world_seed_main = data.world_seed_main;
+        meta.world_seed_ore = data.world_seed_ore;
+        write_save_dir(&dir, &data, &regions, &mut meta, is_new_dir)
+    }
+
+    /// Saves the game state into the world's save directory: one file per region in
+    /// `data.modified_macrogrids`, then the header and `meta`. Regions not listed are left
+    /// as they are, unless `replace_regions` clears whatever region files the directory held.
     ///
     /// # Panics
     ///
     /// Panics if the internal lock is poisoned.
-    pub fn save_game(&mut self, filename: String, data: SaveData) {
+    pub fn save_game(
+        &mut self,
+        filename: String,
+        mut data: SaveData,
+        mut meta: SaveMeta,
+        replace_regions: bool,
+    ) {
         if self.is_saving {
             return;
         }
@@ -181,16 +619,9 @@ impl PersistenceManager {
         let result_clone = self.save_result.clone();
 
         thread::spawn(move || {
-            let path = Path::new(SAVE_DIR).join(&filename);
-            let temp_path = path.with_extension("tmp");
-
-            let res = (|| {
-                let json_vec = serde_json::to_vec(&data)?;
-                let compressed = zstd::encode_all(&json_vec[..], 0)?; // 0 is default compression level
-                fs::write(&temp_path, compressed)?;
-                fs::rename(&temp_path, &path)?;
-                Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
-            })();
+            let dir = Self::save_dir(&filename);
+            let regions = std::mem::take(&mut data.modified_macrogrids);
+            let res = write_save_dir(&dir, &data, &regions, &mut meta, replace_regions);
 
             let res_final = match res {
                 Ok(()) => Ok("Save Successful".to_string()),
@@ -202,7 +633,7 @@ impl PersistenceManager {
         });
     }
 
-    /// Loads the game state from a file.
+    /// Loads the header of a save directory, or the whole of an older single-file save.
     ///
     /// # Panics
     ///
@@ -216,13 +647,7 @@ impl PersistenceManager {
         let result_clone = self.load_result.clone();
 
         thread::spawn(move || {
-            let path = Path::new(SAVE_DIR).join(&filename);
-            let res = (|| {
-                let compressed_content = fs::read(path)?;
-                let decompressed = zstd::decode_all(&compressed_content[..])?;
-                let data: SaveData = serde_json::from_slice(&decompressed)?;
-                Ok::<SaveData, Box<dyn std::error::Error + Send + Sync>>(data)
-            })();
+            let res = Self::read_save(&filename);
 
             let res_final = match res {
                 Ok(data) => Ok(data),
@@ -262,3 +687,134 @@ impl PersistenceManager {
         None
     }
 }
+
+/// Writes `json` zstd-compressed through a temporary file, so a crash never leaves half a
+/// file. The version it replaces becomes backup 1 and older backups move down a place.
+fn write_compressed(path: &Path, json: &[u8]) -> std::io::Result<()> {
+    let temp_path = path.with_extension("tmp");
+    let compressed = zstd::encode_all(json, 0)?; // 0 is default compression level
+    fs::write(&temp_path, compressed)?;
+    if path.is_file() {
+        for n in (1..SAVE_BACKUP_COUNT).rev() {
+            let older = backup_path(path, n);
+            if older.is_file() {
+                fs::rename(older, backup_path(path, n + 1))?;
+            }
+        }
+        fs::rename(path, backup_path(path, 1))?;
+    }
+    fs::rename(&temp_path, path)
+}
+
+/// Writes `bytes` through a temporary file without touching the backups.
+fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Backup `n` of a save file, e.g. `world.dat.bak1`.
+fn backup_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".bak{n}"));
+    path.with_file_name(name)
+}
+
+fn decode_region(path: &Path) -> Option<MacroGridSaveData> {
+    let region = PersistenceManager::read_region_file(path).ok()?;
+    validation::validate_region(&region).ok()?;
+    Some(region)
+}
+
+fn region_files_in(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir.join(REGIONS_DIR))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "dat"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Writes the regions, then the header and then `meta` into save directory `dir`.
+/// `replace_regions` first clears the region files already there. A single-file save
+/// the directory was converted from is retired once the directory holds the world.
+fn write_save_dir(
+    dir: &Path,
+    data: &SaveData,
+    regions: &[MacroGridSaveData],
+    meta: &mut SaveMeta,
+    replace_regions: bool,
+) -> Result<(), PersistenceError> {
+    if replace_regions && dir.join(REGIONS_DIR).is_dir() {
+        fs::remove_dir_all(dir.join(REGIONS_DIR))?;
+    }
+    fs::create_dir_all(dir.join(REGIONS_DIR))?;
+    for region in regions {
+        let path = PersistenceManager::region_path(dir, region.mgx, region.mgy);
+        write_compressed(&path, &serde_json::to_vec(region)?)?;
+    }
+    write_compressed(&dir.join(HEADER_FILE), &serde_json::to_vec(data)?)?;
+    meta.last_saved = unix_secs(SystemTime::now());
+    write_atomically(&dir.join(META_FILE), &serde_json::to_vec(meta)?)?;
+    retire_legacy_save(dir)?;
+    Ok(())
+}
+
+/// Moves the single-file save that save directory `dir` was converted from into the
+/// directory, as the header backup after the ones already there, so it cannot turn up
+/// as a slot of its own. It is dropped if every backup slot is taken by newer saves.
+fn retire_legacy_save(dir: &Path) -> std::io::Result<()> {
+    let legacy = dir.with_extension("dat");
+    if !legacy.is_file() {
+        return Ok(());
+    }
+    let header = dir.join(HEADER_FILE);
+    match (1..=SAVE_BACKUP_COUNT)
+        .map(|n| backup_path(&header, n))
+        .find(|backup| !backup.is_file())
+    {
+        Some(backup) => fs::rename(legacy, backup),
+        None => fs::remove_file(legacy),
+    }
+}
+
+fn read_meta(dir: &Path) -> Option<SaveMeta> {
+    let json = fs::read(dir.join(META_FILE)).ok()?;
+    serde_json::from_slice(&json).ok()
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Last-modified time of `path` in Unix seconds, or 0 if unknown.
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .map_or(0, unix_secs)
+}
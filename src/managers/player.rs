@@ -1,7 +1,7 @@
 use crate::components::Player;
 use crate::constants::{
     BLOCK_SIZE, LIQUID_BUOYANCY, LIQUID_RESISTANCE, PLAYER_FRICTION_AIR, PLAYER_FRICTION_GROUND,
-    PLAYER_GRAVITY, PLAYER_TERMINAL_XVELOCITY, PLAYER_TERMINAL_YVELOCITY, SURFACE_Y_LEVEL,
+    PLAYER_GRAVITY, PLAYER_TERMINAL_XVELOCITY, PLAYER_TERMINAL_YVELOCITY,
 };
 use crate::managers::world::WorldManager;
 use macroquad::prelude::*;
@@ -27,7 +27,7 @@ impl PlayerManager {
 
         self.perform_movement_and_collisions(world_manager);
 
-        self.handle_surface_logic();
+        self.handle_surface_logic(world_manager);
     }
 
     fn get_liquid_level(&self, world_manager: &mut WorldManager) -> u8 {
@@ -132,8 +132,13 @@ impl PlayerManager {
         self.handle_collisions(world_manager, false);
     }
 
-    fn handle_surface_logic(&mut self) {
-        if self.player.y < (SURFACE_Y_LEVEL.to_f32().unwrap_or(0.0)).mul_add(BLOCK_SIZE, 4.0) {
+    fn handle_surface_logic(&mut self, world_manager: &WorldManager) {
+        let column = ((self.player.x + self.player.width / 2.0) / BLOCK_SIZE)
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+        let surface_y = world_manager.surface_height_at(column);
+        if self.player.y < (surface_y.to_f32().unwrap_or(0.0)).mul_add(BLOCK_SIZE, 4.0) {
             // Auto-store natural items
             let mut i = 0;
             while i < self.player.cargo.len() {
@@ -187,7 +192,7 @@ impl PlayerManager {
         let world_y = y.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
 
         if let Some((_, _, _, _, block)) = world_manager.get_block_at_world_coords(world_x, world_y)
-            && !block.is_broken
+            && !block.is_broken()
             && block.block_type.is_solid()
         {
             let block_rect = Rect::new(block.x, block.y, BLOCK_SIZE, BLOCK_SIZE);
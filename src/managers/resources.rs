@@ -0,0 +1,297 @@
+use crate::managers::block::{BlockData, BlockRect};
+use image::RgbaImage;
+use num_traits::ToPrimitive;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::sync::LazyLock;
+
+const RESOURCES_PATH: &str = "resources.json";
+const FORMS_PATH: &str = "data/blocks/resource_forms.json";
+const ATLAS_BYTES: &[u8] = include_bytes!("../atlas.png");
+
+/// First block id handed to natural resources; ids then run on in file order,
+/// so each form occupies one contiguous range.
+pub const NATURAL_ID_START: u32 = 100;
+/// Each form has one column of sprites in the atlas, starting here.
+const SPRITE_ORIGIN_X: f32 = 128.0;
+const SPRITE_SIZE: f32 = 8.0;
+const MAX_RARITY: f32 = 10.0;
+
+pub static NATURAL_RESOURCES: LazyLock<Vec<NaturalResource>> =
+    LazyLock::new(load_natural_resources);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceState {
+    Solid,
+    Liquid,
+}
+
+/// A natural resource from `resources.json`, resolved into block properties.
+#[derive(Clone, Debug)]
+pub struct NaturalResource {
+    pub id: u32,
+    pub key: String,
+    /// Resources listed under several forms share the item of their first entry.
+    pub item_type: String,
+    pub form: String,
+    pub name_en: String,
+    pub name_ja: String,
+    /// Designer rarity, from 0 (rarest) to 10 (most common).
+    pub rarity: f32,
+    pub state: ResourceState,
+    /// Depth range below the local surface where the resource occurs.
+    pub min_depth: i32,
+    pub max_depth: i32,
+    pub hardness: i32,
+    pub weight: i32,
+    pub map_color: [u8; 3],
+    pub sprite: BlockRect,
+}
+
+impl NaturalResource {
+    #[must_use]
+    pub fn spawns_at(&self, depth: i32) -> bool {
+        self.state == ResourceState::Solid && (self.min_depth..=self.max_depth).contains(&depth)
+    }
+
+    #[must_use]
+    pub fn to_block_data(&self) -> BlockData {
+        let is_solid = self.state == ResourceState::Solid;
+        BlockData {
+            id: self.id,
+            key: self.key.clone(),
+            is_solid,
+            is_placeable: true,
+            base_hardness: self.hardness,
+            sprite: Some(self.sprite),
+            item_type: Some(self.item_type.clone()),
+            weight: self.weight,
+            tick_interval: if is_solid { 1 } else { 2 },
+            map_color: self.map_color,
+            tint: None,
+            falls: false,
+            thaw: None,
+            heat: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ResourceEntry {
+    ja: String,
+    en: String,
+    rarity: f32,
+}
+
+#[derive(Deserialize)]
+struct ResourceForm {
+    resources: Vec<ResourceEntry>,
+}
+
+#[derive(Deserialize)]
+struct ResourceCategory {
+    #[serde(deserialize_with = "ordered_forms")]
+    forms: Vec<(String, ResourceForm)>,
+}
+
+#[derive(Deserialize)]
+struct ResourcesFile {
+    natural: ResourceCategory,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DepthBandConfig {
+    /// Band centre for the most common resources.
+    pub shallowest_centre: f32,
+    /// How much deeper the centre sits per point of rarity below the maximum.
+    pub centre_per_rarity: f32,
+    pub min_half_width: f32,
+    pub half_width_per_rarity: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FormConfig {
+    pub state: ResourceState,
+    pub hardness: i32,
+    pub weight: i32,
+    /// Used when the atlas tile for a resource is empty.
+    pub map_color: [u8; 3],
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResourceFormsConfig {
+    pub depth_band: DepthBandConfig,
+    /// Extra hardness per point of rarity below the maximum.
+    pub hardness_per_rarity: f32,
+    pub forms: HashMap<String, FormConfig>,
+}
+
+impl ResourceFormsConfig {
+    fn load() -> Self {
+        fs::read_to_string(FORMS_PATH)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(Self::fallback)
+    }
+
+    fn fallback() -> Self {
+        Self {
+            depth_band: DepthBandConfig {
+                shallowest_centre: 20.0,
+                centre_per_rarity: 45.0,
+                min_half_width: 30.0,
+                half_width_per_rarity: 10.0,
+            },
+            hardness_per_rarity: 0.0,
+            forms: HashMap::new(),
+        }
+    }
+
+    fn form(&self, key: &str) -> FormConfig {
+        self.forms.get(key).cloned().unwrap_or(FormConfig {
+            state: ResourceState::Solid,
+            hardness: 20,
+            weight: 20,
+            map_color: [128, 128, 128],
+        })
+    }
+}
+
+/// Keeps the forms in file order, which fixes the id of every resource.
+fn ordered_forms<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(String, ResourceForm)>, D::Error> {
+    struct FormsVisitor;
+
+    impl<'de> Visitor<'de> for FormsVisitor {
+        type Value = Vec<(String, ResourceForm)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map of resource forms")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut forms = Vec::new();
+            while let Some(entry) = map.next_entry()? {
+                forms.push(entry);
+            }
+            Ok(forms)
+        }
+    }
+
+    deserializer.deserialize_map(FormsVisitor)
+}
+
+fn snake_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Average colour of the opaque pixels of an atlas tile.
+fn sprite_color(atlas: &RgbaImage, sprite: BlockRect) -> Option<[u8; 3]> {
+    let x0 = sprite.x.to_u32()?;
+    let y0 = sprite.y.to_u32()?;
+    let size = sprite.w.to_u32()?;
+    let mut sum = [0u32; 3];
+    let mut count = 0u32;
+    for y in y0..y0 + size {
+        for x in x0..x0 + size {
+            let pixel = atlas.get_pixel_checked(x, y)?;
+            if pixel[3] == 0 {
+                continue;
+            }
+            for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                *total += u32::from(channel);
+            }
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    Some(sum.map(|total| (total / count).to_u8().unwrap_or(u8::MAX)))
+}
+
+fn load_natural_resources() -> Vec<NaturalResource> {
+    let Some(file) = fs::read_to_string(RESOURCES_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str::<ResourcesFile>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let config = ResourceFormsConfig::load();
+    let band = &config.depth_band;
+    let atlas = image::load_from_memory(ATLAS_BYTES)
+        .ok()
+        .map(|img| img.to_rgba8());
+
+    let mut resources = Vec::new();
+    let mut item_types: HashMap<String, String> = HashMap::new();
+    let mut keys = HashSet::new();
+    let mut id = NATURAL_ID_START;
+
+    for (form_index, (form_key, form)) in file.natural.forms.into_iter().enumerate() {
+        let form_config = config.form(&form_key);
+        for (index, entry) in form.resources.into_iter().enumerate() {
+            let name_key = snake_case(&entry.en);
+            let key = if keys.contains(&name_key) {
+                format!("{name_key}_{form_key}")
+            } else {
+                name_key.clone()
+            };
+            keys.insert(key.clone());
+            let item_type = item_types
+                .entry(name_key)
+                .or_insert_with(|| key.clone())
+                .clone();
+
+            let sprite = BlockRect {
+                x: SPRITE_ORIGIN_X + form_index.to_f32().unwrap_or(0.0) * SPRITE_SIZE,
+                y: index.to_f32().unwrap_or(0.0) * SPRITE_SIZE,
+                w: SPRITE_SIZE,
+                h: SPRITE_SIZE,
+            };
+            let scarcity = (MAX_RARITY - entry.rarity).clamp(0.0, MAX_RARITY);
+            let centre = scarcity.mul_add(band.centre_per_rarity, band.shallowest_centre);
+            let half_width = scarcity.mul_add(band.half_width_per_rarity, band.min_half_width);
+            let hardness = if form_config.hardness < 0 {
+                form_config.hardness
+            } else {
+                (form_config.hardness.to_f32().unwrap_or(0.0)
+                    * scarcity.mul_add(config.hardness_per_rarity, 1.0))
+                .round()
+                .to_i32()
+                .unwrap_or(form_config.hardness)
+            };
+
+            resources.push(NaturalResource {
+                id,
+                key,
+                item_type,
+                form: form_key.clone(),
+                name_en: entry.en,
+                name_ja: entry.ja,
+                rarity: entry.rarity,
+                state: form_config.state,
+                min_depth: (centre - half_width).round().to_i32().unwrap_or(0).max(1),
+                max_depth: (centre + half_width).round().to_i32().unwrap_or(0),
+                hardness,
+                weight: form_config.weight,
+                map_color: atlas
+                    .as_ref()
+                    .and_then(|atlas| sprite_color(atlas, sprite))
+                    .unwrap_or(form_config.map_color),
+                sprite,
+            });
+            id += 1;
+        }
+    }
+    resources
+}
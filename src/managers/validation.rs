@@ -0,0 +1,236 @@
+use crate::components::{CHUNK_AREA, WarpGate};
+use crate::error::PersistenceError;
+use crate::managers::persistence::{
+    ChunkSaveData, ItemStack, MacroGridSaveData, SAVE_VERSION, SaveData,
+};
+use crate::utils::chunk_to_macrogrid_coords;
+
+/// Checks a save is current, its player state makes sense and every region it carries
+/// would load back as written.
+///
+/// # Errors
+///
+/// Returns `Corrupt` naming the first problem found.
+pub fn validate_save(data: &SaveData) -> Result<(), PersistenceError> {
+    if data.version != SAVE_VERSION {
+        return Err(PersistenceError::Corrupt(format!(
+            "version {} is not the current version {SAVE_VERSION}",
+            data.version
+        )));
+    }
+    let floats = [
+        ("player_x", data.player_x),
+        ("player_y", data.player_y),
+        ("player_vx", data.player_vx),
+        ("player_vy", data.player_vy),
+        ("player_fuel", data.player_fuel),
+        ("player_max_fuel", data.player_max_fuel),
+    ];
+    if let Some((field, _)) = floats.iter().find(|(_, value)| !value.is_finite()) {
+        return Err(PersistenceError::Corrupt(format!(
+            "{field} is not a number"
+        )));
+    }
+    data.player_cargo
+        .iter()
+        .chain(&data.player_storage)
+        .try_for_each(validate_stack)?;
+    data.player_warp_gates
+        .iter()
+        .try_for_each(validate_warp_gate)?;
+    data.modified_macrogrids
+        .iter()
+        .try_for_each(validate_region)
+}
+
+/// Checks a stack names an item and holds at least one of it.
+///
+/// # Errors
+///
+/// Returns `Corrupt` saying what is wrong.
+pub fn validate_stack(stack: &ItemStack) -> Result<(), PersistenceError> {
+    if stack.item_type.is_empty() {
+        return Err(PersistenceError::Corrupt(
+            "stack without an item type".to_string(),
+        ));
+    }
+    if stack.count == 0 {
+        return Err(PersistenceError::Corrupt(format!(
+            "empty stack of {}",
+            stack.item_type
+        )));
+    }
+    Ok(())
+}
+
+/// Checks a warp gate has a name and a position to warp to.
+///
+/// # Errors
+///
+/// Returns `Corrupt` saying what is wrong.
+pub fn validate_warp_gate(gate: &WarpGate) -> Result<(), PersistenceError> {
+    if gate.name.is_empty() {
+        return Err(PersistenceError::Corrupt(
+            "warp gate without a name".to_string(),
+        ));
+    }
+    if !gate.x.is_finite() || !gate.y.is_finite() {
+        return Err(PersistenceError::Corrupt(format!(
+            "warp gate {} has no position",
+            gate.name
+        )));
+    }
+    Ok(())
+}
+
+/// Checks each chunk of a region lies inside it and decodes to exactly one chunk.
+///
+/// # Errors
+///
+/// Returns `Corrupt` naming the first problem found.
+pub fn validate_region(region: &MacroGridSaveData) -> Result<(), PersistenceError> {
+    region_problems(region)
+        .into_iter()
+        .next()
+        .map_or(Ok(()), Err)
+}
+
+/// Every problem `validate_region` would find, at most one per chunk.
+#[must_use]
+pub fn region_problems(region: &MacroGridSaveData) -> Vec<PersistenceError> {
+    region
+        .chunks
+        .iter()
+        .filter_map(|chunk| {
+            let (mg_coords, _) = chunk_to_macrogrid_coords(chunk.cx, chunk.cy);
+            if (mg_coords.x, mg_coords.y) == (region.mgx, region.mgy) {
+                validate_chunk(chunk).err()
+            } else {
+                Some(PersistenceError::Corrupt(format!(
+                    "chunk ({}, {}) is not in region ({}, {})",
+                    chunk.cx, chunk.cy, region.mgx, region.mgy
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Drops the chunks `region_problems` objects to, returning what was wrong with them.
+pub fn remove_invalid_chunks(region: &mut MacroGridSaveData) -> Vec<PersistenceError> {
+    let problems = region_problems(region);
+    if !problems.is_empty() {
+        let (mgx, mgy) = (region.mgx, region.mgy);
+        region.chunks.retain(|chunk| {
+            let (mg_coords, _) = chunk_to_macrogrid_coords(chunk.cx, chunk.cy);
+            (mg_coords.x, mg_coords.y) == (mgx, mgy) && validate_chunk(chunk).is_ok()
+        });
+    }
+    problems
+}
+
+fn validate_chunk(chunk: &ChunkSaveData) -> Result<(), PersistenceError> {
+    let corrupt = |reason: &str| {
+        PersistenceError::Corrupt(format!("chunk ({}, {}) {reason}", chunk.cx, chunk.cy))
+    };
+    if !chunk.blocks.len().is_multiple_of(3) {
+        return Err(corrupt("has a partial block run"));
+    }
+    let count: u64 = chunk
+        .blocks
+        .chunks_exact(3)
+        .map(|run| u64::from(run[2]))
+        .sum();
+    if count != CHUNK_AREA as u64 {
+        return Err(corrupt(&format!(
+            "holds {count} blocks instead of {CHUNK_AREA}"
+        )));
+    }
+    if chunk
+        .named_blocks
+        .iter()
+        .any(|block| block.i as usize >= CHUNK_AREA)
+    {
+        return Err(corrupt("names a block outside it"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::BlockType;
+    use crate::managers::persistence::BlockSaveData;
+
+    fn chunk(cx: i32, cy: i32, blocks: Vec<u32>) -> ChunkSaveData {
+        ChunkSaveData {
+            cx,
+            cy,
+            blocks,
+            named_blocks: Vec::new(),
+        }
+    }
+
+    fn full_chunk(cx: i32, cy: i32) -> ChunkSaveData {
+        let area = u32::try_from(CHUNK_AREA).unwrap_or(u32::MAX);
+        chunk(cx, cy, vec![1, 0, area - 1, 0, 0, 1])
+    }
+
+    fn region(chunks: Vec<ChunkSaveData>) -> MacroGridSaveData {
+        MacroGridSaveData {
+            mgx: 0,
+            mgy: 0,
+            chunks,
+        }
+    }
+
+    #[test]
+    fn accepts_whole_chunks_inside_the_region() {
+        let region = region(vec![full_chunk(0, 0), full_chunk(1, 2)]);
+
+        assert!(region_problems(&region).is_empty());
+        assert!(validate_region(&region).is_ok());
+    }
+
+    #[test]
+    fn reports_one_problem_per_bad_chunk() {
+        let mut named = full_chunk(3, 0);
+        named.named_blocks.push(BlockSaveData {
+            i: u32::try_from(CHUNK_AREA).unwrap_or(u32::MAX),
+            t: BlockType::WarpGate,
+            n: Some("Gate".to_string()),
+        });
+        let region = region(vec![
+            full_chunk(0, 0),
+            chunk(1, 0, vec![1, 0, 10]),
+            chunk(2, 0, vec![1, 0]),
+            named,
+            full_chunk(-1, 0),
+        ]);
+
+        let problems: Vec<String> = region_problems(&region)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(problems.len(), 4);
+        assert!(problems[0].contains("chunk (1, 0) holds 10 blocks"));
+        assert!(problems[1].contains("chunk (2, 0) has a partial block run"));
+        assert!(problems[2].contains("chunk (3, 0) names a block outside it"));
+        assert!(problems[3].contains("chunk (-1, 0) is not in region (0, 0)"));
+        assert!(validate_region(&region).is_err());
+    }
+
+    #[test]
+    fn removing_invalid_chunks_keeps_the_rest() {
+        let mut region = region(vec![
+            full_chunk(0, 0),
+            chunk(1, 0, vec![1, 0, 10]),
+            full_chunk(-1, 0),
+            full_chunk(2, 0),
+        ]);
+
+        assert_eq!(remove_invalid_chunks(&mut region).len(), 2);
+        let kept: Vec<(i32, i32)> = region.chunks.iter().map(|c| (c.cx, c.cy)).collect();
+        assert_eq!(kept, vec![(0, 0), (2, 0)]);
+        assert!(region_problems(&region).is_empty());
+    }
+}
@@ -1,53 +1,97 @@
 use super::WorldManager;
 use super::generation;
 use super::modifications;
-use crate::components::{Block, BlockPos, Chunk, ChunkRelPos};
+use crate::components::{Block, BlockPos, BlockType, Chunk, ChunkRelPos};
 use crate::constants::{CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS};
 use crate::utils::{
     chunk_to_macrogrid_coords, world_to_chunk_coords, world_to_relative_in_chunk_coords,
 };
 use num_traits::ToPrimitive;
 
+/// Chunk holding block `(bx, by)` and the block's cell within it.
+fn locate(bx: i32, by: i32) -> (i32, i32, usize, usize) {
+    let size_x = CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(1);
+    let size_y = CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(1);
+    (
+        bx.div_euclid(size_x),
+        by.div_euclid(size_y),
+        bx.rem_euclid(size_x).to_usize().unwrap_or(0),
+        by.rem_euclid(size_y).to_usize().unwrap_or(0),
+    )
+}
+
 impl WorldManager {
     pub fn ensure_chunk_exists_and_generated(&mut self, chunk_x: i32, chunk_y: i32) {
-        let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(chunk_x, chunk_y);
-        let macrogrid = self.macrogrids.entry(mg_coords).or_default();
+        if !self.is_chunk_generated(chunk_x, chunk_y) {
+            self.install_chunk(generation::generate_chunk(
+                chunk_x,
+                chunk_y,
+                &self.generator,
+            ));
+        }
 
-        let entry = macrogrid
-            .chunks
-            .entry(rel_coords)
-            .or_insert_with(|| Chunk::new(chunk_x, chunk_y));
-
-        if !entry.is_generated {
-            entry.blocks = generation::generate_chunk_blocks(chunk_x, chunk_y, &self.noise_ore);
-            entry.is_generated = true;
-            self.generated_chunk_coords
-                .insert(BlockPos::new(chunk_x, chunk_y));
-
-            // Apply pending modifications if any (Lazy Loading)
-            if let Some(mod_data) = self
-                .pending_modifications
-                .remove(&BlockPos::new(chunk_x, chunk_y))
-            {
-                modifications::apply_chunk_save_data(entry, &mod_data);
-            }
+        self.chunk_access
+            .insert(BlockPos::new(chunk_x, chunk_y), self.access_clock);
+        self.visited_chunks.insert(BlockPos::new(chunk_x, chunk_y));
+    }
+
+    #[must_use]
+    pub fn is_chunk_generated(&self, chunk_x: i32, chunk_y: i32) -> bool {
+        self.get_chunk(chunk_x, chunk_y)
+            .is_some_and(|chunk| chunk.is_generated)
+    }
+
+    /// Puts a freshly generated chunk into the world, whether it was generated here or
+    /// on a worker thread. Chunks that are already generated are left alone.
+    pub(crate) fn install_chunk(&mut self, mut chunk: Chunk) {
+        let (chunk_x, chunk_y) = (chunk.cx, chunk.cy);
+        if self.is_chunk_generated(chunk_x, chunk_y) {
+            return;
+        }
+
+        // Apply pending modifications if any (Lazy Loading)
+        self.load_region_of(chunk_x, chunk_y);
+        if let Some(mod_data) = self
+            .pending_modifications
+            .remove(&BlockPos::new(chunk_x, chunk_y))
+        {
+            modifications::apply_chunk_save_data(&mut chunk, &mod_data);
+        }
 
-            // Track liquids
-            for (bx, row) in entry.blocks.iter().enumerate() {
-                for (by, block) in row.iter().enumerate() {
-                    if block.block_type.is_liquid() {
-                        let world_bx = chunk_x * CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0)
-                            + bx.to_i32().unwrap_or(0);
-                        let world_by = chunk_y * CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0)
-                            + by.to_i32().unwrap_or(0);
-                        self.active_liquids
-                            .insert(BlockPos::new(world_bx, world_by));
-                    }
-                }
+        // Track liquids that can still flow; settled rivers, lakes and aquifers wait
+        // until disturbed. Pumps placed in a saved chunk start working again.
+        let mut liquids = Vec::new();
+        let mut has_heat = false;
+        for block in chunk.blocks() {
+            let (bx, by) = block.coords();
+            has_heat |= block.block_type.get_heat().is_some();
+            if block.block_type.is_liquid() {
+                liquids.push(BlockPos::new(bx, by));
+            } else if block.block_type == BlockType::Pump {
+                self.pumps.insert(BlockPos::new(bx, by));
             }
         }
 
-        self.visited_chunks.insert(BlockPos::new(chunk_x, chunk_y));
+        let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(chunk_x, chunk_y);
+        self.macrogrids
+            .entry(mg_coords)
+            .or_default()
+            .chunks
+            .insert(rel_coords, chunk);
+        self.generated_chunk_coords
+            .insert(BlockPos::new(chunk_x, chunk_y));
+        self.chunk_access
+            .insert(BlockPos::new(chunk_x, chunk_y), self.access_clock);
+
+        for pos in liquids {
+            if !self.is_liquid_at_rest(pos.x, pos.y) {
+                self.active_liquids.insert(pos);
+            }
+        }
+        self.activate_border_liquids(chunk_x, chunk_y);
+        if has_heat {
+            self.invalidate_temperatures();
+        }
     }
 
     pub fn get_chunk_mut(&mut self, chunk_x: i32, chunk_y: i32) -> Option<&mut Chunk> {
@@ -63,84 +107,49 @@ impl WorldManager {
         self.macrogrids.get(&mg_coords)?.chunks.get(&rel_coords)
     }
 
+    #[must_use]
     pub fn get_block_at_world_coords(
-        &mut self,
+        &self,
         world_x: f32,
         world_y: f32,
-    ) -> Option<(i32, i32, usize, usize, &mut Block)> {
+    ) -> Option<(i32, i32, usize, usize, Block)> {
         let BlockPos { x: cx, y: cy } = world_to_chunk_coords(world_x, world_y);
         let ChunkRelPos { x: rel_x, y: rel_y } =
             world_to_relative_in_chunk_coords(world_x, world_y);
-
-        let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(cx, cy);
-        let chunk = self
-            .macrogrids
-            .get_mut(&mg_coords)?
-            .chunks
-            .get_mut(&rel_coords)?;
-
-        if chunk.is_generated {
-            Some((cx, cy, rel_x, rel_y, chunk.get_block(rel_x, rel_y)?))
-        } else {
-            None
-        }
+        let block = self.get_chunk(cx, cy)?.get_block(rel_x, rel_y)?;
+        Some((cx, cy, rel_x, rel_y, block))
     }
 
-    pub fn get_block_mut(&mut self, bx: i32, by: i32) -> Option<&mut Block> {
-        let chunk_x = (bx.to_f32().unwrap_or(0.0) / CHUNK_SIZE_X_BLOCKS.to_f32().unwrap_or(0.0))
-            .floor()
-            .to_i32()
-            .unwrap_or(0);
-        let chunk_y = (by.to_f32().unwrap_or(0.0) / CHUNK_SIZE_Y_BLOCKS.to_f32().unwrap_or(0.0))
-            .floor()
-            .to_i32()
-            .unwrap_or(0);
-
-        let rel_x = ((bx % CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0))
-            + CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0))
-            % CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0);
-        let rel_y = ((by % CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0))
-            + CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0))
-            % CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0);
-
-        let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(chunk_x, chunk_y);
-        let chunk = self
-            .macrogrids
-            .get_mut(&mg_coords)?
-            .chunks
-            .get_mut(&rel_coords)?;
+    /// A copy of block `(bx, by)`, or `None` while its chunk is not loaded.
+    #[must_use]
+    pub fn get_block(&self, bx: i32, by: i32) -> Option<Block> {
+        let (cx, cy, rel_x, rel_y) = locate(bx, by);
+        self.get_chunk(cx, cy)?.get_block(rel_x, rel_y)
+    }
 
-        if chunk.is_generated {
-            chunk.get_block(rel_x.to_usize().unwrap_or(0), rel_y.to_usize().unwrap_or(0))
-        } else {
-            None
+    /// Writes a changed copy from `get_block` back into its chunk.
+    pub fn set_block(&mut self, block: &Block) {
+        let (bx, by) = block.coords();
+        let (cx, cy, rel_x, rel_y) = locate(bx, by);
+        let is_hot = |b: &Block| b.block_type.get_heat().is_some();
+        if is_hot(block) || self.get_block(bx, by).is_some_and(|b| is_hot(&b)) {
+            self.invalidate_temperatures();
+        }
+        if let Some(chunk) = self.get_chunk_mut(cx, cy) {
+            chunk.set_block(rel_x, rel_y, block);
         }
     }
 
-    pub fn get_block_ref(&self, bx: i32, by: i32) -> Option<&Block> {
-        let chunk_x = (bx.to_f32().unwrap_or(0.0) / CHUNK_SIZE_X_BLOCKS.to_f32().unwrap_or(0.0))
-            .floor()
-            .to_i32()
-            .unwrap_or(0);
-        let chunk_y = (by.to_f32().unwrap_or(0.0) / CHUNK_SIZE_Y_BLOCKS.to_f32().unwrap_or(0.0))
-            .floor()
-            .to_i32()
-            .unwrap_or(0);
-
-        let rel_x = ((bx % CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0))
-            + CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0))
-            % CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0);
-        let rel_y = ((by % CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0))
-            + CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0))
-            % CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0);
-
-        let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(chunk_x, chunk_y);
-        let chunk = self.macrogrids.get(&mg_coords)?.chunks.get(&rel_coords)?;
+    #[must_use]
+    pub fn block_name(&self, bx: i32, by: i32) -> Option<&str> {
+        let (cx, cy, rel_x, rel_y) = locate(bx, by);
+        self.get_chunk(cx, cy)?.name(rel_x, rel_y)
+    }
 
-        if chunk.is_generated {
-            Some(&chunk.blocks[rel_x.to_usize().unwrap_or(0)][rel_y.to_usize().unwrap_or(0)])
-        } else {
-            None
+    pub fn set_block_name(&mut self, bx: i32, by: i32, name: Option<String>) {
+        let (cx, cy, rel_x, rel_y) = locate(bx, by);
+        if let Some(chunk) = self.get_chunk_mut(cx, cy) {
+            chunk.set_name(rel_x, rel_y, name);
         }
     }
 }
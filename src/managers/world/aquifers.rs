@@ -0,0 +1,238 @@
+use super::WorldManager;
+use super::generation::{ChunkCells, WorldGenerator};
+use super::strata::STRATA_CONFIG;
+use crate::components::{BlockPos, BlockType};
+use crate::constants::{CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS};
+use crate::utils::{hash_coords, hash_to_unit};
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::LazyLock;
+
+const AQUIFERS_PATH: &str = "data/worldgen/aquifers.json";
+const SALT_RESERVE: u32 = 0xA90F_0001;
+
+// Offset along the unused noise axis, apart from the cave and strata fields.
+const AQUIFER_Z: f64 = 155.3;
+
+pub static AQUIFER_CONFIG: LazyLock<Option<AquiferConfig>> = LazyLock::new(|| {
+    fs::read_to_string(AQUIFERS_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+});
+
+/// Indices of the strata layers named in the config.
+static AQUIFER_LAYERS: LazyLock<Vec<usize>> = LazyLock::new(|| {
+    AQUIFER_CONFIG.as_ref().map_or_else(Vec::new, |config| {
+        STRATA_CONFIG
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| config.layers.contains(&layer.key))
+            .map(|(i, _)| i)
+            .collect()
+    })
+});
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AquiferConfig {
+    pub layers: Vec<String>,
+    /// Porous rocks that hold the water.
+    pub host_blocks: Vec<u32>,
+    pub noise_scale: f64,
+    /// Stretches the noise vertically; above 1.0 aquifers spread out as flat beds.
+    pub vertical_scale: f64,
+    pub threshold: f64,
+    pub min_depth: i32,
+    /// Side of the square of aquifer that draws on one reservoir.
+    pub reservoir_size_blocks: i32,
+    /// Liquid levels (8 per block) a reservoir releases on top of the water in its rock.
+    pub min_reserve: u32,
+    pub max_reserve: u32,
+    /// Levels a breached aquifer cell takes back from its reservoir per liquid tick.
+    pub inflow_per_tick: u8,
+    /// Spacing of the samples that decide whether a chunk shows an aquifer hint on the map.
+    pub hint_spacing_blocks: usize,
+}
+
+impl WorldGenerator {
+    /// The reservoir feeding block `(bx, by)` if it lies in an aquifer. Judged from the noise
+    /// and strata alone, so chunk borders and the liquid simulation agree with generation.
+    #[must_use]
+    pub fn aquifer_at(&self, bx: i32, by: i32) -> Option<BlockPos> {
+        let config = AQUIFER_CONFIG.as_ref()?;
+        let saturation = self.noise_ore.get([
+            f64::from(bx) * config.noise_scale,
+            f64::from(by) * config.noise_scale * config.vertical_scale,
+            AQUIFER_Z,
+        ]);
+        if saturation <= config.threshold + self.settings.liquid_abundance.threshold_shift() {
+            return None;
+        }
+
+        // Depth below the surface as it stood before erosion, as the strata use it.
+        let exhumed = self.erosion_at(bx).exhumation.round().to_i32().unwrap_or(0);
+        let depth = by - self.surface_height_at(bx) + exhumed;
+        if depth < config.min_depth {
+            return None;
+        }
+        let strata = self.strata_at(bx, by, depth, &self.plates.sample(bx, by));
+        if !AQUIFER_LAYERS.contains(&strata.layer_index)
+            || !config.host_blocks.contains(&strata.block_type.to_id())
+        {
+            return None;
+        }
+
+        let size = config.reservoir_size_blocks.max(1);
+        Some(BlockPos::new(bx.div_euclid(size), by.div_euclid(size)))
+    }
+
+    /// Liquid levels `reservoir` holds before anything is drawn from it.
+    #[must_use]
+    pub fn aquifer_capacity(&self, reservoir: BlockPos) -> u32 {
+        let Some(config) = &*AQUIFER_CONFIG else {
+            return 0;
+        };
+        let roll = hash_to_unit(hash_coords(
+            self.seed ^ SALT_RESERVE,
+            reservoir.x,
+            reservoir.y,
+        ));
+        let span = config.max_reserve.saturating_sub(config.min_reserve);
+        let reserve = config.min_reserve.to_f32().unwrap_or(0.0)
+            + (span.to_f32().unwrap_or(0.0) * roll).floor();
+        (reserve * self.settings.liquid_abundance.factor())
+            .to_u32()
+            .unwrap_or(0)
+    }
+
+    /// Whether chunk `(cx, cy)` holds aquifer water, sampled on a coarse grid.
+    #[must_use]
+    pub fn chunk_has_aquifer(&self, cx: i32, cy: i32) -> bool {
+        let Some(config) = &*AQUIFER_CONFIG else {
+            return false;
+        };
+        let origin_bx = cx * CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0);
+        let origin_by = cy * CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0);
+        let step = config.hint_spacing_blocks.max(1);
+        (0..CHUNK_SIZE_X_BLOCKS).step_by(step).any(|x| {
+            (0..CHUNK_SIZE_Y_BLOCKS).step_by(step).any(|y| {
+                self.aquifer_at(
+                    origin_bx + x.to_i32().unwrap_or(0),
+                    origin_by + y.to_i32().unwrap_or(0),
+                )
+                .is_some()
+            })
+        })
+    }
+}
+
+/// Soaks the porous rock of aquifers with water (TGIP Phase 11). Runs after the caves so
+/// tunnels through an aquifer are flooded, then walls off every opening beside the water
+/// with the rock that was cut away, leaving the aquifer sealed until the player breaks in.
+pub fn fill_aquifers(generator: &WorldGenerator, chunk: &mut ChunkCells) {
+    let Some(config) = &*AQUIFER_CONFIG else {
+        return;
+    };
+    let (origin_bx, origin_by) = (chunk.origin_bx, chunk.origin_by);
+
+    for (x, column) in chunk.cells.iter_mut().enumerate() {
+        let bx = origin_bx + x.to_i32().unwrap_or(0);
+        for (y, cell) in column.iter_mut().enumerate() {
+            if cell.layer.is_none() {
+                continue;
+            }
+            // Ore bodies stay solid inside the aquifer.
+            let is_host = config.host_blocks.contains(&cell.block_type.to_id());
+            if !is_host && cell.block_type.is_solid() {
+                continue;
+            }
+            let by = origin_by + y.to_i32().unwrap_or(0);
+            if generator.aquifer_at(bx, by).is_some() {
+                cell.block_type = BlockType::Water;
+                cell.layer = None;
+            }
+        }
+    }
+
+    for (x, column) in chunk.cells.iter_mut().enumerate() {
+        let bx = origin_bx + x.to_i32().unwrap_or(0);
+        for (y, cell) in column.iter_mut().enumerate() {
+            if cell.block_type.is_solid() || cell.depth < config.min_depth - 1 {
+                continue;
+            }
+            let by = origin_by + y.to_i32().unwrap_or(0);
+            if generator.aquifer_at(bx, by).is_some() {
+                continue;
+            }
+            let beside_aquifer = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .iter()
+                .any(|&(dx, dy)| generator.aquifer_at(bx + dx, by + dy).is_some());
+            if beside_aquifer {
+                cell.block_type = if cell.back_type.is_solid() {
+                    cell.back_type
+                } else {
+                    BlockType::Stone
+                };
+            }
+        }
+    }
+}
+
+impl WorldManager {
+    /// The reservoir feeding block `(bx, by)` if it lies in an aquifer.
+    #[must_use]
+    pub fn aquifer_at(&self, bx: i32, by: i32) -> Option<BlockPos> {
+        self.generator.aquifer_at(bx, by)
+    }
+
+    /// Liquid levels `reservoir` can still release.
+    #[must_use]
+    pub fn aquifer_reserve(&self, reservoir: BlockPos) -> u32 {
+        self.aquifer_reserves
+            .get(&reservoir)
+            .copied()
+            .unwrap_or_else(|| self.generator.aquifer_capacity(reservoir))
+    }
+
+    /// Takes up to `amount` levels out of `reservoir` and returns how many it gave.
+    pub fn draw_from_aquifer(&mut self, reservoir: BlockPos, amount: u32) -> u32 {
+        let reserve = self.aquifer_reserve(reservoir);
+        let drawn = amount.min(reserve);
+        self.aquifer_reserves.insert(reservoir, reserve - drawn);
+        drawn
+    }
+
+    /// Tops a breached aquifer cell up from its reservoir, returning its new level.
+    pub(crate) fn recharge_aquifer_cell(&mut self, bx: i32, by: i32, level: u8) -> u8 {
+        let Some(config) = &*AQUIFER_CONFIG else {
+            return level;
+        };
+        let generator = &self.generator;
+        let Some(reservoir) = *self
+            .aquifer_cells
+            .entry(BlockPos::new(bx, by))
+            .or_insert_with(|| generator.aquifer_at(bx, by))
+        else {
+            return level;
+        };
+        let wanted = 8u8.saturating_sub(level).min(config.inflow_per_tick);
+        let drawn = self
+            .draw_from_aquifer(reservoir, u32::from(wanted))
+            .to_u8()
+            .unwrap_or(0);
+        if drawn > 0 {
+            self.set_liquid_block(bx, by, level + drawn, BlockType::Water);
+        }
+        level + drawn
+    }
+
+    /// Whether chunk `(cx, cy)` holds aquifer water; cached for the map.
+    pub fn chunk_has_aquifer(&mut self, cx: i32, cy: i32) -> bool {
+        *self
+            .aquifer_hints
+            .entry(BlockPos::new(cx, cy))
+            .or_insert_with(|| self.generator.chunk_has_aquifer(cx, cy))
+    }
+}
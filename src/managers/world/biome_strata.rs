@@ -0,0 +1,157 @@
+use super::generation::{ChunkCells, WorldGenerator};
+use super::geothermal::rock_temperature;
+use crate::components::BlockType;
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::LazyLock;
+
+const BIOME_STRATA_PATH: &str = "data/worldgen/biome_strata.json";
+
+// Offsets along the unused noise axis so each field is independent.
+const BEDS_Z: f64 = 95.3;
+const NODULES_Z: f64 = 139.7;
+
+pub static BIOME_STRATA: LazyLock<Vec<BiomeStratum>> = LazyLock::new(|| {
+    fs::read_to_string(BIOME_STRATA_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StratumHost {
+    /// The biome's surface and subsoil blocks.
+    Soil,
+    /// Bedrock strata.
+    Rock,
+}
+
+/// Limits a stratum to flat-lying beds, fringed by a second block along their edges.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BedsConfig {
+    pub noise_scale: f64,
+    /// Stretches the noise vertically; above 1.0 beds become thin and flat.
+    pub vertical_scale: f64,
+    pub threshold: f64,
+    pub rim_block: u32,
+    /// Noise band below the threshold that becomes the rim.
+    pub rim_width: f64,
+}
+
+/// Concretions of another block scattered through the stratum.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodulesConfig {
+    pub block: u32,
+    pub noise_scale: f64,
+    pub threshold: f64,
+}
+
+/// A layer that only forms under some climates (TGIP Phase 10), such as permafrost under
+/// tundra, evaporite beds under desert and laterite in rainforest soil.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BiomeStratum {
+    pub key: String,
+    pub biomes: Vec<String>,
+    pub block: u32,
+    pub hosts: Vec<StratumHost>,
+    /// Depth range below the local surface.
+    pub min_depth: i32,
+    pub max_depth: i32,
+    #[serde(default)]
+    pub beds: Option<BedsConfig>,
+    #[serde(default)]
+    pub nodules: Option<NodulesConfig>,
+}
+
+impl WorldGenerator {
+    /// Block the stratum puts at `(bx, by)` within its depth range, if any.
+    fn biome_stratum_block(&self, stratum: &BiomeStratum, bx: i32, by: i32) -> Option<BlockType> {
+        let x = f64::from(bx);
+        let y = f64::from(by);
+        let mut block = BlockType::from_id(stratum.block);
+
+        if let Some(beds) = &stratum.beds {
+            let bed = self.noise_main.get([
+                x * beds.noise_scale,
+                y * beds.noise_scale * beds.vertical_scale,
+                BEDS_Z,
+            ]);
+            if bed <= beds.threshold - beds.rim_width {
+                return None;
+            }
+            if bed <= beds.threshold {
+                block = BlockType::from_id(beds.rim_block);
+            }
+        }
+
+        if let Some(nodules) = &stratum.nodules
+            && self
+                .noise_ore
+                .get([x * nodules.noise_scale, y * nodules.noise_scale, NODULES_Z])
+                > nodules.threshold
+        {
+            block = BlockType::from_id(nodules.block);
+        }
+        Some(block)
+    }
+}
+
+/// Lays the climate-bound strata into the soil and upper bedrock of each column. Frozen
+/// blocks stop at the depth where the ground temperature reaches their thaw point.
+pub fn apply_biome_strata(generator: &WorldGenerator, chunk: &mut ChunkCells) {
+    let strata = &*BIOME_STRATA;
+    if strata.is_empty() {
+        return;
+    }
+    let (origin_bx, origin_by) = (chunk.origin_bx, chunk.origin_by);
+
+    for (x, column) in chunk.cells.iter_mut().enumerate() {
+        let bx = origin_bx + x.to_i32().unwrap_or(0);
+        let ground_y = chunk.ground[x];
+        let biome = chunk.climate[x].biome();
+        let column_strata: Vec<&BiomeStratum> = strata
+            .iter()
+            .filter(|s| s.biomes.contains(&biome.key))
+            .collect();
+        if column_strata.is_empty() {
+            continue;
+        }
+        let gradient = generator.geothermal_gradient(bx, ground_y);
+
+        for (y, cell) in column.iter_mut().enumerate() {
+            let by = origin_by + y.to_i32().unwrap_or(0);
+            let depth = by - ground_y;
+            let host = if cell.is_host_rock() {
+                StratumHost::Rock
+            } else if cell.layer.is_none()
+                && (cell.block_type == biome.subsoil() || cell.block_type == biome.surface())
+            {
+                StratumHost::Soil
+            } else {
+                continue;
+            };
+
+            for stratum in &column_strata {
+                if !stratum.hosts.contains(&host)
+                    || !(stratum.min_depth..=stratum.max_depth).contains(&depth)
+                {
+                    continue;
+                }
+                if let Some(thaw) = BlockType::from_id(stratum.block).get_thaw() {
+                    if rock_temperature(depth, biome.base_temperature, gradient) >= thaw.temperature
+                    {
+                        continue;
+                    }
+                }
+                if let Some(block) = generator.biome_stratum_block(stratum, bx, by) {
+                    cell.block_type = block;
+                    cell.back_type = block;
+                    break;
+                }
+            }
+        }
+    }
+}
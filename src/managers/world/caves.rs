@@ -0,0 +1,253 @@
+use super::generation::{ChunkCells, WorldGenerator};
+use super::strata::STRATA_CONFIG;
+use crate::components::BlockType;
+use crate::utils::{hash_coords, hash_to_unit};
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::LazyLock;
+
+const CAVES_PATH: &str = "data/worldgen/caves.json";
+const SALT_DRIPSTONE: u32 = 0xCA7E_0001;
+const SALT_LAVA: u32 = 0xCA7E_0002;
+
+// Offsets along the unused noise axis so each cave system is independent.
+const WORM_Z: f64 = 5.3;
+const WORM_GATE_Z: f64 = 19.7;
+const CAVERN_Z: f64 = 35.1;
+const KARST_Z: f64 = 51.9;
+const LAVA_TUBE_Z: f64 = 250.3;
+
+pub static CAVE_CONFIG: LazyLock<CaveConfig> = LazyLock::new(CaveConfig::load);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WormConfig {
+    pub noise_scale: f64,
+    /// Stretches the noise vertically; above 1.0 tunnels run more horizontally.
+    pub vertical_scale: f64,
+    pub width: f64,
+    /// Tunnels widen with depth up to `max_width`.
+    pub width_per_depth: f64,
+    pub max_width: f64,
+    pub gate_noise_scale: f64,
+    /// Tunnels are cut off where the gate noise falls below this.
+    pub gate_threshold: f64,
+    pub min_depth: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CavernConfig {
+    pub noise_scale: f64,
+    pub vertical_scale: f64,
+    pub threshold: f64,
+    pub min_depth: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KarstConfig {
+    pub layers: Vec<String>,
+    /// Soluble rocks the karst dissolves.
+    pub host_blocks: Vec<u32>,
+    pub noise_scale: f64,
+    pub threshold: f64,
+    pub min_depth: i32,
+    pub dripstone_block: u32,
+    /// Chance of a stalactite under a karst ceiling or a stalagmite on its floor.
+    pub dripstone_chance: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LavaTubeConfig {
+    pub biomes: Vec<String>,
+    pub noise_scale: f64,
+    pub vertical_scale: f64,
+    pub width: f64,
+    pub min_depth: i32,
+    pub max_depth: i32,
+    pub lava_block: u32,
+    /// Chance that a stretch of tube floor holds lava.
+    pub lava_chance: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaveConfig {
+    pub worms: Option<WormConfig>,
+    pub caverns: Option<CavernConfig>,
+    pub karst: Option<KarstConfig>,
+    pub lava_tubes: Option<LavaTubeConfig>,
+}
+
+impl CaveConfig {
+    fn load() -> Self {
+        fs::read_to_string(CAVES_PATH)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or(Self {
+                worms: None,
+                caverns: None,
+                karst: None,
+                lava_tubes: None,
+            })
+    }
+}
+
+impl WorldGenerator {
+    /// Whether the worm tunnels or caverns open up the block `depth` blocks below the surface.
+    /// Depends only on world coordinates, so neighbouring chunks agree on every border.
+    fn is_tunnel(&self, bx: i32, by: i32, depth: i32) -> bool {
+        let config = &*CAVE_CONFIG;
+        let x = f64::from(bx);
+        let y = f64::from(by);
+
+        if let Some(w) = &config.worms
+            && depth >= w.min_depth
+        {
+            let density = self.settings.cave_density;
+            let width = f64::from(depth - w.min_depth)
+                .mul_add(w.width_per_depth, w.width)
+                .min(w.max_width)
+                * f64::from(density.factor());
+            let tunnel = self
+                .noise_main
+                .get([
+                    x * w.noise_scale,
+                    y * w.noise_scale * w.vertical_scale,
+                    WORM_Z,
+                ])
+                .abs();
+            if tunnel < width
+                && self
+                    .noise_ore
+                    .get([x * w.gate_noise_scale, y * w.gate_noise_scale, WORM_GATE_Z])
+                    >= w.gate_threshold + density.threshold_shift()
+            {
+                return true;
+            }
+        }
+
+        if let Some(c) = &config.caverns
+            && depth >= c.min_depth
+        {
+            let chamber = self.noise_main.get([
+                x * c.noise_scale,
+                y * c.noise_scale * c.vertical_scale,
+                CAVERN_Z,
+            ]);
+            if chamber > c.threshold + self.settings.cave_density.threshold_shift() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_karst_void(&self, karst: &KarstConfig, bx: i32, by: i32) -> bool {
+        self.noise_ore.get([
+            f64::from(bx) * karst.noise_scale,
+            f64::from(by) * karst.noise_scale * 1.5,
+            KARST_Z,
+        ]) > karst.threshold + self.settings.cave_density.threshold_shift()
+    }
+
+    /// Whether karst dissolves the block at `(bx, by)`, judged from the strata alone so the
+    /// answer is the same on both sides of a chunk border.
+    fn is_karst_cell(
+        &self,
+        karst: &KarstConfig,
+        layers: &[usize],
+        bx: i32,
+        by: i32,
+        depth: i32,
+    ) -> bool {
+        if depth < karst.min_depth || !self.is_karst_void(karst, bx, by) {
+            return false;
+        }
+        let strata = self.strata_at(bx, by, depth, &self.plates.sample(bx, by));
+        layers.contains(&strata.layer_index)
+            && karst.host_blocks.contains(&strata.block_type.to_id())
+    }
+
+    fn is_lava_tube(&self, tubes: &LavaTubeConfig, bx: i32, by: i32) -> bool {
+        self.noise_ore
+            .get([
+                f64::from(bx) * tubes.noise_scale,
+                f64::from(by) * tubes.noise_scale * tubes.vertical_scale,
+                LAVA_TUBE_Z,
+            ])
+            .abs()
+            < tubes.width
+    }
+}
+
+/// Carves caves into the host rock of a chunk (TGIP Phase 11): worm tunnels and
+/// caverns everywhere, karst with dripstone in soluble rock and lava tubes under volcanoes.
+pub fn carve_caves(generator: &WorldGenerator, chunk: &mut ChunkCells) {
+    let config = &*CAVE_CONFIG;
+    let karst_layers: Vec<usize> = config.karst.as_ref().map_or_else(Vec::new, |k| {
+        STRATA_CONFIG
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| k.layers.contains(&layer.key))
+            .map(|(i, _)| i)
+            .collect()
+    });
+
+    let (origin_bx, origin_by) = (chunk.origin_bx, chunk.origin_by);
+    for (x, column) in chunk.cells.iter_mut().enumerate() {
+        let bx = origin_bx + x.to_i32().unwrap_or(0);
+        let biome_key = &chunk.climate[x].biome().key;
+        let tubes = config
+            .lava_tubes
+            .as_ref()
+            .filter(|t| t.biomes.contains(biome_key));
+
+        for (y, cell) in column.iter_mut().enumerate() {
+            if !cell.is_host_rock() {
+                continue;
+            }
+            let by = origin_by + y.to_i32().unwrap_or(0);
+            let depth = cell.depth;
+
+            if let Some(t) = tubes
+                && (t.min_depth..=t.max_depth).contains(&depth)
+                && generator.is_lava_tube(t, bx, by)
+            {
+                // Lava pools along the floor of some stretches of the tube.
+                let on_floor = !generator.is_lava_tube(t, bx, by + 1);
+                let stretch = hash_coords(generator.seed ^ SALT_LAVA, bx.div_euclid(8), 0);
+                let lava_chance = t.lava_chance * generator.settings.liquid_abundance.factor();
+                cell.block_type = if on_floor && hash_to_unit(stretch) < lava_chance {
+                    BlockType::from_id(t.lava_block)
+                } else {
+                    BlockType::Air
+                };
+                continue;
+            }
+
+            if generator.is_tunnel(bx, by, depth) {
+                cell.block_type = BlockType::Air;
+                continue;
+            }
+
+            if let Some(k) = &config.karst
+                && depth >= k.min_depth
+                && cell.layer.is_some_and(|l| karst_layers.contains(&l))
+                && k.host_blocks.contains(&cell.block_type.to_id())
+                && generator.is_karst_void(k, bx, by)
+            {
+                // Stalactites hang from solid ceilings, stalagmites rise from solid floors.
+                let ceiling = !generator.is_karst_cell(k, &karst_layers, bx, by - 1, depth - 1)
+                    && !generator.is_tunnel(bx, by - 1, depth - 1);
+                let floor = !generator.is_karst_cell(k, &karst_layers, bx, by + 1, depth + 1)
+                    && !generator.is_tunnel(bx, by + 1, depth + 1);
+                let roll = hash_to_unit(hash_coords(generator.seed ^ SALT_DRIPSTONE, bx, by));
+                cell.block_type = if (ceiling || floor) && roll < k.dripstone_chance {
+                    BlockType::from_id(k.dripstone_block)
+                } else {
+                    BlockType::Air
+                };
+            }
+        }
+    }
+}
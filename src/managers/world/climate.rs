@@ -0,0 +1,260 @@
+use super::WorldManager;
+use super::generation::WorldGenerator;
+use crate::components::BlockType;
+use crate::constants::{
+    BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CLIMATE_NOISE_HUMIDITY, CLIMATE_NOISE_TEMPERATURE,
+    EQUATOR_TEMPERATURE, INLAND_HUMIDITY, LAPSE_RATE, LATITUDE_PERIOD_MACROGRIDS,
+    MACROGRID_SIZE_CHUNKS, MOUNTAIN_UPLIFT_BLOCKS, NOISE_SCALE_CLIMATE, OCEAN_HUMIDITY,
+    OROGRAPHIC_BONUS, PLAYER_INITIAL_X, POLE_TEMPERATURE, RAIN_SHADOW_REACH_MACROGRIDS,
+    RAIN_SHADOW_STRENGTH, SPAWN_LATITUDE, SURFACE_Y_LEVEL,
+};
+use crate::utils::{hash_coords, hash_to_unit};
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::LazyLock;
+
+const BIOMES_PATH: &str = "data/worldgen/biomes.json";
+const SALT_WIND: u32 = 0xC11A_0001;
+
+// Offsets along the unused noise axis so each field is independent.
+const TEMPERATURE_Z: f64 = 223.1;
+const HUMIDITY_Z: f64 = 239.9;
+
+pub static BIOMES: LazyLock<Vec<Biome>> = LazyLock::new(load_biomes);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BiomeOreWeight {
+    pub block: u32,
+    pub weight: f32,
+}
+
+/// A biome and the climate range it covers. The first biome whose range
+/// contains a column's climate wins, so the list ends with a catch-all.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Biome {
+    pub key: String,
+    #[serde(default)]
+    pub min_temperature: Option<f32>,
+    #[serde(default)]
+    pub max_temperature: Option<f32>,
+    #[serde(default)]
+    pub min_humidity: Option<f32>,
+    #[serde(default)]
+    pub max_humidity: Option<f32>,
+    /// Plate stress needed, for biomes tied to boundaries rather than climate.
+    #[serde(default)]
+    pub min_stress: Option<f32>,
+    pub surface_block: u32,
+    /// Fills the soil layer between the surface block and the bedrock.
+    pub subsoil_block: u32,
+    pub soil_depth_bonus: i32,
+    /// Added to the surface temperature the player feels.
+    pub base_temperature: f32,
+    #[serde(default)]
+    pub ore_weights: Vec<BiomeOreWeight>,
+}
+
+impl Biome {
+    fn matches(&self, temperature: f32, humidity: f32, stress: f32) -> bool {
+        self.min_temperature.is_none_or(|t| temperature >= t)
+            && self.max_temperature.is_none_or(|t| temperature <= t)
+            && self.min_humidity.is_none_or(|h| humidity >= h)
+            && self.max_humidity.is_none_or(|h| humidity <= h)
+            && self.min_stress.is_none_or(|s| stress >= s)
+    }
+
+    #[must_use]
+    pub fn surface(&self) -> BlockType {
+        BlockType::from_id(self.surface_block)
+    }
+
+    #[must_use]
+    pub fn subsoil(&self) -> BlockType {
+        BlockType::from_id(self.subsoil_block)
+    }
+
+    /// Multiplier on how often an ore is picked in this biome.
+    #[must_use]
+    pub fn ore_weight(&self, block: u32) -> f32 {
+        self.ore_weights
+            .iter()
+            .find(|o| o.block == block)
+            .map_or(1.0, |o| o.weight)
+    }
+}
+
+fn load_biomes() -> Vec<Biome> {
+    let biomes = fs::read_to_string(BIOMES_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<Biome>>(&content).ok())
+        .unwrap_or_default();
+    if biomes.is_empty() {
+        vec![fallback_biome()]
+    } else {
+        biomes
+    }
+}
+
+fn fallback_biome() -> Biome {
+    Biome {
+        key: "temperate".to_string(),
+        min_temperature: None,
+        max_temperature: None,
+        min_humidity: None,
+        max_humidity: None,
+        min_stress: None,
+        surface_block: BlockType::GRASS.to_id(),
+        subsoil_block: BlockType::DIRT.to_id(),
+        soil_depth_bonus: 0,
+        base_temperature: 0.0,
+        ore_weights: Vec::new(),
+    }
+}
+
+/// Sea-level climate of one macrogrid column.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClimateCell {
+    pub temperature: f32,
+    /// 0.0 (arid) to 1.0 (saturated).
+    pub humidity: f32,
+}
+
+/// Climate of one block column, after the lapse rate for its elevation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClimateSample {
+    pub temperature: f32,
+    pub humidity: f32,
+    /// Index into `BIOMES`.
+    pub biome_index: usize,
+}
+
+impl ClimateSample {
+    #[must_use]
+    pub fn biome(&self) -> &'static Biome {
+        &BIOMES[self.biome_index.min(BIOMES.len() - 1)]
+    }
+}
+
+fn macrogrid_size_blocks() -> f32 {
+    (MACROGRID_SIZE_CHUNKS * CHUNK_SIZE_X_BLOCKS)
+        .to_f32()
+        .unwrap_or(128.0)
+}
+
+fn macrogrid_centre_bx(mx: i32) -> i32 {
+    ((mx.to_f32().unwrap_or(0.0) + 0.5) * macrogrid_size_blocks())
+        .floor()
+        .to_i32()
+        .unwrap_or(0)
+}
+
+/// 0.0 at the equator and 1.0 at the poles, repeating along X.
+fn latitude(bx: f32) -> f32 {
+    let spawn_x = PLAYER_INITIAL_X / BLOCK_SIZE;
+    let phase = 2.0f32.mul_add(-SPAWN_LATITUDE, 1.0).acos();
+    let angle = (bx - spawn_x) / (LATITUDE_PERIOD_MACROGRIDS * macrogrid_size_blocks())
+        * std::f32::consts::TAU;
+    0.5f32.mul_add(-(angle + phase).cos(), 0.5)
+}
+
+impl WorldGenerator {
+    /// Prevailing wind along X for this world, `1` or `-1`.
+    fn wind_direction(&self) -> i32 {
+        if hash_to_unit(hash_coords(self.seed ^ SALT_WIND, 0, 0)) < 0.5 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Computes the climate of a macrogrid column (TGIP Phase 3).
+    #[must_use]
+    pub fn climate_cell(&self, mx: i32) -> ClimateCell {
+        let centre_bx = macrogrid_centre_bx(mx);
+        let x = f64::from(centre_bx);
+        let wind = self.wind_direction();
+
+        let temperature = latitude(centre_bx.to_f32().unwrap_or(0.0))
+            .mul_add(POLE_TEMPERATURE - EQUATOR_TEMPERATURE, EQUATOR_TEMPERATURE)
+            + self
+                .noise_main
+                .get([x * NOISE_SCALE_CLIMATE, 0.5, TEMPERATURE_Z])
+                .to_f32()
+                .unwrap_or(0.0)
+                * CLIMATE_NOISE_TEMPERATURE;
+
+        // Moisture comes from oceanic crust upwind and fades further inland.
+        let plate_at = |k: i32| {
+            self.plates
+                .sample(macrogrid_centre_bx(mx - wind * k), SURFACE_Y_LEVEL)
+        };
+        let here = plate_at(0);
+        let mut moisture = 0.0;
+        let mut total = 0.0;
+        let mut barrier = here.uplift;
+        let mut weight = 1.0;
+        for k in 0..=RAIN_SHADOW_REACH_MACROGRIDS {
+            let upwind = if k == 0 { here } else { plate_at(k) };
+            moisture += (1.0 - upwind.continentality) * weight;
+            total += weight;
+            weight *= 0.5;
+            barrier = barrier.max(upwind.uplift);
+        }
+        let mut humidity =
+            (moisture / total).mul_add(OCEAN_HUMIDITY - INLAND_HUMIDITY, INLAND_HUMIDITY);
+
+        // Mountains upwind wring the air dry; slopes rising into the wind get the rain.
+        let shadow = ((barrier - here.uplift) / MOUNTAIN_UPLIFT_BLOCKS).clamp(0.0, 1.0);
+        let rising = ((plate_at(-1).uplift - here.uplift) / MOUNTAIN_UPLIFT_BLOCKS).clamp(0.0, 1.0);
+        humidity += rising * OROGRAPHIC_BONUS - shadow * RAIN_SHADOW_STRENGTH;
+        humidity += self
+            .noise_main
+            .get([x * NOISE_SCALE_CLIMATE, 0.5, HUMIDITY_Z])
+            .to_f32()
+            .unwrap_or(0.0)
+            * CLIMATE_NOISE_HUMIDITY;
+
+        ClimateCell {
+            temperature,
+            humidity: humidity.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Climate and biome of block column `bx` whose ground is at `surface_y` (TGIP Phase 10).
+    #[must_use]
+    pub fn climate_at(&self, bx: i32, surface_y: i32) -> ClimateSample {
+        let size = macrogrid_size_blocks();
+        let fx = (bx.to_f32().unwrap_or(0.0) - size / 2.0) / size;
+        let x0 = fx.floor();
+        let t = fx - x0;
+        let mx = x0.to_i32().unwrap_or(0);
+        let left = self.climate_cell(mx);
+        let right = self.climate_cell(mx + 1);
+
+        let elevation = (SURFACE_Y_LEVEL - surface_y).to_f32().unwrap_or(0.0);
+        let temperature = (right.temperature - left.temperature).mul_add(t, left.temperature)
+            - elevation * LAPSE_RATE;
+        let humidity = (right.humidity - left.humidity).mul_add(t, left.humidity);
+        let stress = self.plates.sample(bx, SURFACE_Y_LEVEL).geological_stress;
+
+        let biome_index = BIOMES
+            .iter()
+            .position(|b| b.matches(temperature, humidity, stress))
+            .unwrap_or(BIOMES.len() - 1);
+        ClimateSample {
+            temperature,
+            humidity,
+            biome_index,
+        }
+    }
+}
+
+impl WorldManager {
+    /// Climate and biome of block column `bx`.
+    #[must_use]
+    pub fn climate_at(&self, bx: i32) -> ClimateSample {
+        self.generator.climate_at(bx, self.surface_height_at(bx))
+    }
+}
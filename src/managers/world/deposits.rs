@@ -0,0 +1,449 @@
+use super::climate::Biome;
+use super::generation::{ChunkCells, GenCell, WorldGenerator};
+use super::strata::{STRATA_CONFIG, fault_strength};
+use crate::components::BlockType;
+use crate::managers::resources::{NATURAL_RESOURCES, NaturalResource};
+use crate::utils::{hash_coords, hash_to_unit};
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::LazyLock;
+
+const DEPOSITS_PATH: &str = "data/worldgen/deposits.json";
+const SALT_VEIN: u32 = 0x0DE9_0001;
+const SALT_PLACER: u32 = 0x0DE9_0002;
+const SALT_POCKET: u32 = 0x0DE9_0003;
+const SALT_RELIC: u32 = 0x0DE9_0004;
+
+// Offsets along the unused noise axis so each pass samples its own field.
+const VEIN_GATE_Z: f64 = 101.1;
+const FRACTURE_Z: f64 = 117.9;
+const BODY_Z: f64 = 131.3;
+const ZONING_Z: f64 = 147.7;
+const CHANNEL_Z: f64 = 163.1;
+const LENS_Z: f64 = 179.9;
+const POCKET_Z: f64 = 193.3;
+const POCKET_WARP_Z: f64 = 209.7;
+
+/// A mineralization pass run over every chunk after the strata are laid down (TGIP Phase 8).
+pub trait DepositPass: Send + Sync {
+    fn apply(&self, generator: &WorldGenerator, chunk: &mut ChunkCells);
+}
+
+pub static DEPOSIT_PASSES: LazyLock<Vec<Box<dyn DepositPass>>> = LazyLock::new(load_passes);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DepositOre {
+    pub block: u32,
+    pub weight: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HydrothermalParams {
+    pub key: String,
+    /// Strata layer keys the veins may cut through.
+    pub layers: Vec<String>,
+    #[serde(default)]
+    pub min_depth: i32,
+    /// Half-width of a vein following a fault plane.
+    pub vein_width_blocks: f32,
+    /// Faults weaker than this carry no veins.
+    pub min_fault_strength: f32,
+    /// Winding fracture veins away from faults; a width of 0 disables them.
+    #[serde(default)]
+    pub fracture_noise_scale: f64,
+    #[serde(default)]
+    pub fracture_width: f64,
+    /// Noise gate along veins, higher values leave more gaps.
+    pub continuity: f64,
+    pub ores: Vec<DepositOre>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MagmaticParams {
+    pub key: String,
+    pub layers: Vec<String>,
+    #[serde(default)]
+    pub min_depth: i32,
+    pub noise_scale: f64,
+    pub threshold: f64,
+    /// Lowers the threshold along convergent boundaries, where magma rises.
+    pub stress_bonus: f64,
+    pub ores: Vec<DepositOre>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlacerParams {
+    pub key: String,
+    pub layers: Vec<String>,
+    /// Frequency of the buried paleo-channels along X.
+    pub channel_noise_scale: f64,
+    /// Channel half-width as a fraction of the noise range.
+    pub channel_width: f64,
+    pub lens_depth_blocks: f32,
+    pub lens_depth_variation: f32,
+    pub lens_thickness_blocks: f32,
+    pub ores: Vec<DepositOre>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DisseminatedParams {
+    pub key: String,
+    pub layers: Vec<String>,
+    pub noise_scale: f64,
+    pub threshold: f64,
+    /// Added to the threshold per point of rarity below the maximum, so rare minerals form smaller pockets.
+    pub rarity_threshold: f64,
+    /// Size of the cells that each hold a single mineral.
+    pub pocket_blocks: i32,
+    /// Block ids left to the other passes.
+    #[serde(default)]
+    pub exclude: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RelicParams {
+    pub key: String,
+    pub layers: Vec<String>,
+    /// Rocks that preserve the finds.
+    pub host_blocks: Vec<u32>,
+    pub min_depth: i32,
+    pub max_depth: i32,
+    /// Chance for each host block in the band to hold a find.
+    pub chance: f32,
+    pub ores: Vec<DepositOre>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DepositConfig {
+    Hydrothermal(HydrothermalParams),
+    Magmatic(MagmaticParams),
+    Placer(PlacerParams),
+    Disseminated(DisseminatedParams),
+    Relic(RelicParams),
+}
+
+impl DepositConfig {
+    fn into_pass(self) -> Box<dyn DepositPass> {
+        match self {
+            Self::Hydrothermal(params) => Box::new(HydrothermalPass {
+                layers: layer_indices(&params.layers),
+                params,
+            }),
+            Self::Magmatic(params) => Box::new(MagmaticPass {
+                layers: layer_indices(&params.layers),
+                params,
+            }),
+            Self::Placer(params) => Box::new(PlacerPass {
+                layers: layer_indices(&params.layers),
+                params,
+            }),
+            Self::Disseminated(params) => Box::new(DisseminatedPass {
+                layers: layer_indices(&params.layers),
+                resources: NATURAL_RESOURCES
+                    .iter()
+                    .filter(|r| !params.exclude.contains(&r.id))
+                    .cloned()
+                    .collect(),
+                params,
+            }),
+            Self::Relic(params) => Box::new(RelicPass {
+                layers: layer_indices(&params.layers),
+                params,
+            }),
+        }
+    }
+}
+
+fn load_passes() -> Vec<Box<dyn DepositPass>> {
+    fs::read_to_string(DEPOSITS_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<DepositConfig>>(&content).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(DepositConfig::into_pass)
+        .collect()
+}
+
+fn layer_indices(keys: &[String]) -> Vec<usize> {
+    STRATA_CONFIG
+        .layers
+        .iter()
+        .enumerate()
+        .filter(|(_, layer)| keys.contains(&layer.key))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Picks an ore by weight for `roll` in `[0, 1)`.
+pub(super) fn pick_ore(ores: &[DepositOre], roll: f32) -> Option<BlockType> {
+    let total: f32 = ores.iter().map(|o| o.weight).sum();
+    let mut roll = roll.clamp(0.0, 0.999) * total;
+    for ore in ores {
+        if roll < ore.weight {
+            return Some(BlockType::from_id(ore.block));
+        }
+        roll -= ore.weight;
+    }
+    None
+}
+
+fn accepts(layers: &[usize], cell: &GenCell, min_depth: i32) -> bool {
+    cell.is_host_rock()
+        && cell.depth >= min_depth
+        && cell.layer.is_some_and(|l| layers.contains(&l))
+}
+
+/// Iterates the cells of a chunk together with their world block coordinates.
+fn for_each_cell(chunk: &mut ChunkCells, mut f: impl FnMut(i32, i32, &mut GenCell)) {
+    let (origin_bx, origin_by) = (chunk.origin_bx, chunk.origin_by);
+    for (x, column) in chunk.cells.iter_mut().enumerate() {
+        let bx = origin_bx + x.to_i32().unwrap_or(0);
+        for (y, cell) in column.iter_mut().enumerate() {
+            f(bx, origin_by + y.to_i32().unwrap_or(0), cell);
+        }
+    }
+}
+
+/// Thin veins along fault planes and rock fractures (gold, silver, quartz).
+struct HydrothermalPass {
+    params: HydrothermalParams,
+    layers: Vec<usize>,
+}
+
+impl DepositPass for HydrothermalPass {
+    fn apply(&self, generator: &WorldGenerator, chunk: &mut ChunkCells) {
+        let p = &self.params;
+        for_each_cell(chunk, |bx, by, cell| {
+            if !accepts(&self.layers, cell, p.min_depth) {
+                return;
+            }
+            let x = f64::from(bx);
+            let y = f64::from(by);
+
+            let richness = generator.settings.ore_richness;
+            let on_fault = WorldGenerator::fault_distance(bx, by)
+                <= p.vein_width_blocks * richness.factor()
+                && fault_strength(&generator.plates.sample(bx, by)) >= p.min_fault_strength;
+            let fracture_width = p.fracture_width * f64::from(richness.factor());
+            let on_fracture = fracture_width > 0.0
+                && generator
+                    .noise_ore
+                    .get([
+                        x * p.fracture_noise_scale,
+                        y * p.fracture_noise_scale,
+                        FRACTURE_Z,
+                    ])
+                    .abs()
+                    < fracture_width;
+            if !on_fault && !on_fracture {
+                return;
+            }
+
+            let gate = generator.noise_ore.get([x * 0.08, y * 0.08, VEIN_GATE_Z]);
+            if gate < p.continuity - 0.5 {
+                return;
+            }
+
+            // The mineral changes in short segments along each vein.
+            let segment = hash_coords(
+                generator.seed ^ SALT_VEIN,
+                WorldGenerator::fault_block(bx, by),
+                by.div_euclid(6),
+            );
+            if let Some(ore) = pick_ore(&p.ores, hash_to_unit(segment)) {
+                cell.block_type = ore;
+            }
+        });
+    }
+}
+
+/// Massive ore bodies deep in igneous rock (iron, nickel).
+struct MagmaticPass {
+    params: MagmaticParams,
+    layers: Vec<usize>,
+}
+
+impl DepositPass for MagmaticPass {
+    fn apply(&self, generator: &WorldGenerator, chunk: &mut ChunkCells) {
+        let p = &self.params;
+        for_each_cell(chunk, |bx, by, cell| {
+            if !accepts(&self.layers, cell, p.min_depth) {
+                return;
+            }
+            let x = f64::from(bx);
+            let y = f64::from(by);
+
+            let body = generator
+                .noise_main
+                .get([x * p.noise_scale, y * p.noise_scale, BODY_Z])
+                + 0.5
+                    * generator.noise_main.get([
+                        x * p.noise_scale * 2.0,
+                        y * p.noise_scale * 2.0,
+                        BODY_Z,
+                    ]);
+            let stress = f64::from(generator.plates.sample(bx, by).geological_stress.max(0.0));
+            let threshold = p.threshold + generator.settings.ore_richness.threshold_shift();
+            if body < threshold - p.stress_bonus * stress {
+                return;
+            }
+
+            // A slow field zones each body into bands of different minerals.
+            let zoning = generator.noise_ore.get([
+                x * p.noise_scale * 0.5,
+                y * p.noise_scale * 0.5,
+                ZONING_Z,
+            ]);
+            if let Some(ore) = pick_ore(&p.ores, (zoning * 0.5 + 0.5).to_f32().unwrap_or(0.0)) {
+                cell.block_type = ore;
+            }
+        });
+    }
+}
+
+/// Flat lenses of heavy sand along buried river channels (gold sand, iron sand).
+struct PlacerPass {
+    params: PlacerParams,
+    layers: Vec<usize>,
+}
+
+impl DepositPass for PlacerPass {
+    fn apply(&self, generator: &WorldGenerator, chunk: &mut ChunkCells) {
+        let p = &self.params;
+        for_each_cell(chunk, |bx, by, cell| {
+            if !accepts(&self.layers, cell, 0) {
+                return;
+            }
+            let x = f64::from(bx);
+
+            let channel = generator
+                .noise_main
+                .get([x * p.channel_noise_scale, 0.5, CHANNEL_Z])
+                .abs();
+            let channel_width =
+                p.channel_width * f64::from(generator.settings.ore_richness.factor());
+            if channel >= channel_width {
+                return;
+            }
+            // Thickest at the channel axis, pinching out towards the banks.
+            let taper = (1.0 - channel / channel_width).to_f32().unwrap_or(0.0);
+            let centre = generator
+                .noise_main
+                .get([x * p.channel_noise_scale * 4.0, 0.5, LENS_Z])
+                .to_f32()
+                .unwrap_or(0.0)
+                .mul_add(p.lens_depth_variation, p.lens_depth_blocks);
+            let half_thickness = p.lens_thickness_blocks * taper / 2.0;
+            if (cell.depth.to_f32().unwrap_or(0.0) - centre).abs() > half_thickness {
+                return;
+            }
+
+            let grain = hash_coords(generator.seed ^ SALT_PLACER, bx, by);
+            if let Some(ore) = pick_ore(&p.ores, hash_to_unit(grain)) {
+                cell.block_type = ore;
+            }
+        });
+    }
+}
+
+/// Small pockets of every natural resource in the registry, each within its depth band.
+struct DisseminatedPass {
+    params: DisseminatedParams,
+    layers: Vec<usize>,
+    resources: Vec<NaturalResource>,
+}
+
+impl DisseminatedPass {
+    /// Picks a resource whose depth band contains `depth`, weighted by rarity and biome.
+    fn pick(&self, depth: i32, biome: &Biome, roll: f32) -> Option<&NaturalResource> {
+        let weight = |r: &NaturalResource| r.rarity.max(0.1) * biome.ore_weight(r.id);
+        let candidates = || self.resources.iter().filter(|r| r.spawns_at(depth));
+        let total: f32 = candidates().map(weight).sum();
+        let mut roll = roll.clamp(0.0, 0.999) * total;
+        for resource in candidates() {
+            let weight = weight(resource);
+            if roll < weight {
+                return Some(resource);
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+impl DepositPass for DisseminatedPass {
+    fn apply(&self, generator: &WorldGenerator, chunk: &mut ChunkCells) {
+        let p = &self.params;
+        let pocket = p.pocket_blocks.max(1);
+        let (origin_bx, climate) = (chunk.origin_bx, chunk.climate);
+        for_each_cell(chunk, |bx, by, cell| {
+            if !accepts(&self.layers, cell, 1) {
+                return;
+            }
+            let x = f64::from(bx);
+            let y = f64::from(by);
+
+            let presence =
+                generator
+                    .noise_ore
+                    .get([x * p.noise_scale, y * p.noise_scale, POCKET_Z]);
+            let threshold = p.threshold + generator.settings.ore_richness.threshold_shift();
+            if presence < threshold {
+                return;
+            }
+
+            // Warp the pocket grid so pockets do not end on straight lines.
+            let warp = generator
+                .noise_main
+                .get([x * 0.05, y * 0.05, POCKET_WARP_Z])
+                * f64::from(pocket);
+            let warp = warp.round().to_i32().unwrap_or(0);
+            let roll = hash_to_unit(hash_coords(
+                generator.seed ^ SALT_POCKET,
+                (bx + warp).div_euclid(pocket),
+                (by - warp).div_euclid(pocket),
+            ));
+            let column = (bx - origin_bx).to_usize().unwrap_or(0);
+            let Some(resource) = self.pick(cell.depth, climate[column].biome(), roll) else {
+                return;
+            };
+            let scarcity = f64::from(10.0 - resource.rarity).max(0.0);
+            if presence >= scarcity.mul_add(p.rarity_threshold, threshold) {
+                cell.block_type = BlockType::from_id(resource.id);
+            }
+        });
+    }
+}
+
+/// Lone fossils and buried artifacts in a depth band of particular host rocks (TGIP Phase 11).
+struct RelicPass {
+    params: RelicParams,
+    layers: Vec<usize>,
+}
+
+impl DepositPass for RelicPass {
+    fn apply(&self, generator: &WorldGenerator, chunk: &mut ChunkCells) {
+        let p = &self.params;
+        for_each_cell(chunk, |bx, by, cell| {
+            if !accepts(&self.layers, cell, p.min_depth)
+                || cell.depth > p.max_depth
+                || !p.host_blocks.contains(&cell.block_type.to_id())
+            {
+                return;
+            }
+            let chance = p.chance * generator.settings.ore_richness.factor();
+            let roll = hash_to_unit(hash_coords(generator.seed ^ SALT_RELIC, bx, by));
+            if roll >= chance {
+                return;
+            }
+            // The roll is uniform below the chance, so it also picks which find this is.
+            let roll = roll / chance;
+            if let Some(find) = pick_ore(&p.ores, roll) {
+                cell.block_type = find;
+            }
+        });
+    }
+}
@@ -0,0 +1,336 @@
+use super::WorldManager;
+use super::generation::WorldGenerator;
+use super::terrain::spawn_flatten_factor;
+use crate::constants::{
+    BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, EROSION_PREPARE_RADIUS_MACROGRIDS, MACROGRID_SIZE_CHUNKS,
+    PLAYER_INITIAL_X,
+};
+use crate::utils::{hash_coords, hash_to_unit};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, LazyLock};
+
+const EROSION_PATH: &str = "data/worldgen/erosion.json";
+const SALT_DROPLET: u32 = 0xE205_0001;
+
+pub static EROSION_CONFIG: LazyLock<ErosionConfig> = LazyLock::new(ErosionConfig::load);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ErosionConfig {
+    /// Horizontal spacing of the simulated heightmap; results are interpolated between samples.
+    pub sample_spacing_blocks: i32,
+    /// Macrogrids simulated on each side so water running in from neighbours is included.
+    pub margin_macrogrids: i32,
+    /// Samples on each side of a macrogrid edge where neighbouring columns are blended,
+    /// so their separate simulations meet without a step.
+    pub blend_samples: i32,
+    pub droplets_per_sample: u32,
+    pub max_steps: u32,
+    /// Sediment a droplet can carry per block of drop, speed and water.
+    pub capacity: f32,
+    pub erode_rate: f32,
+    pub deposit_rate: f32,
+    pub evaporation: f32,
+    pub gravity: f32,
+    /// Slope assumed on flats so droplets keep a little carrying capacity.
+    pub min_slope: f32,
+    pub max_exhumation_blocks: f32,
+    pub max_sediment_blocks: f32,
+    /// Exhumation that strips the soil completely and leaves bare rock.
+    pub soil_strip_blocks: f32,
+}
+
+impl ErosionConfig {
+    fn load() -> Self {
+        fs::read_to_string(EROSION_PATH)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(Self::fallback)
+    }
+
+    /// No erosion at all, used when the data file is missing or invalid.
+    const fn fallback() -> Self {
+        Self {
+            sample_spacing_blocks: 4,
+            margin_macrogrids: 0,
+            blend_samples: 0,
+            droplets_per_sample: 0,
+            max_steps: 0,
+            capacity: 0.0,
+            erode_rate: 0.0,
+            deposit_rate: 0.0,
+            evaporation: 1.0,
+            gravity: 0.0,
+            min_slope: 0.0,
+            max_exhumation_blocks: 0.0,
+            max_sediment_blocks: 0.0,
+            soil_strip_blocks: 1.0,
+        }
+    }
+
+    fn samples_per_macrogrid(&self) -> i32 {
+        (MACROGRID_SIZE_CHUNKS * CHUNK_SIZE_X_BLOCKS)
+            .to_i32()
+            .unwrap_or(128)
+            / self.sample_spacing_blocks.max(1)
+    }
+
+    /// `blend_samples`, limited to what the simulated margin covers.
+    fn blend_samples(&self) -> i32 {
+        self.blend_samples.clamp(
+            0,
+            self.margin_macrogrids.max(0) * self.samples_per_macrogrid(),
+        )
+    }
+}
+
+/// Erosion result for one column.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ErosionSample {
+    /// Blocks of rock and soil washed away, lowering the surface.
+    pub exhumation: f32,
+    /// Blocks of sediment laid down, raising the surface.
+    pub sediment: f32,
+}
+
+impl ErosionSample {
+    /// Fraction of the original soil cover left, 0.0 where the rock is bare.
+    #[must_use]
+    pub fn soil_remaining(&self) -> f32 {
+        (1.0 - self.exhumation / EROSION_CONFIG.soil_strip_blocks.max(f32::EPSILON)).clamp(0.0, 1.0)
+    }
+
+    /// Net change of the surface Y; positive where it was worn down.
+    #[must_use]
+    pub fn surface_offset(&self) -> i32 {
+        (self.exhumation - self.sediment)
+            .round()
+            .to_i32()
+            .unwrap_or(0)
+    }
+}
+
+impl WorldGenerator {
+    /// Runs the hydraulic erosion of macrogrid column `mx` (TGIP Phase 4). Droplets fall on a
+    /// coarse copy of the heightmap, pick up sediment on slopes and drop it on flats and in pits.
+    /// The result reaches `blend_samples` into each neighbouring column.
+    fn simulate_erosion(&self, mx: i32) -> Arc<[ErosionSample]> {
+        let config = &*EROSION_CONFIG;
+        let per_macrogrid = config.samples_per_macrogrid();
+        let first = (mx - config.margin_macrogrids) * per_macrogrid;
+        let len = (2 * config.margin_macrogrids + 1) * per_macrogrid;
+        let spacing = config.sample_spacing_blocks.max(1);
+
+        // Elevation grows upwards here, unlike block Y.
+        let initial: Vec<f32> = (first..first + len)
+            .map(|i| {
+                -self
+                    .uneroded_surface_height_at(i * spacing)
+                    .to_f32()
+                    .unwrap_or(0.0)
+            })
+            .collect();
+        let mut elevation = initial.clone();
+        let last = elevation.len().saturating_sub(1);
+        let last_f = last.to_f32().unwrap_or(0.0);
+
+        let height_at = |elevation: &[f32], p: f32| {
+            let i = p
+                .floor()
+                .to_usize()
+                .unwrap_or(0)
+                .min(last.saturating_sub(1));
+            let t = p - i.to_f32().unwrap_or(0.0);
+            (elevation[i + 1] - elevation[i]).mul_add(t, elevation[i])
+        };
+        // Spreads a height change over the two samples either side of `p`.
+        let apply = |elevation: &mut [f32], p: f32, amount: f32| {
+            let i = p
+                .floor()
+                .to_usize()
+                .unwrap_or(0)
+                .min(last.saturating_sub(1));
+            let t = p - i.to_f32().unwrap_or(0.0);
+            elevation[i] += amount * (1.0 - t);
+            elevation[i + 1] += amount * t;
+        };
+
+        let droplets = config.droplets_per_sample * len.to_u32().unwrap_or(0);
+        for d in 0..droplets {
+            let start = hash_to_unit(hash_coords(
+                self.seed ^ SALT_DROPLET,
+                mx,
+                d.to_i32().unwrap_or(0),
+            ));
+            let mut p = start * last_f;
+            let mut water = 1.0;
+            let mut speed = 0.0f32;
+            let mut sediment = 0.0f32;
+
+            for _ in 0..config.max_steps {
+                let here = height_at(&elevation, p);
+                let left = height_at(&elevation, (p - 1.0).max(0.0));
+                let right = height_at(&elevation, (p + 1.0).min(last_f));
+                let next = if left < right { p - 1.0 } else { p + 1.0 };
+                if next < 0.0 || next > last_f {
+                    break;
+                }
+                let drop = here - height_at(&elevation, next);
+
+                if drop <= 0.0 {
+                    // A pit: fill it with what the droplet carries and stop.
+                    apply(&mut elevation, p, sediment.min(-drop + 0.1));
+                    break;
+                }
+
+                let capacity =
+                    drop.max(config.min_slope) * speed.max(1.0) * water * config.capacity;
+                if sediment > capacity {
+                    let deposit = (sediment - capacity) * config.deposit_rate;
+                    apply(&mut elevation, p, deposit);
+                    sediment -= deposit;
+                } else {
+                    let erode = ((capacity - sediment) * config.erode_rate).min(drop);
+                    apply(&mut elevation, p, -erode);
+                    sediment += erode;
+                }
+
+                speed = drop.mul_add(config.gravity, speed * speed).sqrt();
+                water *= 1.0 - config.evaporation;
+                p = next;
+            }
+        }
+
+        let blend = config.blend_samples();
+        let skip = (config.margin_macrogrids * per_macrogrid - blend)
+            .to_usize()
+            .unwrap_or(0);
+        initial
+            .iter()
+            .zip(&elevation)
+            .skip(skip)
+            .take((per_macrogrid + 2 * blend).to_usize().unwrap_or(0))
+            .map(|(before, after)| ErosionSample {
+                exhumation: (before - after).clamp(0.0, config.max_exhumation_blocks),
+                sediment: (after - before).clamp(0.0, config.max_sediment_blocks),
+            })
+            .collect()
+    }
+
+    /// Sample `local` of column `mx`'s own simulation, where `local` may reach
+    /// `blend_samples` past either edge.
+    fn column_sample(&self, mx: i32, local: i32) -> ErosionSample {
+        self.erosion
+            .get_or_insert_with(mx, || self.simulate_erosion(mx))
+            .get(
+                (local + EROSION_CONFIG.blend_samples())
+                    .to_usize()
+                    .unwrap_or(0),
+            )
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn erosion_sample(&self, index: i32) -> ErosionSample {
+        let config = &*EROSION_CONFIG;
+        let per_macrogrid = config.samples_per_macrogrid().max(1);
+        let blend = config.blend_samples();
+        let mx = index.div_euclid(per_macrogrid);
+        let local = index.rem_euclid(per_macrogrid);
+        let own = self.column_sample(mx, local);
+
+        // Near an edge, fade into the neighbour's run over the same cells: half and half
+        // at the edge, all our own `blend` samples in.
+        let (other, distance) = if local < blend {
+            (self.column_sample(mx - 1, local + per_macrogrid), local)
+        } else if local >= per_macrogrid - blend {
+            (
+                self.column_sample(mx + 1, local - per_macrogrid),
+                per_macrogrid - 1 - local,
+            )
+        } else {
+            return own;
+        };
+        let weight =
+            0.5 + 0.5 * (distance.to_f32().unwrap_or(0.0) + 0.5) / blend.to_f32().unwrap_or(1.0);
+        ErosionSample {
+            exhumation: (own.exhumation - other.exhumation).mul_add(weight, other.exhumation),
+            sediment: (own.sediment - other.sediment).mul_add(weight, other.sediment),
+        }
+    }
+
+    /// Erosion at block column `bx`, interpolated from the cached macrogrid samples.
+    #[must_use]
+    pub fn erosion_at(&self, bx: i32) -> ErosionSample {
+        let spacing = EROSION_CONFIG.sample_spacing_blocks.max(1);
+        let index = bx.div_euclid(spacing);
+        let t = bx.rem_euclid(spacing).to_f32().unwrap_or(0.0) / spacing.to_f32().unwrap_or(1.0);
+        let a = self.erosion_sample(index);
+        let b = self.erosion_sample(index + 1);
+        // The spawn area keeps its flat ground and soil.
+        let flatten = spawn_flatten_factor(bx);
+        ErosionSample {
+            exhumation: (b.exhumation - a.exhumation).mul_add(t, a.exhumation) * flatten,
+            sediment: (b.sediment - a.sediment).mul_add(t, a.sediment) * flatten,
+        }
+    }
+
+    /// Macrogrid columns around the home gate worth eroding before anyone asks for them.
+    #[must_use]
+    pub fn spawn_erosion_columns() -> RangeInclusive<i32> {
+        let spawn_bx = (PLAYER_INITIAL_X / BLOCK_SIZE)
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+        let macrogrid_blocks = (MACROGRID_SIZE_CHUNKS * CHUNK_SIZE_X_BLOCKS)
+            .to_i32()
+            .unwrap_or(128);
+        let mx = spawn_bx.div_euclid(macrogrid_blocks);
+        mx - EROSION_PREPARE_RADIUS_MACROGRIDS..=mx + EROSION_PREPARE_RADIUS_MACROGRIDS
+    }
+
+    /// Simulates macrogrid column `mx` now unless it already has been.
+    pub fn prepare_erosion(&self, mx: i32) {
+        self.erosion
+            .get_or_insert_with(mx, || self.simulate_erosion(mx));
+    }
+
+    /// Macrogrid columns whose simulations `erosion_at(bx)` reads, including the
+    /// neighbours blended in near a column edge.
+    #[must_use]
+    pub fn erosion_columns(bx: i32) -> RangeInclusive<i32> {
+        let config = &*EROSION_CONFIG;
+        let per_macrogrid = config.samples_per_macrogrid().max(1);
+        let blend = config.blend_samples();
+        let index = bx.div_euclid(config.sample_spacing_blocks.max(1));
+        (index - blend).div_euclid(per_macrogrid)..=(index + 1 + blend).div_euclid(per_macrogrid)
+    }
+
+    /// Whether `erosion_at(bx)` can answer without running a simulation.
+    #[must_use]
+    pub fn is_eroded(&self, bx: i32) -> bool {
+        Self::erosion_columns(bx).all(|mx| self.erosion.contains(mx))
+    }
+}
+
+impl WorldManager {
+    /// Erosion at block column `bx`.
+    #[must_use]
+    pub fn erosion_at(&self, bx: i32) -> ErosionSample {
+        self.generator.erosion_at(bx)
+    }
+
+    /// Queues erosion on the generation workers for every column that block columns
+    /// `first_bx..=last_bx` read and that has not been simulated yet.
+    pub fn request_erosion(&self, first_bx: i32, last_bx: i32) {
+        let first = *WorldGenerator::erosion_columns(first_bx).start();
+        let last = *WorldGenerator::erosion_columns(last_bx).end();
+        for mx in first..=last {
+            if !self.generator.erosion.contains(mx) {
+                self.workers.request_erosion(mx);
+            }
+        }
+    }
+}
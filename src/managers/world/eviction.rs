@@ -0,0 +1,100 @@
+use super::WorldManager;
+use super::modifications;
+use crate::components::{BlockPos, CHUNK_AREA};
+use crate::constants::{
+    CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, EVICTION_DISTANCE_WEIGHT, EVICTION_TARGET_FRACTION,
+};
+use crate::utils::chunk_to_macrogrid_coords;
+use num_traits::ToPrimitive;
+use std::collections::HashSet;
+use std::mem::size_of;
+
+/// Approximate bytes of block data held by one loaded chunk: two palette indices, a
+/// liquid level and hit points per cell.
+#[must_use]
+pub const fn chunk_bytes() -> usize {
+    CHUNK_AREA * (2 * size_of::<u16>() + size_of::<u8>() + size_of::<i32>())
+}
+
+impl WorldManager {
+    /// Approximate bytes of block data currently loaded.
+    #[must_use]
+    pub fn loaded_chunk_bytes(&self) -> usize {
+        self.generated_chunk_coords.len() * chunk_bytes()
+    }
+
+    /// Unloads chunks once loaded blocks exceed `chunk_memory_cap`, going by how long each
+    /// has gone unused and how far it lies from `center`. Chunks used since the current
+    /// frame began stay. Untouched chunks are simply dropped and regenerated from the
+    /// seed later; changed ones and ones with moving liquid are kept in
+    /// `pending_modifications` until they load again.
+    pub fn evict_chunks(&mut self, center: BlockPos) {
+        if self.loaded_chunk_bytes() <= self.chunk_memory_cap {
+            return;
+        }
+        let target = (self.chunk_memory_cap.to_f32().unwrap_or(0.0) * EVICTION_TARGET_FRACTION)
+            .to_usize()
+            .unwrap_or(0);
+        let excess = self.loaded_chunk_bytes().saturating_sub(target);
+        let count = excess.div_ceil(chunk_bytes());
+
+        let mut candidates: Vec<(f32, BlockPos)> = self
+            .generated_chunk_coords
+            .iter()
+            .filter_map(|&pos| {
+                let last_access = self.chunk_access.get(&pos).copied().unwrap_or(0);
+                if last_access >= self.access_clock {
+                    return None;
+                }
+                let idle = (self.access_clock - last_access).to_f32().unwrap_or(0.0);
+                let distance = (pos.x - center.x).abs().max((pos.y - center.y).abs());
+                let score = distance
+                    .to_f32()
+                    .unwrap_or(0.0)
+                    .mul_add(EVICTION_DISTANCE_WEIGHT, idle);
+                Some((score, pos))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let chunk_of = |pos: &BlockPos| {
+            BlockPos::new(
+                pos.x.div_euclid(CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(1)),
+                pos.y.div_euclid(CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(1)),
+            )
+        };
+        let flowing: HashSet<BlockPos> = self.active_liquids.iter().map(chunk_of).collect();
+
+        for (_, pos) in candidates.into_iter().take(count) {
+            self.unload_chunk(pos, flowing.contains(&pos));
+        }
+        self.active_liquids
+            .retain(|p| self.generated_chunk_coords.contains(&chunk_of(p)));
+        self.pumps
+            .retain(|p| self.generated_chunk_coords.contains(&chunk_of(p)));
+        self.aquifer_cells
+            .retain(|p, _| self.generated_chunk_coords.contains(&chunk_of(p)));
+    }
+
+    fn unload_chunk(&mut self, pos: BlockPos, has_flowing_liquid: bool) {
+        let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(pos.x, pos.y);
+        let Some(macrogrid) = self.macrogrids.get_mut(&mg_coords) else {
+            return;
+        };
+        if let Some(chunk) = macrogrid.chunks.remove(&rel_coords)
+            && chunk.is_generated
+            && (chunk.is_modified_in_session || has_flowing_liquid)
+        {
+            self.pending_modifications.insert(
+                pos,
+                modifications::encode_chunk_save_data(pos.x, pos.y, &chunk),
+            );
+        }
+        if macrogrid.chunks.is_empty() {
+            self.macrogrids.remove(&mg_coords);
+        }
+        self.generated_chunk_coords.remove(&pos);
+        self.chunk_access.remove(&pos);
+        self.invalidate_temperatures();
+    }
+}
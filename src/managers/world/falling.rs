@@ -0,0 +1,63 @@
+use super::WorldManager;
+use crate::components::BlockType;
+use crate::constants::{BLOCK_SIZE, MAX_FALL_BLOCKS};
+use crate::utils::world_to_chunk_coords;
+use num_traits::ToPrimitive;
+
+impl WorldManager {
+    /// Drops the column of falling blocks resting on `(bx, by)` after that cell opened up,
+    /// as far as the empty space below reaches. Returns every cell that changed.
+    pub fn drop_falling_blocks(&mut self, bx: i32, by: i32) -> Vec<(i32, i32)> {
+        let is_empty = |manager: &Self, y: i32| {
+            manager
+                .get_block(bx, y)
+                .is_some_and(|b| b.block_type == BlockType::Air)
+        };
+        if !is_empty(self, by) {
+            return Vec::new();
+        }
+
+        let mut landing = by;
+        while landing - by < MAX_FALL_BLOCKS && is_empty(self, landing + 1) {
+            landing += 1;
+        }
+
+        let mut changed = Vec::new();
+        let mut y = by - 1;
+        while self
+            .get_block(bx, y)
+            .is_some_and(|b| !b.is_broken() && b.block_type.falls())
+        {
+            self.move_block(bx, y, landing);
+            changed.push((bx, y));
+            changed.push((bx, landing));
+            landing -= 1;
+            y -= 1;
+        }
+        changed
+    }
+
+    /// Moves the block at `(bx, from_y)` into the empty cell `(bx, to_y)`, leaving air.
+    fn move_block(&mut self, bx: i32, from_y: i32, to_y: i32) {
+        let (Some(mut from), Some(mut to)) = (self.get_block(bx, from_y), self.get_block(bx, to_y))
+        else {
+            return;
+        };
+        to.block_type = from.block_type;
+        to.max_hp = from.max_hp;
+        to.current_hp = from.current_hp;
+        to.liquid_level = 0;
+        from.block_type = BlockType::Air;
+        from.current_hp = 0;
+        self.set_block(&from);
+        self.set_block(&to);
+
+        for y in [from_y, to_y] {
+            let chunk = world_to_chunk_coords(
+                bx.to_f32().unwrap_or(0.0) * BLOCK_SIZE,
+                y.to_f32().unwrap_or(0.0) * BLOCK_SIZE,
+            );
+            self.mark_chunk_modified(chunk.x, chunk.y);
+        }
+    }
+}
@@ -1,116 +1,283 @@
-use crate::components::{Block, BlockType};
+use super::aquifers::fill_aquifers;
+use super::biome_strata::apply_biome_strata;
+use super::caves::carve_caves;
+use super::climate::ClimateSample;
+use super::deposits::DEPOSIT_PASSES;
+use super::erosion::ErosionSample;
+use super::hydrology::{WaterBody, place_water_bodies};
+use super::plates::PlateLayer;
+use super::settings::WorldSettings;
+use crate::components::{BlockType, Chunk};
 use crate::constants::{
     BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, HARDNESS_DEPTH_MULTIPLIER,
-    NOISE_SCALE_ORE, ORE_THRESHOLD, PLAYER_INITIAL_X, PLAYER_INITIAL_Y, SURFACE_Y_LEVEL,
+    PLAYER_INITIAL_X, PLAYER_INITIAL_Y,
 };
 use macroquad::prelude::*;
-use noise::{NoiseFn, Perlin};
+use noise::{NoiseFn, Perlin, Seedable};
 use num_traits::ToPrimitive;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+
+/// Everything chunk generation derives from the world seeds.
+pub struct WorldGenerator {
+    pub seed: u32,
+    pub noise_main: Perlin,
+    pub noise_ore: Perlin,
+    pub plates: PlateLayer,
+    pub erosion: MacrogridCache<ErosionSample>,
+    pub hydrology: MacrogridCache<WaterBody>,
+    pub settings: WorldSettings,
+}
+
+impl WorldGenerator {
+    #[must_use]
+    pub fn new(seed_main: u32, seed_ore: u32) -> Self {
+        Self::with_settings(seed_main, seed_ore, WorldSettings::default())
+    }
+
+    #[must_use]
+    pub fn with_settings(seed_main: u32, seed_ore: u32, settings: WorldSettings) -> Self {
+        Self {
+            seed: seed_main,
+            noise_main: Perlin::new(seed_main).set_seed(seed_main),
+            noise_ore: Perlin::new(seed_ore).set_seed(seed_ore),
+            plates: PlateLayer::new(seed_main),
+            erosion: MacrogridCache::default(),
+            hydrology: MacrogridCache::default(),
+            settings,
+        }
+    }
+}
+
+/// Results of a world-scale simulation for each macrogrid column (or, keyed by
+/// `BlockPos`, each macrogrid cell), computed the first time it is needed and kept for
+/// the life of the world.
+#[derive(Debug)]
+pub struct MacrogridCache<T, K = i32> {
+    macrogrids: RwLock<HashMap<K, Arc<[T]>>>,
+}
+
+impl<T, K> Default for MacrogridCache<T, K> {
+    fn default() -> Self {
+        Self {
+            macrogrids: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T, K: Copy + Eq + Hash> MacrogridCache<T, K> {
+    #[must_use]
+    pub fn contains(&self, key: K) -> bool {
+        self.macrogrids
+            .read()
+            .is_ok_and(|macrogrids| macrogrids.contains_key(&key))
+    }
+
+    pub fn get_or_insert_with(&self, key: K, simulate: impl FnOnce() -> Arc<[T]>) -> Arc<[T]> {
+        if let Some(cached) = self
+            .macrogrids
+            .read()
+            .ok()
+            .and_then(|macrogrids| macrogrids.get(&key).cloned())
+        {
+            return cached;
+        }
+        let result = simulate();
+        match self.macrogrids.write() {
+            Ok(mut macrogrids) => macrogrids.entry(key).or_insert(result).clone(),
+            Err(_) => result,
+        }
+    }
+}
+
+/// A generated cell before it is stored in the `Chunk`, so later passes can rewrite it.
+#[derive(Clone, Copy, Debug)]
+pub struct GenCell {
+    pub block_type: BlockType,
+    /// Wall drawn behind the cell, kept as the host rock when a later pass replaces it.
+    pub back_type: BlockType,
+    /// Factor applied to the base hardness of whatever block ends up in this cell.
+    pub hardness_multiplier: f64,
+    /// Strata layer index, or `None` for air, soil and fixed blocks.
+    pub layer: Option<usize>,
+    /// Blocks below the surface as it stood before erosion (negative above ground),
+    /// so rock and ore bands rise to meet a worn-down surface.
+    pub depth: i32,
+}
+
+impl GenCell {
+    /// Whether a deposit may replace this cell.
+    #[must_use]
+    pub fn is_host_rock(&self) -> bool {
+        self.layer.is_some() && self.block_type.is_solid()
+    }
+}
+
+/// Working copy of a chunk during generation, indexed `[x][y]` like `Chunk::blocks`.
+pub struct ChunkCells {
+    pub origin_bx: i32,
+    pub origin_by: i32,
+    pub ground: [i32; CHUNK_SIZE_X_BLOCKS],
+    pub climate: [ClimateSample; CHUNK_SIZE_X_BLOCKS],
+    pub cells: Vec<Vec<GenCell>>,
+}
 
 #[must_use]
-pub fn generate_chunk_blocks(chunk_x: i32, chunk_y: i32, noise_ore: &Perlin) -> Vec<Vec<Block>> {
-    let (origin_x, origin_y) = chunk_coords_to_world_origin(chunk_x, chunk_y);
-    let mut blocks = Vec::new();
+pub fn generate_chunk(chunk_x: i32, chunk_y: i32, generator: &WorldGenerator) -> Chunk {
+    let mut chunk = generate_base_cells(chunk_x, chunk_y, generator);
+    apply_biome_strata(generator, &mut chunk);
+    for pass in DEPOSIT_PASSES.iter() {
+        pass.apply(generator, &mut chunk);
+    }
+    carve_caves(generator, &mut chunk);
+    fill_aquifers(generator, &mut chunk);
+    place_water_bodies(generator, &mut chunk);
+    build_chunk(chunk_x, chunk_y, &chunk)
+}
+
+fn generate_base_cells(chunk_x: i32, chunk_y: i32, generator: &WorldGenerator) -> ChunkCells {
+    let origin_bx = chunk_x * CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0);
+    let origin_by = chunk_y * CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(0);
+    let mut ground = [0; CHUNK_SIZE_X_BLOCKS];
+    let mut climate = [ClimateSample::default(); CHUNK_SIZE_X_BLOCKS];
+    let mut cells = Vec::with_capacity(CHUNK_SIZE_X_BLOCKS);
+
+    // Initial Spawn Point - Warp Gate
+    let player_start_x_block = (PLAYER_INITIAL_X / BLOCK_SIZE)
+        .floor()
+        .to_i32()
+        .unwrap_or(0);
+    let player_start_y_block = (PLAYER_INITIAL_Y / BLOCK_SIZE)
+        .floor()
+        .to_i32()
+        .unwrap_or(0);
+
+    for (bx, (column_ground, column_climate)) in
+        ground.iter_mut().zip(climate.iter_mut()).enumerate()
+    {
+        let x_block = origin_bx + bx.to_i32().unwrap_or(0);
+        let ground_y = generator.surface_height_at(x_block);
+        *column_ground = ground_y;
+        *column_climate = generator.climate_at(x_block, ground_y);
+        let biome = column_climate.biome();
+        let erosion = generator.erosion_at(x_block);
+        let exhumed = erosion.exhumation.round().to_i32().unwrap_or(0);
+
+        // Colliding plates squeeze the soil, so bedrock comes up closer to the surface
+        // along mountain ranges. Erosion strips it from slopes and piles sediment in valleys.
+        let stress = generator.plates.sample(x_block, ground_y).geological_stress;
+        let soil_depth = ((6.0 - stress.max(0.0) * 4.0)
+            + biome.soil_depth_bonus.to_f32().unwrap_or(0.0))
+        .mul_add(erosion.soil_remaining(), erosion.sediment)
+        .round()
+        .to_i32()
+        .unwrap_or(6);
+        let mut column = Vec::with_capacity(CHUNK_SIZE_Y_BLOCKS);
 
-    for bx in 0..CHUNK_SIZE_X_BLOCKS {
-        let mut row = Vec::new();
         for by in 0..CHUNK_SIZE_Y_BLOCKS {
-            let wx = origin_x + bx.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
-            let wy = origin_y + by.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
-            let y_block = (wy / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
-            let x_block = (wx / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
-
-            // Initial Spawn Point - Warp Gate
-            let player_start_x_block = (PLAYER_INITIAL_X / BLOCK_SIZE)
-                .floor()
-                .to_i32()
-                .unwrap_or(0);
-            let player_start_y_block = (PLAYER_INITIAL_Y / BLOCK_SIZE)
-                .floor()
-                .to_i32()
-                .unwrap_or(0);
-
-            let (max_hp, sprite_rect, block_type) =
-                if x_block == player_start_x_block && y_block == player_start_y_block {
-                    let bt = BlockType::WarpGate;
-                    (50, bt.get_sprite(), bt)
-                } else if x_block == (PLAYER_INITIAL_X / BLOCK_SIZE).to_i32().unwrap_or(0)
-                    && y_block
-                        == ((PLAYER_INITIAL_Y + BLOCK_SIZE) / BLOCK_SIZE)
-                            .to_i32()
-                            .unwrap_or(0)
-                {
-                    let bt = BlockType::Indestructible;
-                    (bt.get_base_hardness(), bt.get_sprite(), bt)
-                } else if y_block < SURFACE_Y_LEVEL {
-                    (0, None, BlockType::Air)
-                } else if y_block == SURFACE_Y_LEVEL {
-                    let bt = BlockType::Grass;
-                    (bt.get_base_hardness(), bt.get_sprite(), bt)
-                } else {
-                    let mut b_type = BlockType::Dirt;
-
-                    if y_block > SURFACE_Y_LEVEL + 5 {
-                        let ore_val = noise_ore.get([
-                            wx.to_f64().unwrap_or(0.0) * NOISE_SCALE_ORE,
-                            wy.to_f64().unwrap_or(0.0) * NOISE_SCALE_ORE,
-                            256.0,
-                        ]);
-                        if ore_val >= ORE_THRESHOLD {
-                            b_type = BlockType::Coal;
-                        } else if y_block > SURFACE_Y_LEVEL + 10 {
-                            let water_val = noise_ore.get([
-                                wx.to_f64().unwrap_or(0.0) * 0.1,
-                                wy.to_f64().unwrap_or(0.0) * 0.1,
-                                512.0,
-                            ]);
-                            if water_val > 0.65 {
-                                b_type = BlockType::Water;
-                            } else {
-                                b_type = BlockType::Stone;
-                            }
-                        }
-                    }
+            let y_block = origin_by + by.to_i32().unwrap_or(0);
+            let depth = y_block - ground_y;
+            let rock_depth = depth + exhumed;
+            let fixed = |block_type| GenCell {
+                block_type,
+                back_type: block_type,
+                hardness_multiplier: 1.0,
+                layer: None,
+                depth: rock_depth,
+            };
 
-                    if y_block > 1000 {
-                        b_type = BlockType::Indestructible;
+            let cell = if x_block == player_start_x_block && y_block == player_start_y_block {
+                fixed(BlockType::WarpGate)
+            } else if x_block == player_start_x_block && y_block == player_start_y_block + 1 {
+                fixed(BlockType::Indestructible)
+            } else if depth < 0 {
+                fixed(BlockType::Air)
+            } else if depth == 0 && soil_depth > 0 {
+                fixed(biome.surface())
+            } else if y_block > generator.settings.depth_limit {
+                fixed(BlockType::Indestructible)
+            } else if depth > 0 && depth <= soil_depth {
+                GenCell {
+                    hardness_multiplier: depth
+                        .to_f64()
+                        .unwrap_or(0.0)
+                        .mul_add(HARDNESS_DEPTH_MULTIPLIER, 1.0),
+                    ..fixed(biome.subsoil())
+                }
+            } else {
+                let water_val = generator.noise_ore.get([
+                    f64::from(x_block) * f64::from(BLOCK_SIZE) * 0.1,
+                    f64::from(y_block) * f64::from(BLOCK_SIZE) * 0.1,
+                    512.0,
+                ]);
+                let water_threshold = 0.65 + generator.settings.liquid_abundance.threshold_shift();
+                if water_val > water_threshold && depth > 0 {
+                    fixed(BlockType::Water)
+                } else {
+                    // Bare rock where erosion took all the soil, with whatever ore it holds.
+                    let plate = generator.plates.sample(x_block, y_block);
+                    let strata = generator.strata_at(x_block, y_block, rock_depth, &plate);
+                    GenCell {
+                        block_type: strata.block_type,
+                        back_type: strata.block_type,
+                        hardness_multiplier: strata.hardness_multiplier,
+                        layer: Some(strata.layer_index),
+                        depth: rock_depth,
                     }
+                }
+            };
+            column.push(cell);
+        }
+        cells.push(column);
+    }
 
-                    let base_hardness = b_type.get_base_hardness();
-                    let s_rect = b_type.get_sprite();
+    ChunkCells {
+        origin_bx,
+        origin_by,
+        ground,
+        climate,
+        cells,
+    }
+}
 
-                    let hp = if base_hardness == -1 {
-                        -1
-                    } else {
-                        let depth = (y_block - SURFACE_Y_LEVEL).to_f64().unwrap_or(0.0);
-                        let multiplier = 1.0 + depth * HARDNESS_DEPTH_MULTIPLIER;
-                        (base_hardness.to_f64().unwrap_or(0.0) * multiplier)
-                            .floor()
-                            .to_i32()
-                            .unwrap_or(0)
-                    };
+fn build_chunk(chunk_x: i32, chunk_y: i32, cells: &ChunkCells) -> Chunk {
+    let mut chunk = Chunk::generated(chunk_x, chunk_y);
+    for (bx, column) in cells.cells.iter().enumerate() {
+        for (by, cell) in column.iter().enumerate() {
+            let block_type = cell.block_type;
 
-                    (hp, s_rect, b_type)
-                };
+            let max_hp = if block_type == BlockType::WarpGate {
+                50
+            } else if block_type == BlockType::Air {
+                0
+            } else {
+                let base_hardness = block_type.get_base_hardness();
+                if base_hardness == -1 {
+                    -1
+                } else {
+                    (base_hardness.to_f64().unwrap_or(0.0) * cell.hardness_multiplier)
+                        .floor()
+                        .to_i32()
+                        .unwrap_or(0)
+                }
+            };
 
+            let Some(mut block) = chunk.get_block(bx, by) else {
+                continue;
+            };
+            block.block_type = block_type;
+            block.max_hp = max_hp;
+            block.current_hp = max_hp;
+            block.liquid_level = if block_type.is_liquid() { 8 } else { 0 };
             if block_type == BlockType::WarpGate {
-                let mut b = Block::new(wx, wy, max_hp, sprite_rect, block_type);
-                b.name = Some("Home".to_string());
-                b.back_type = BlockType::Air; // Don't show a wall behind the home gate
-                row.push(b);
+                block.back_type = BlockType::Air; // Don't show a wall behind the home gate
+                chunk.set_name(bx, by, Some("Home".to_string()));
             } else {
-                row.push(Block::new(wx, wy, max_hp, sprite_rect, block_type));
+                block.back_type = cell.back_type;
             }
+            chunk.set_block(bx, by, &block);
         }
-        blocks.push(row);
     }
-    blocks
-}
-
-fn chunk_coords_to_world_origin(chunk_x: i32, chunk_y: i32) -> (f32, f32) {
-    let world_x =
-        chunk_x.to_f32().unwrap_or(0.0) * CHUNK_SIZE_X_BLOCKS.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
-    let world_y =
-        chunk_y.to_f32().unwrap_or(0.0) * CHUNK_SIZE_Y_BLOCKS.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
-    (world_x, world_y)
+    chunk
 }
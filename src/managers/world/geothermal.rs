@@ -0,0 +1,93 @@
+use super::WorldManager;
+use super::generation::WorldGenerator;
+use crate::components::BlockPos;
+use crate::constants::{
+    BOUNDARY_GRADIENT_BONUS, HEAT_RADIUS_BLOCKS, OCEANIC_GRADIENT_BONUS, SURFACE_TEMPERATURE,
+    TEMPERATURE_CACHE_MAX, TEMPERATURE_GRADIENT,
+};
+use num_traits::ToPrimitive;
+
+/// Temperature `depth` blocks under ground whose surface sits at `base_temperature`
+/// above the global surface temperature.
+#[must_use]
+pub fn rock_temperature(depth: i32, base_temperature: f32, gradient: f32) -> f32 {
+    depth
+        .max(0)
+        .to_f32()
+        .unwrap_or(0.0)
+        .mul_add(gradient, SURFACE_TEMPERATURE + base_temperature)
+}
+
+impl WorldGenerator {
+    /// Degrees gained per block of depth at `(bx, by)` (TGIP Phase 5). Thin oceanic crust
+    /// and plate boundaries, where magma rises close to the surface, heat up fastest.
+    #[must_use]
+    pub fn geothermal_gradient(&self, bx: i32, by: i32) -> f32 {
+        let plate = self.plates.sample(bx, by);
+        let oceanic = (1.0 - plate.continentality).clamp(0.0, 1.0);
+        let boundary = plate.geological_stress.abs().min(1.0);
+        TEMPERATURE_GRADIENT
+            * BOUNDARY_GRADIENT_BONUS
+                .mul_add(boundary, OCEANIC_GRADIENT_BONUS.mul_add(oceanic, 1.0))
+    }
+
+    /// Temperature of undisturbed rock at `(bx, by)` under ground at `surface_y`, before
+    /// any nearby heat source.
+    #[must_use]
+    pub fn ground_temperature(&self, bx: i32, by: i32, surface_y: i32) -> f32 {
+        rock_temperature(
+            by - surface_y,
+            self.climate_at(bx, surface_y).biome().base_temperature,
+            self.geothermal_gradient(bx, by),
+        )
+    }
+}
+
+impl WorldManager {
+    /// Temperature at block `(bx, by)`: the ground temperature plus the heat of the hottest
+    /// lava or other heat source in range, fading with distance. Unloaded blocks add no heat.
+    /// Results are cached until a heat source or loaded chunk changes.
+    #[must_use]
+    pub fn temperature_at(&self, bx: i32, by: i32) -> f32 {
+        let pos = BlockPos::new(bx, by);
+        if let Some(&temperature) = self.temperature_cache.borrow().get(&pos) {
+            return temperature;
+        }
+        let temperature = self.measure_temperature(bx, by);
+        // A column still waiting for erosion reads a rough surface; ask again later.
+        if !self.generator.is_eroded(bx) {
+            return temperature;
+        }
+        let mut cache = self.temperature_cache.borrow_mut();
+        if cache.len() >= TEMPERATURE_CACHE_MAX {
+            cache.clear();
+        }
+        cache.insert(pos, temperature);
+        temperature
+    }
+
+    fn measure_temperature(&self, bx: i32, by: i32) -> f32 {
+        let reach = (HEAT_RADIUS_BLOCKS + 1).to_f32().unwrap_or(1.0);
+        let mut source_heat: f32 = 0.0;
+        for dy in -HEAT_RADIUS_BLOCKS..=HEAT_RADIUS_BLOCKS {
+            for dx in -HEAT_RADIUS_BLOCKS..=HEAT_RADIUS_BLOCKS {
+                let Some(heat) = self
+                    .get_block(bx + dx, by + dy)
+                    .and_then(|b| b.block_type.get_heat())
+                else {
+                    continue;
+                };
+                let distance = dx.to_f32().unwrap_or(0.0).hypot(dy.to_f32().unwrap_or(0.0));
+                source_heat = source_heat.max(heat * (1.0 - distance / reach).max(0.0));
+            }
+        }
+        self.generator
+            .ground_temperature(bx, by, self.surface_height_at(bx))
+            + source_heat
+    }
+
+    /// Forgets every cached temperature, after heat may have appeared or gone.
+    pub(crate) fn invalidate_temperatures(&mut self) {
+        self.temperature_cache.get_mut().clear();
+    }
+}
@@ -0,0 +1,341 @@
+use super::deposits::{DepositOre, pick_ore};
+use super::generation::{ChunkCells, GenCell, WorldGenerator};
+use super::terrain::spawn_flatten_factor;
+use crate::components::BlockType;
+use crate::constants::{CHUNK_SIZE_X_BLOCKS, MACROGRID_SIZE_CHUNKS};
+use crate::utils::{hash_coords, hash_to_unit};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::{Arc, LazyLock};
+
+const HYDROLOGY_PATH: &str = "data/worldgen/hydrology.json";
+const SALT_PLACER: u32 = 0x4D20_0001;
+
+pub static HYDROLOGY_CONFIG: LazyLock<Option<HydrologyConfig>> = LazyLock::new(|| {
+    fs::read_to_string(HYDROLOGY_PATH)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+});
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SedimentConfig {
+    /// Fine sediment on top of the bed.
+    pub top_block: u32,
+    /// Coarse sediment below it.
+    pub base_block: u32,
+    pub blocks_per_flow: f32,
+    pub max_blocks: i32,
+    /// Heavy minerals that settle on the bottom of the gravel.
+    pub placers: Vec<DepositOre>,
+    pub placer_chance: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HydrologyConfig {
+    /// How far upstream rain is gathered into a valley.
+    pub catchment_reach_blocks: i32,
+    /// Rises up to this high inside a valley do not split its catchment.
+    pub divide_tolerance_blocks: i32,
+    /// Catchments below this hold no water, only the bed of a former river.
+    pub river_min_flow: f32,
+    pub former_riverbed_min_flow: f32,
+    pub width_per_flow: f32,
+    pub max_width: i32,
+    pub depth_per_flow: f32,
+    pub max_depth: i32,
+    /// Water a lake may hold per unit of catchment flow, in blocks.
+    pub lake_volume_per_flow: f32,
+    /// Shallower basins are left as a river; measured above the valley floor.
+    pub min_lake_depth: i32,
+    pub max_lake_depth: i32,
+    /// Wider basins are left as a river; must not exceed a macrogrid.
+    pub max_lake_width: i32,
+    pub sediment: SedimentConfig,
+}
+
+/// A river, lake or dried-up riverbed, owned by the macrogrid holding its lowest column.
+#[derive(Clone, Debug)]
+pub struct WaterBody {
+    /// First block column covered.
+    pub left: i32,
+    /// Block Y of the top water row, or `None` for a former riverbed.
+    pub surface_y: Option<i32>,
+    /// Block Y of the bed in each covered column, starting at `left`.
+    pub beds: Vec<i32>,
+    pub sediment_blocks: i32,
+}
+
+/// What a water body leaves in one block column.
+#[derive(Clone, Copy, Debug)]
+pub struct WaterColumn {
+    pub surface_y: Option<i32>,
+    /// First block below the water, where the sediment starts.
+    pub bed_y: i32,
+    pub sediment_blocks: i32,
+}
+
+impl WaterColumn {
+    #[must_use]
+    pub fn is_water(&self, by: i32) -> bool {
+        self.surface_y.is_some_and(|s| by >= s && by < self.bed_y)
+    }
+}
+
+fn macrogrid_size_blocks() -> i32 {
+    (MACROGRID_SIZE_CHUNKS * CHUNK_SIZE_X_BLOCKS)
+        .to_i32()
+        .unwrap_or(128)
+}
+
+fn index(i: i32) -> usize {
+    i.to_usize().unwrap_or(0)
+}
+
+impl WorldGenerator {
+    /// Traces where rain collects along the eroded heightmap of macrogrid column `mx`
+    /// plus one macrogrid either side (TGIP Phase 9), and places a river, lake or former
+    /// riverbed in every valley whose lowest column lies in `mx`.
+    fn simulate_hydrology(&self, mx: i32) -> Arc<[WaterBody]> {
+        let Some(config) = &*HYDROLOGY_CONFIG else {
+            return Arc::new([]);
+        };
+        let size = macrogrid_size_blocks();
+        let first = (mx - 1) * size;
+        let ground: Vec<i32> = (first..first + 3 * size)
+            .map(|bx| self.surface_height_at(bx))
+            .collect();
+        let humidity: Vec<f32> = (mx - 1..=mx + 1)
+            .map(|m| self.climate_cell(m).humidity)
+            .collect();
+        let rain = |i: i32| humidity[index(i / size)];
+        let last = 3 * size - 1;
+        let g = |i: i32| ground[index(i)];
+
+        let mut bodies = Vec::new();
+        for i in size..2 * size {
+            // Valley floors, taking the left end of a flat bottom.
+            if !(g(i) > g(i - 1) && g(i) >= g(i + 1)) {
+                continue;
+            }
+
+            // Rain on every slope draining into this valley. Bumps lower than the divide
+            // tolerance are filled and spilled over, so only the deepest floor of a valley
+            // collects the water.
+            let mut flow = rain(i);
+            let mut deepest = true;
+            for step in [-1, 1] {
+                let mut crest = g(i);
+                let mut j = i;
+                while (j + step).clamp(0, last) == j + step
+                    && (j - i).abs() < config.catchment_reach_blocks
+                    && g(j + step) <= crest + config.divide_tolerance_blocks
+                {
+                    j += step;
+                    flow += rain(j);
+                    crest = crest.min(g(j));
+                    if g(j) > g(i) || (step < 0 && g(j) == g(i)) {
+                        deepest = false;
+                    }
+                }
+            }
+            if !deepest {
+                continue;
+            }
+            if flow < config.former_riverbed_min_flow {
+                continue;
+            }
+
+            let width = (flow * config.width_per_flow)
+                .round()
+                .to_i32()
+                .unwrap_or(1)
+                .clamp(1, config.max_width);
+            let notch_start = (i - (width - 1) / 2).max(1);
+            let notch_end = (notch_start + width - 1).min(last - 1);
+            let sediment_blocks = (flow * config.sediment.blocks_per_flow)
+                .round()
+                .to_i32()
+                .unwrap_or(1)
+                .clamp(1, config.sediment.max_blocks);
+
+            // Wetter worlds carry water in smaller catchments too.
+            let river_min_flow = config.river_min_flow / self.settings.liquid_abundance.factor();
+            let body = if flow < river_min_flow {
+                // Only the sand and gravel of an old channel remain, under the topsoil.
+                WaterBody {
+                    left: first + notch_start,
+                    surface_y: None,
+                    beds: (notch_start..=notch_end).map(|c| g(c) + 1).collect(),
+                    sediment_blocks,
+                }
+            } else {
+                let depth = (flow * config.depth_per_flow)
+                    .round()
+                    .to_i32()
+                    .unwrap_or(1)
+                    .clamp(1, config.max_depth);
+                let bottom = g(i) + depth;
+                let mut bed = ground.clone();
+                for c in notch_start..=notch_end {
+                    bed[index(c)] = bed[index(c)].max(bottom);
+                }
+                let b = |c: i32| bed[index(c)];
+
+                // Water can rise until it spills over the lower of the highest points either
+                // side; the channel alone fills up to its lower bank.
+                let spill_y = bed[..=index(i)]
+                    .iter()
+                    .min()
+                    .copied()
+                    .unwrap_or(bottom)
+                    .max(bed[index(i)..].iter().min().copied().unwrap_or(bottom));
+                let bank_y = b(notch_start - 1).max(b(notch_end + 1));
+                let run = |surface_y: i32| {
+                    let mut l = i;
+                    while l > 0 && b(l - 1) > surface_y {
+                        l -= 1;
+                    }
+                    let mut r = i;
+                    while r < last && b(r + 1) > surface_y {
+                        r += 1;
+                    }
+                    (l, r)
+                };
+                let budget = flow * config.lake_volume_per_flow;
+                let surface_y = (spill_y..bank_y)
+                    .find(|&s| {
+                        let (l, r) = run(s);
+                        let volume: i32 = (l..=r).map(|c| b(c) - s).sum();
+                        (config.min_lake_depth..=config.max_lake_depth).contains(&(g(i) - s))
+                            && r - l < config.max_lake_width.min(size)
+                            && volume.to_f32().unwrap_or(f32::MAX) <= budget
+                    })
+                    .unwrap_or(bank_y);
+                let (l, r) = run(surface_y);
+                WaterBody {
+                    left: first + l,
+                    surface_y: Some(surface_y),
+                    beds: (l..=r).map(b).collect(),
+                    sediment_blocks,
+                }
+            };
+
+            // The home gate keeps its dry, flat ground.
+            let columns = body.left..body.left + body.beds.len().to_i32().unwrap_or(0);
+            if columns.clone().any(|bx| spawn_flatten_factor(bx) < 1.0) {
+                continue;
+            }
+            bodies.push(body);
+        }
+        bodies.into()
+    }
+
+    /// Water and riverbed sediment in block column `bx`. Where bodies overlap the
+    /// highest water surface wins, and any water wins over a dry riverbed.
+    #[must_use]
+    pub fn water_column_at(&self, bx: i32) -> Option<WaterColumn> {
+        let mx = bx.div_euclid(macrogrid_size_blocks());
+        let mut best: Option<WaterColumn> = None;
+        for m in mx - 1..=mx + 1 {
+            let bodies = self
+                .hydrology
+                .get_or_insert_with(m, || self.simulate_hydrology(m));
+            for body in bodies.iter() {
+                let Some(&bed_y) = body
+                    .beds
+                    .get((bx - body.left).to_usize().unwrap_or(usize::MAX))
+                else {
+                    continue;
+                };
+                let column = WaterColumn {
+                    surface_y: body.surface_y,
+                    bed_y,
+                    sediment_blocks: body.sediment_blocks,
+                };
+                let better = match (best.and_then(|b| b.surface_y), column.surface_y) {
+                    (_, None) => best.is_none(),
+                    (None, Some(_)) => true,
+                    (Some(current), Some(new)) => new < current,
+                };
+                if better {
+                    best = Some(column);
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Fills rivers and lakes and lays sediment under them and along former riverbeds.
+/// Runs after the caves, sealing any opening next to the water so it stays put.
+pub fn place_water_bodies(generator: &WorldGenerator, chunk: &mut ChunkCells) {
+    let Some(config) = &*HYDROLOGY_CONFIG else {
+        return;
+    };
+    let sediment = &config.sediment;
+    let origin_bx = chunk.origin_bx;
+    let origin_by = chunk.origin_by;
+    // One extra column either side for the neighbours of the edge columns.
+    let columns: Vec<Option<WaterColumn>> = (origin_bx - 1
+        ..=origin_bx + CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(0))
+        .map(|bx| generator.water_column_at(bx))
+        .collect();
+    let is_water = |x: usize, by: i32| columns[x].is_some_and(|c| c.is_water(by));
+
+    for (x, column) in chunk.cells.iter_mut().enumerate() {
+        let bx = origin_bx + x.to_i32().unwrap_or(0);
+        let ground_y = chunk.ground[x];
+        let water = columns[x + 1];
+
+        for (y, cell) in column.iter_mut().enumerate() {
+            let by = origin_by + y.to_i32().unwrap_or(0);
+            let sediment_cell = |block: u32| GenCell {
+                block_type: BlockType::from_id(block),
+                layer: None,
+                hardness_multiplier: 1.0,
+                ..*cell
+            };
+
+            if let Some(w) = water {
+                if w.is_water(by) {
+                    cell.block_type = BlockType::Water;
+                    cell.layer = None;
+                    continue;
+                }
+                if w.surface_y.is_some_and(|s| by < s) && by >= ground_y {
+                    // The channel cut below the banks, above the waterline.
+                    cell.block_type = BlockType::Air;
+                    cell.layer = None;
+                    continue;
+                }
+                let into_bed = by - w.bed_y;
+                if (0..w.sediment_blocks).contains(&into_bed) {
+                    let roll = hash_to_unit(hash_coords(generator.seed ^ SALT_PLACER, bx, by));
+                    let placer = (into_bed == w.sediment_blocks - 1
+                        && roll < sediment.placer_chance)
+                        .then(|| pick_ore(&sediment.placers, roll / sediment.placer_chance))
+                        .flatten();
+                    *cell = if let Some(ore) = placer {
+                        GenCell {
+                            block_type: ore,
+                            ..sediment_cell(sediment.base_block)
+                        }
+                    } else if into_bed < (w.sediment_blocks + 1) / 2 {
+                        sediment_cell(sediment.top_block)
+                    } else {
+                        sediment_cell(sediment.base_block)
+                    };
+                    continue;
+                }
+            }
+
+            // Caves and pockets must not open beside or below the water.
+            if !cell.block_type.is_solid()
+                && (is_water(x, by) || is_water(x + 2, by) || is_water(x + 1, by - 1))
+            {
+                *cell = sediment_cell(sediment.base_block);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use super::WorldManager;
-use crate::components::{BlockPos, BlockType};
-use crate::constants::BLOCK_SIZE;
+use crate::components::{Block, BlockPos, BlockType};
+use crate::constants::{BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS};
 use num_traits::ToPrimitive;
 use std::collections::HashSet;
 
@@ -26,7 +26,7 @@ impl WorldManager {
                 continue;
             }
 
-            let (level, b_type, interval) = if let Some(b) = self.get_block_ref(bx, by) {
+            let (level, b_type, interval) = if let Some(b) = self.get_block(bx, by) {
                 if !b.block_type.is_liquid() || b.liquid_level == 0 {
                     continue;
                 }
@@ -45,10 +45,21 @@ impl WorldManager {
                 continue;
             }
 
+            // A breached aquifer keeps pushing water in until its reservoir runs dry.
+            let level = if b_type == BlockType::Water && level < 8 {
+                let recharged = self.recharge_aquifer_cell(bx, by, level);
+                if recharged > level {
+                    Self::activate_neighbors(bx, by, &mut next_active);
+                }
+                recharged
+            } else {
+                level
+            };
+
             let mut moved = false;
 
             // 1. Try Down
-            if let Some(down) = self.get_block_ref(bx, by + 1) {
+            if let Some(down) = self.get_block(bx, by + 1) {
                 let d_solid = down.block_type.is_solid();
                 let d_level = down.liquid_level;
                 if !d_solid && d_level < 8 {
@@ -73,7 +84,7 @@ impl WorldManager {
 
             for dx in [-1, 1] {
                 let nx = bx + dx;
-                if let Some(side) = self.get_block_ref(nx, by)
+                if let Some(side) = self.get_block(nx, by)
                     && !side.block_type.is_solid()
                 {
                     // Only spread to air if we have enough pressure (level > 1)
@@ -133,7 +144,7 @@ impl WorldManager {
                     let (target_x, target_y, target_level, can_fill) = if level < 8 {
                         let not_trapped = !self.check_trapped_air(bx, by);
                         (bx, by, level, not_trapped)
-                    } else if let Some(up) = self.get_block_ref(bx, by - 1) {
+                    } else if let Some(up) = self.get_block(bx, by - 1) {
                         let is_permeable = !up.block_type.is_solid() && up.liquid_level < 8;
                         let not_trapped = if is_permeable {
                             // Check if air is trapped (sealed U-tube)
@@ -153,7 +164,7 @@ impl WorldManager {
                         } else {
                             // "Teleport" flow:
                             let (s_level, s_type) = self
-                                .get_block_ref(surface_x, surface_y)
+                                .get_block(surface_x, surface_y)
                                 .map_or((0, BlockType::Air), |s| (s.liquid_level, s.block_type));
 
                             if s_level > 0 {
@@ -175,17 +186,17 @@ impl WorldManager {
                 // Check if still unstable
                 let is_settled = {
                     let d = self
-                        .get_block_ref(bx, by + 1)
+                        .get_block(bx, by + 1)
                         .map_or(true, |b| b.block_type.is_solid() || b.liquid_level == 8);
                     let l = self
-                        .get_block_ref(bx - 1, by)
+                        .get_block(bx - 1, by)
                         .map_or(true, |b| b.block_type.is_solid() || b.liquid_level >= level);
                     let r = self
-                        .get_block_ref(bx + 1, by)
+                        .get_block(bx + 1, by)
                         .map_or(true, |b| b.block_type.is_solid() || b.liquid_level >= level);
                     if level == 8 {
                         let u = self
-                            .get_block_ref(bx, by - 1)
+                            .get_block(bx, by - 1)
                             .map_or(true, |b| b.block_type.is_solid() || b.liquid_level == 8);
                         d && l && r && u
                     } else {
@@ -200,6 +211,41 @@ impl WorldManager {
         self.active_liquids = next_active;
     }
 
+    /// Whether a liquid block has nowhere to flow: supported from below and level with its
+    /// sides. Generated rivers and lakes start like this and are left out of the simulation
+    /// until something next to them changes. A side in a chunk that is not loaded counts as
+    /// holding; `activate_border_liquids` wakes the block once that chunk arrives.
+    pub(crate) fn is_liquid_at_rest(&self, bx: i32, by: i32) -> bool {
+        let Some(level) = self.get_block(bx, by).map(|b| b.liquid_level) else {
+            return true;
+        };
+        let holds = |b: Block, min_level: u8| {
+            b.block_type.is_solid() || (b.block_type.is_liquid() && b.liquid_level >= min_level)
+        };
+        self.get_block(bx, by + 1).is_none_or(|b| holds(b, 8))
+            && self.get_block(bx - 1, by).is_none_or(|b| holds(b, level))
+            && self.get_block(bx + 1, by).is_none_or(|b| holds(b, level))
+    }
+
+    /// Wakes liquid in the loaded chunks around chunk `(chunk_x, chunk_y)` that was left at
+    /// rest against it while it was missing and may now flow into it.
+    pub(crate) fn activate_border_liquids(&mut self, chunk_x: i32, chunk_y: i32) {
+        let size_x = CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(1);
+        let size_y = CHUNK_SIZE_Y_BLOCKS.to_i32().unwrap_or(1);
+        let (left, top) = (chunk_x * size_x, chunk_y * size_y);
+        let (right, bottom) = (left + size_x - 1, top + size_y - 1);
+        let rows = (left..=right).flat_map(|bx| [(bx, top - 1), (bx, bottom + 1)]);
+        let columns = (top..=bottom).flat_map(|by| [(left - 1, by), (right + 1, by)]);
+        for (bx, by) in rows.chain(columns) {
+            let is_liquid = self
+                .get_block(bx, by)
+                .is_some_and(|b| b.block_type.is_liquid() && b.liquid_level > 0);
+            if is_liquid && !self.is_liquid_at_rest(bx, by) {
+                self.active_liquids.insert(BlockPos::new(bx, by));
+            }
+        }
+    }
+
     pub(crate) fn activate_neighbors(bx: i32, by: i32, next_active: &mut HashSet<BlockPos>) {
         next_active.insert(BlockPos::new(bx, by));
         next_active.insert(BlockPos::new(bx, by - 1));
@@ -209,18 +255,14 @@ impl WorldManager {
     }
 
     pub(crate) fn set_liquid_block(&mut self, bx: i32, by: i32, level: u8, b_type: BlockType) {
-        if let Some(b) = self.get_block_mut(bx, by) {
-            b.liquid_level = level;
-            if level == 0 {
-                b.block_type = BlockType::Air;
-                b.is_broken = true;
-                b.sprite_rect = None;
-            } else {
-                b.block_type = b_type;
-                b.is_broken = false;
-                b.sprite_rect = b_type.get_sprite();
+        if let Some(mut b) = self.get_block(bx, by) {
+            if b.block_type != b_type && level > 0 {
+                b.max_hp = b_type.get_base_hardness();
+                b.current_hp = b.max_hp;
             }
-            b.is_modified = true;
+            b.liquid_level = level;
+            b.block_type = if level == 0 { BlockType::Air } else { b_type };
+            self.set_block(&b);
         }
     }
 
@@ -231,7 +273,7 @@ impl WorldManager {
         visited: &mut HashSet<BlockPos>,
     ) -> (i32, i32, u8) {
         // Initial fallback level. If we can't find current block (shouldn't happen), assume 0.
-        let self_level = self.get_block_ref(bx, by).map_or(0, |b| b.liquid_level);
+        let self_level = self.get_block(bx, by).map_or(0, |b| b.liquid_level);
 
         if !visited.insert(BlockPos::new(bx, by)) || visited.len() > 2048 {
             return (by, bx, self_level);
@@ -241,7 +283,7 @@ impl WorldManager {
 
         // Check above in the same column
         let mut curr_y = by - 1;
-        while let Some(b) = self.get_block_ref(bx, curr_y) {
+        while let Some(b) = self.get_block(bx, curr_y) {
             visited.insert(BlockPos::new(bx, curr_y));
             if b.liquid_level > 0 && !b.block_type.is_solid() {
                 // Better if Higher (smaller Y) OR Same Y but Higher Level
@@ -262,7 +304,7 @@ impl WorldManager {
         // Search neighbors (Left, Right, Down)
         let neighbors = [(-1, 0), (1, 0), (0, 1)];
         for (dx, dy) in neighbors {
-            if let Some(nb) = self.get_block_ref(bx + dx, by + dy) {
+            if let Some(nb) = self.get_block(bx + dx, by + dy) {
                 // Traverse full blocks
                 if nb.liquid_level == 8 && !nb.block_type.is_solid() {
                     let nb_best = self.find_highest_liquid_pos(bx + dx, by + dy, visited);
@@ -305,7 +347,7 @@ impl WorldManager {
                     continue;
                 }
 
-                if let Some(nb) = self.get_block_ref(nx, ny) {
+                if let Some(nb) = self.get_block(nx, ny) {
                     if !nb.block_type.is_solid() && nb.liquid_level < 8 {
                         // Air or partial liquid passes
                         visited.insert(BlockPos::new(nx, ny));
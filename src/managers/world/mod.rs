@@ -1,13 +1,38 @@
 use crate::components::{BlockPos, MacroGrid};
-use noise::{Perlin, Seedable};
+use crate::constants::CHUNK_MEMORY_CAP_MB;
+use generation::WorldGenerator;
+use settings::WorldSettings;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use workers::GenerationWorkers;
 
 pub mod access;
+pub mod aquifers;
+pub mod biome_strata;
+pub mod caves;
+pub mod climate;
+pub mod deposits;
+pub mod erosion;
+pub mod eviction;
+pub mod falling;
 pub mod generation;
+pub mod geothermal;
+pub mod hydrology;
 pub mod liquids;
 pub mod modifications;
+pub mod plates;
+pub mod pumps;
+pub mod regions;
+pub mod settings;
+pub mod strata;
+pub mod streaming;
+pub mod terrain;
+pub mod thumbnail;
 pub mod update;
 pub mod view;
+pub mod workers;
 
 pub struct WorldManager {
     pub macrogrids: HashMap<BlockPos, MacroGrid>,
@@ -16,10 +41,38 @@ pub struct WorldManager {
     pub pending_modifications: HashMap<BlockPos, crate::managers::persistence::ChunkSaveData>,
     pub active_liquids: HashSet<BlockPos>,
     pub liquid_tick_counter: u64,
+    /// Levels left in each aquifer reservoir that has been drawn from.
+    pub aquifer_reserves: HashMap<BlockPos, u32>,
+    pub aquifer_hints: HashMap<BlockPos, bool>,
+    /// Reservoir feeding each block the liquid simulation has recharged, so the aquifer
+    /// noise is worked out once per cell rather than on every liquid tick.
+    pub(crate) aquifer_cells: HashMap<BlockPos, Option<BlockPos>>,
+    pub pumps: HashSet<BlockPos>,
+    pub pump_tick_counter: u32,
     pub world_seed_main: u32,
     pub world_seed_ore: u32,
-    pub(crate) noise_main: Perlin,
-    pub(crate) noise_ore: Perlin,
+    /// Frame each loaded chunk was last wanted on, for unloading the stale ones.
+    pub chunk_access: HashMap<BlockPos, u64>,
+    pub access_clock: u64,
+    /// Bytes of block data kept loaded before chunks are unloaded.
+    pub chunk_memory_cap: usize,
+    /// Save directory whose region files are read as their chunks are needed; `None`
+    /// until the world has been saved in one.
+    pub region_dir: Option<PathBuf>,
+    pub loaded_regions: HashSet<BlockPos>,
+    /// Regions changed since the last save, and those a running save is writing.
+    pub dirty_regions: HashSet<BlockPos>,
+    pub saving_regions: HashSet<BlockPos>,
+    /// Save directory the running save is writing, taken when it began.
+    pub saving_dir: Option<PathBuf>,
+    /// Saved regions whose file and backups could not be read; saves leave them alone.
+    pub unreadable_regions: HashSet<BlockPos>,
+    /// Problems met reading region files, waiting to be shown.
+    pub region_problems: Vec<String>,
+    /// Temperatures worked out so far, forgotten whenever a heat source may have changed.
+    pub(crate) temperature_cache: RefCell<HashMap<BlockPos, f32>>,
+    pub(crate) generator: Arc<WorldGenerator>,
+    pub(crate) workers: GenerationWorkers,
 }
 
 impl Default for WorldManager {
@@ -33,9 +86,8 @@ impl WorldManager {
         let mut rng = ::rand::rng();
         let seed_main = ::rand::Rng::random::<u32>(&mut rng);
         let seed_ore = ::rand::Rng::random::<u32>(&mut rng);
-
-        let noise_main = Perlin::new(seed_main).set_seed(seed_main);
-        let noise_ore = Perlin::new(seed_ore).set_seed(seed_ore);
+        let generator = Arc::new(WorldGenerator::new(seed_main, seed_ore));
+        let workers = GenerationWorkers::new(&generator);
 
         Self {
             macrogrids: HashMap::new(),
@@ -44,24 +96,54 @@ impl WorldManager {
             pending_modifications: HashMap::new(),
             active_liquids: HashSet::new(),
             liquid_tick_counter: 0,
+            aquifer_reserves: HashMap::new(),
+            aquifer_hints: HashMap::new(),
+            aquifer_cells: HashMap::new(),
+            pumps: HashSet::new(),
+            pump_tick_counter: 0,
             world_seed_main: seed_main,
             world_seed_ore: seed_ore,
-            noise_main,
-            noise_ore,
+            chunk_access: HashMap::new(),
+            access_clock: 0,
+            chunk_memory_cap: CHUNK_MEMORY_CAP_MB * 1024 * 1024,
+            region_dir: None,
+            loaded_regions: HashSet::new(),
+            dirty_regions: HashSet::new(),
+            saving_regions: HashSet::new(),
+            saving_dir: None,
+            unreadable_regions: HashSet::new(),
+            region_problems: Vec::new(),
+            temperature_cache: RefCell::new(HashMap::new()),
+            generator,
+            workers,
         }
     }
 
-    pub fn seed(&mut self, main: u32, ore: u32) {
+    pub fn seed(&mut self, main: u32, ore: u32, settings: WorldSettings) {
         self.world_seed_main = main;
         self.world_seed_ore = ore;
-        self.noise_main = Perlin::new(main).set_seed(main);
-        self.noise_ore = Perlin::new(ore).set_seed(ore);
+        self.generator = Arc::new(WorldGenerator::with_settings(main, ore, settings));
+        self.workers = GenerationWorkers::new(&self.generator);
         self.macrogrids.clear();
         self.generated_chunk_coords.clear();
         self.visited_chunks.clear();
         self.pending_modifications.clear();
         self.active_liquids.clear();
         self.liquid_tick_counter = 0;
+        self.aquifer_reserves.clear();
+        self.aquifer_hints.clear();
+        self.aquifer_cells.clear();
+        self.pumps.clear();
+        self.pump_tick_counter = 0;
+        self.chunk_access.clear();
+        self.region_dir = None;
+        self.loaded_regions.clear();
+        self.dirty_regions.clear();
+        self.saving_regions.clear();
+        self.saving_dir = None;
+        self.unreadable_regions.clear();
+        self.region_problems.clear();
+        self.temperature_cache.get_mut().clear();
     }
 
     pub fn reset(&mut self) {
@@ -71,5 +153,25 @@ impl WorldManager {
         self.pending_modifications.clear();
         self.active_liquids.clear();
         self.liquid_tick_counter = 0;
+        self.aquifer_reserves.clear();
+        self.aquifer_hints.clear();
+        self.aquifer_cells.clear();
+        self.pumps.clear();
+        self.pump_tick_counter = 0;
+        self.chunk_access.clear();
+        self.region_dir = None;
+        self.loaded_regions.clear();
+        self.dirty_regions.clear();
+        self.saving_regions.clear();
+        self.saving_dir = None;
+        self.unreadable_regions.clear();
+        self.region_problems.clear();
+        self.temperature_cache.get_mut().clear();
+    }
+
+    /// Options the current world was generated with.
+    #[must_use]
+    pub fn settings(&self) -> &WorldSettings {
+        &self.generator.settings
     }
 }
@@ -1,17 +1,24 @@
 use super::WorldManager;
 use crate::components::{BlockPos, BlockType, Chunk};
 use crate::constants::{CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS};
+use crate::managers::persistence::{BlockSaveData, ChunkSaveData};
 use crate::utils::chunk_to_macrogrid_coords;
+use num_traits::ToPrimitive;
 
 impl WorldManager {
+    /// Lays saved regions over the world: generated chunks change at once, the rest when
+    /// they load. `seed` clears what an earlier world left pending.
     pub fn apply_modifications(
         &mut self,
         mod_macrogrids_data: Vec<crate::managers::persistence::MacroGridSaveData>,
     ) {
-        // Clear old pending modifications to avoid stale data (though typically this is called on load)
-        self.pending_modifications.clear();
-
+        self.invalidate_temperatures();
         for mg_data in mod_macrogrids_data {
+            // Saves from before region files carry every region at once. They all count
+            // as loaded, and as dirty so the next save writes them into a save directory.
+            let mg_coords = BlockPos::new(mg_data.mgx, mg_data.mgy);
+            self.loaded_regions.insert(mg_coords);
+            self.dirty_regions.insert(mg_coords);
             for chunk_data in mg_data.chunks {
                 let cx = chunk_data.cx;
                 let cy = chunk_data.cy;
@@ -54,16 +61,16 @@ pub fn apply_chunk_save_data(
             let bx = current_idx % CHUNK_SIZE_X_BLOCKS;
             let by = current_idx / CHUNK_SIZE_X_BLOCKS;
 
-            if let Some(block) = chunk.get_block(bx, by) {
+            if let Some(mut block) = chunk.get_block(bx, by) {
+                // Placed blocks get the hit points of their kind; others keep the generated ones.
+                if block.block_type != block_type {
+                    block.max_hp = block_type.get_base_hardness();
+                }
                 block.block_type = block_type;
-                block.is_modified = true;
-
-                let new_sprite = block.block_type.get_sprite();
-
-                block.sprite_rect = new_sprite;
-                block.is_broken = block.block_type == BlockType::Air;
-                block.current_hp = if block.is_broken { 0 } else { block.max_hp };
+                block.current_hp = block.max_hp;
+                block.last_damage_time = None;
                 block.liquid_level = level;
+                chunk.set_block(bx, by, &block);
             }
             current_idx += 1;
         }
@@ -73,10 +80,70 @@ pub fn apply_chunk_save_data(
     for named_block in &chunk_data.named_blocks {
         let bx = named_block.i as usize / CHUNK_SIZE_Y_BLOCKS;
         let by = named_block.i as usize % CHUNK_SIZE_Y_BLOCKS;
-        if let Some(block) = chunk.get_block(bx, by) {
-            block.name.clone_from(&named_block.n);
-        }
+        chunk.set_name(bx, by, named_block.n.clone());
     }
 
     chunk.is_modified_in_session = true;
 }
+
+/// Encodes the whole of chunk `(cx, cy)` for saving, or for keeping it while unloaded.
+#[must_use]
+pub fn encode_chunk_save_data(cx: i32, cy: i32, chunk: &Chunk) -> ChunkSaveData {
+    let mut rle_blocks: Vec<u32> = Vec::new();
+    let mut named_blocks: Vec<BlockSaveData> = Vec::new();
+
+    let mut last_type_id: Option<u32> = None;
+    let mut last_level: u8 = 0;
+    let mut current_count: u32 = 0;
+
+    // Scan row-major (y then x) for better horizontal RLE runs
+    for by in 0..CHUNK_SIZE_Y_BLOCKS {
+        for bx in 0..CHUNK_SIZE_X_BLOCKS {
+            let Some(block) = chunk.get_block(bx, by) else {
+                continue;
+            };
+            let type_id = block.block_type.to_id();
+            let level = block.liquid_level;
+            let index: u32 = (bx * CHUNK_SIZE_Y_BLOCKS + by).to_u32().unwrap_or(0);
+
+            if let Some(name) = chunk.name(bx, by) {
+                named_blocks.push(BlockSaveData {
+                    i: index,
+                    t: block.block_type,
+                    n: Some(name.to_string()),
+                });
+            }
+
+            if let Some(l_id) = last_type_id {
+                if l_id == type_id && last_level == level && current_count < u32::MAX {
+                    current_count += 1;
+                } else {
+                    rle_blocks.push(l_id);
+                    rle_blocks.push(u32::from(last_level));
+                    rle_blocks.push(current_count);
+
+                    last_type_id = Some(type_id);
+                    last_level = level;
+                    current_count = 1;
+                }
+            } else {
+                last_type_id = Some(type_id);
+                last_level = level;
+                current_count = 1;
+            }
+        }
+    }
+
+    if let Some(l_id) = last_type_id {
+        rle_blocks.push(l_id);
+        rle_blocks.push(u32::from(last_level));
+        rle_blocks.push(current_count);
+    }
+
+    ChunkSaveData {
+        cx,
+        cy,
+        blocks: rle_blocks,
+        named_blocks,
+    }
+}
@@ -0,0 +1,268 @@
+use super::generation::MacrogridCache;
+use crate::components::BlockPos;
+use crate::constants::{
+    CHUNK_SIZE_X_BLOCKS, CONTINENTAL_PLATE_RATIO, MACROGRID_SIZE_CHUNKS, MOUNTAIN_UPLIFT_BLOCKS,
+    PLATE_SIZE_MACROGRIDS, PLATE_VERTICAL_STRETCH, RIFT_DEPTH_BLOCKS, TRENCH_DEPTH_BLOCKS,
+    VOLCANIC_ARC_UPLIFT_BLOCKS,
+};
+use crate::utils::{hash_coords, hash_to_unit};
+use macroquad::prelude::Vec2;
+use num_traits::ToPrimitive;
+use std::sync::Arc;
+
+// Salts keep the different per-plate properties independent of each other.
+const SALT_JITTER_X: u32 = 0x1A2B_0001;
+const SALT_JITTER_Y: u32 = 0x1A2B_0002;
+const SALT_TYPE: u32 = 0x1A2B_0003;
+const SALT_ANGLE: u32 = 0x1A2B_0004;
+const SALT_SPEED: u32 = 0x1A2B_0005;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlateType {
+    Oceanic,
+    Continental,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryType {
+    Interior,
+    Convergent,
+    Divergent,
+    Transform,
+}
+
+/// Plate properties of a single macrogrid cell.
+#[derive(Clone, Copy, Debug)]
+pub struct PlateCell {
+    pub plate_id: u32,
+    pub plate_type: PlateType,
+    pub motion: Vec2,
+    /// Signed boundary pressure in `[-1, 1]`: positive where plates collide,
+    /// negative where they pull apart, zero in plate interiors.
+    pub geological_stress: f32,
+    pub boundary: BoundaryType,
+    /// Elevation offset in blocks produced by the boundary (mountains, trenches, rifts).
+    pub uplift: f32,
+}
+
+/// Plate values interpolated at block resolution.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlateSample {
+    pub geological_stress: f32,
+    /// Magnitude of sideways (transform) motion across the nearest boundary.
+    pub shear: f32,
+    /// 0.0 for oceanic crust, 1.0 for continental crust.
+    pub continentality: f32,
+    pub uplift: f32,
+}
+
+/// Voronoi plate layer laid over the macrogrid. Every value is derived from the
+/// seed, so any cell can be recomputed on demand; the corners blended by `sample` are
+/// kept per macrogrid cell since every block of a chunk needs the same four.
+#[derive(Debug)]
+pub struct PlateLayer {
+    seed: u32,
+    corners: MacrogridCache<PlateCell, BlockPos>,
+}
+
+struct PlateSeed {
+    id: u32,
+    x: f32,
+    y: f32,
+}
+
+impl PlateLayer {
+    #[must_use]
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            corners: MacrogridCache::default(),
+        }
+    }
+
+    fn macrogrid_size_blocks() -> f32 {
+        (MACROGRID_SIZE_CHUNKS * CHUNK_SIZE_X_BLOCKS)
+            .to_f32()
+            .unwrap_or(128.0)
+    }
+
+    /// Jittered plate seed of a coarse lattice cell, in macrogrid units.
+    fn plate_seed(&self, lx: i32, ly: i32) -> PlateSeed {
+        let jx = hash_to_unit(hash_coords(self.seed ^ SALT_JITTER_X, lx, ly));
+        let jy = hash_to_unit(hash_coords(self.seed ^ SALT_JITTER_Y, lx, ly));
+        PlateSeed {
+            id: hash_coords(self.seed, lx, ly),
+            x: (lx.to_f32().unwrap_or(0.0) + jx) * PLATE_SIZE_MACROGRIDS,
+            y: (ly.to_f32().unwrap_or(0.0) + jy) * PLATE_SIZE_MACROGRIDS * PLATE_VERTICAL_STRETCH,
+        }
+    }
+
+    /// Finds the plate owning the centre of a macrogrid cell.
+    fn nearest_plate(&self, mg: BlockPos) -> PlateSeed {
+        let px = mg.x.to_f32().unwrap_or(0.0) + 0.5;
+        let py = mg.y.to_f32().unwrap_or(0.0) + 0.5;
+        let lx = (px / PLATE_SIZE_MACROGRIDS).floor().to_i32().unwrap_or(0);
+        let ly = (py / (PLATE_SIZE_MACROGRIDS * PLATE_VERTICAL_STRETCH))
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+
+        let mut best = self.plate_seed(lx, ly);
+        let mut best_dist = f32::MAX;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let candidate = self.plate_seed(lx + dx, ly + dy);
+                // Plates are stretched vertically so boundaries cut through the crust
+                // as steep planes instead of horizontal layers.
+                let ddx = candidate.x - px;
+                let ddy = (candidate.y - py) / PLATE_VERTICAL_STRETCH;
+                let dist = ddx.mul_add(ddx, ddy * ddy);
+                if dist < best_dist {
+                    best_dist = dist;
+                    best = candidate;
+                }
+            }
+        }
+        best
+    }
+
+    fn plate_type(&self, plate_id: u32) -> PlateType {
+        if hash_to_unit(hash_coords(
+            self.seed ^ SALT_TYPE,
+            plate_id.cast_signed(),
+            0,
+        )) < CONTINENTAL_PLATE_RATIO
+        {
+            PlateType::Continental
+        } else {
+            PlateType::Oceanic
+        }
+    }
+
+    fn plate_motion(&self, plate_id: u32) -> Vec2 {
+        let angle = hash_to_unit(hash_coords(
+            self.seed ^ SALT_ANGLE,
+            plate_id.cast_signed(),
+            0,
+        )) * std::f32::consts::TAU;
+        let speed = hash_to_unit(hash_coords(
+            self.seed ^ SALT_SPEED,
+            plate_id.cast_signed(),
+            0,
+        ))
+        .mul_add(0.8, 0.2);
+        Vec2::new(angle.cos(), angle.sin()) * speed
+    }
+
+    /// Computes the plate cell for a macrogrid coordinate.
+    #[must_use]
+    pub fn cell(&self, mg: BlockPos) -> PlateCell {
+        let plate_id = self.nearest_plate(mg).id;
+        let plate_type = self.plate_type(plate_id);
+        let motion = self.plate_motion(plate_id);
+
+        let mut convergence = 0.0;
+        let mut shear = 0.0;
+        let mut boundary_count = 0;
+        let mut uplift = 0.0;
+
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let neighbor_id = self.nearest_plate(BlockPos::new(mg.x + dx, mg.y + dy)).id;
+            if neighbor_id == plate_id {
+                continue;
+            }
+            boundary_count += 1;
+
+            let normal = Vec2::new(dx.to_f32().unwrap_or(0.0), dy.to_f32().unwrap_or(0.0));
+            let relative = motion - self.plate_motion(neighbor_id);
+            // Positive when this plate moves towards its neighbour.
+            let closing = relative.dot(normal);
+            convergence += closing;
+            shear += relative.perp_dot(normal).abs();
+
+            let neighbor_type = self.plate_type(neighbor_id);
+            uplift += match (plate_type, neighbor_type) {
+                _ if closing < 0.0 => -(-closing).min(1.0) * RIFT_DEPTH_BLOCKS,
+                (PlateType::Continental, PlateType::Continental) => {
+                    closing.min(1.0) * MOUNTAIN_UPLIFT_BLOCKS
+                }
+                (PlateType::Continental, PlateType::Oceanic) => {
+                    closing.min(1.0) * VOLCANIC_ARC_UPLIFT_BLOCKS
+                }
+                (PlateType::Oceanic, _) => -closing.min(1.0) * TRENCH_DEPTH_BLOCKS,
+            };
+        }
+
+        if boundary_count > 0 {
+            uplift /= boundary_count.to_f32().unwrap_or(1.0);
+        }
+        let geological_stress = (convergence / 2.0).clamp(-1.0, 1.0);
+        let boundary = if boundary_count == 0 {
+            BoundaryType::Interior
+        } else if shear > convergence.abs() * 2.0 {
+            BoundaryType::Transform
+        } else if convergence >= 0.0 {
+            BoundaryType::Convergent
+        } else {
+            BoundaryType::Divergent
+        };
+
+        PlateCell {
+            plate_id,
+            plate_type,
+            motion,
+            geological_stress,
+            boundary,
+            uplift,
+        }
+    }
+
+    /// Bilinearly interpolates the plate values of the four macrogrid cells
+    /// surrounding a block, so properties blend smoothly across cell borders.
+    #[must_use]
+    pub fn sample(&self, bx: i32, by: i32) -> PlateSample {
+        let size = Self::macrogrid_size_blocks();
+        let fx = (bx.to_f32().unwrap_or(0.0) - size / 2.0) / size;
+        let fy = (by.to_f32().unwrap_or(0.0) - size / 2.0) / size;
+        let x0 = fx.floor();
+        let y0 = fy.floor();
+        let tx = fx - x0;
+        let ty = fy - y0;
+        let mx = x0.to_i32().unwrap_or(0);
+        let my = y0.to_i32().unwrap_or(0);
+
+        let corners = self.corners.get_or_insert_with(BlockPos::new(mx, my), || {
+            Arc::new([
+                self.cell(BlockPos::new(mx, my)),
+                self.cell(BlockPos::new(mx + 1, my)),
+                self.cell(BlockPos::new(mx, my + 1)),
+                self.cell(BlockPos::new(mx + 1, my + 1)),
+            ])
+        });
+        let weights = [
+            (1.0 - tx) * (1.0 - ty),
+            tx * (1.0 - ty),
+            (1.0 - tx) * ty,
+            tx * ty,
+        ];
+
+        let mut sample = PlateSample::default();
+        for (cell, weight) in corners.iter().zip(weights) {
+            let shear = if cell.boundary == BoundaryType::Transform {
+                1.0
+            } else {
+                0.0
+            };
+            let continental = if cell.plate_type == PlateType::Continental {
+                1.0
+            } else {
+                0.0
+            };
+            sample.geological_stress += cell.geological_stress * weight;
+            sample.shear += shear * weight;
+            sample.continentality += continental * weight;
+            sample.uplift += cell.uplift * weight;
+        }
+        sample
+    }
+}
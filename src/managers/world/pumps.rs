@@ -0,0 +1,52 @@
+use super::WorldManager;
+use crate::components::{BlockPos, BlockType};
+use crate::constants::{PUMP_DRAIN_PER_TICK, PUMP_TICK_INTERVAL};
+use num_traits::ToPrimitive;
+
+impl WorldManager {
+    /// Runs every placed pump. A pump takes water from its four neighbours; next to an
+    /// aquifer it empties the reservoir behind the cell first, then the cell itself.
+    pub fn update_pumps(&mut self) {
+        if self.pumps.is_empty() {
+            return;
+        }
+        self.pump_tick_counter += 1;
+        if !self.pump_tick_counter.is_multiple_of(PUMP_TICK_INTERVAL) {
+            return;
+        }
+
+        // Forget pumps that were broken or replaced.
+        let pumps: Vec<BlockPos> = self.pumps.iter().copied().collect();
+        for pump in pumps {
+            match self.get_block(pump.x, pump.y) {
+                Some(b) if b.block_type == BlockType::Pump => {}
+                Some(_) => {
+                    self.pumps.remove(&pump);
+                    continue;
+                }
+                // Chunk not loaded: keep the pump for later.
+                None => continue,
+            }
+
+            for (dx, dy) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+                let (nx, ny) = (pump.x + dx, pump.y + dy);
+                let Some(level) = self
+                    .get_block(nx, ny)
+                    .filter(|b| b.block_type == BlockType::Water && b.liquid_level > 0)
+                    .map(|b| b.liquid_level)
+                else {
+                    continue;
+                };
+
+                let from_reservoir = self.generator.aquifer_at(nx, ny).map_or(0, |reservoir| {
+                    self.draw_from_aquifer(reservoir, PUMP_DRAIN_PER_TICK)
+                });
+                if from_reservoir == 0 {
+                    let drained = PUMP_DRAIN_PER_TICK.to_u8().unwrap_or(u8::MAX);
+                    self.set_liquid_block(nx, ny, level.saturating_sub(drained), BlockType::Water);
+                    Self::activate_neighbors(nx, ny, &mut self.active_liquids);
+                }
+            }
+        }
+    }
+}
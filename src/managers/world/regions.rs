@@ -0,0 +1,123 @@
+use super::WorldManager;
+use super::modifications;
+use crate::components::BlockPos;
+use crate::managers::persistence::{ChunkSaveData, MacroGridSaveData, PersistenceManager};
+use crate::utils::chunk_to_macrogrid_coords;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+impl WorldManager {
+    /// Reads the saved region holding chunk `(cx, cy)` into `pending_modifications` the
+    /// first time one of its chunks is needed, so loading a world never reads every region.
+    /// Chunks that do not load, and regions that cannot be read at all, are reported in
+    /// `region_problems`; an unreadable region is never saved over.
+    pub(crate) fn load_region_of(&mut self, cx: i32, cy: i32) {
+        let (mg_coords, _) = chunk_to_macrogrid_coords(cx, cy);
+        if !self.loaded_regions.insert(mg_coords) {
+            return;
+        }
+        let Some(dir) = &self.region_dir else {
+            return;
+        };
+        let region = match PersistenceManager::load_region(dir, mg_coords.x, mg_coords.y) {
+            Ok(Some((region, problems))) => {
+                self.region_problems.extend(
+                    problems
+                        .into_iter()
+                        .map(|e| format!("Region ({}, {}): {e}", mg_coords.x, mg_coords.y)),
+                );
+                region
+            }
+            Ok(None) => return,
+            Err(e) => {
+                self.unreadable_regions.insert(mg_coords);
+                self.region_problems.push(format!(
+                    "Region ({}, {}) is unreadable and will not be saved: {e}",
+                    mg_coords.x, mg_coords.y
+                ));
+                return;
+            }
+        };
+        for chunk_data in region.chunks {
+            self.pending_modifications
+                .insert(BlockPos::new(chunk_data.cx, chunk_data.cy), chunk_data);
+        }
+    }
+
+    /// Flags chunk `(cx, cy)` as changed so its region is written on the next save.
+    pub fn mark_chunk_modified(&mut self, cx: i32, cy: i32) {
+        if let Some(chunk) = self.get_chunk_mut(cx, cy) {
+            chunk.is_modified_in_session = true;
+            self.dirty_regions
+                .insert(chunk_to_macrogrid_coords(cx, cy).0);
+        }
+    }
+
+    /// Everything region `mg_coords` should hold on disk: its changed loaded chunks and
+    /// the saved chunks waiting for it in `pending_modifications`.
+    #[must_use]
+    pub fn region_save_data(&self, mg_coords: BlockPos) -> MacroGridSaveData {
+        let mut chunks: Vec<ChunkSaveData> = self
+            .macrogrids
+            .get(&mg_coords)
+            .into_iter()
+            .flat_map(|macrogrid| macrogrid.chunks.values())
+            .filter(|chunk| chunk.is_modified_in_session)
+            .map(|chunk| modifications::encode_chunk_save_data(chunk.cx, chunk.cy, chunk))
+            .collect();
+        chunks.extend(
+            self.pending_modifications
+                .values()
+                .filter(|data| chunk_to_macrogrid_coords(data.cx, data.cy).0 == mg_coords)
+                .cloned(),
+        );
+        chunks.sort_unstable_by_key(|data| (data.cx, data.cy));
+        MacroGridSaveData {
+            mgx: mg_coords.x,
+            mgy: mg_coords.y,
+            chunks,
+        }
+    }
+
+    /// Whether the next save has to rewrite every region, because the world has no save
+    /// directory of its own yet.
+    #[must_use]
+    pub const fn needs_full_save(&self) -> bool {
+        self.region_dir.is_none()
+    }
+
+    /// Dirty regions in a stable order, for writing. Regions whose file could not be read
+    /// are left out, so the chunks on disk outlive the ones changed here.
+    #[must_use]
+    pub fn dirty_region_save_data(&self) -> Vec<MacroGridSaveData> {
+        let mut regions: Vec<BlockPos> = self
+            .dirty_regions
+            .difference(&self.unreadable_regions)
+            .copied()
+            .collect();
+        regions.sort_unstable_by_key(|pos| (pos.x, pos.y));
+        regions
+            .into_iter()
+            .map(|mg_coords| self.region_save_data(mg_coords))
+            .collect()
+    }
+
+    /// Hands the dirty regions to a save into `dir` in progress; changes made meanwhile
+    /// dirty them again.
+    pub fn begin_region_save(&mut self, dir: PathBuf) {
+        self.saving_regions = std::mem::take(&mut self.dirty_regions);
+        self.saving_dir = Some(dir);
+    }
+
+    /// Settles the save started by `begin_region_save`. A failed save leaves its regions
+    /// dirty; a successful one makes the directory it wrote the world's save directory.
+    pub fn finish_region_save(&mut self, is_saved: bool) {
+        let saved: HashSet<BlockPos> = std::mem::take(&mut self.saving_regions);
+        let dir = self.saving_dir.take();
+        if !is_saved {
+            self.dirty_regions.extend(saved);
+        } else if dir.is_some() {
+            self.region_dir = dir;
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+/// Bedrock depths offered on the new-game screen, in block Y.
+pub const DEPTH_LIMIT_PRESETS: [i32; 4] = [500, 1000, 2000, 4000];
+pub const STARTING_MONEY_PRESETS: [i32; 4] = [0, 500, 2000, 10000];
+
+/// How much of a feature a world has, relative to the tuned default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Abundance {
+    Scarce,
+    #[default]
+    Normal,
+    Abundant,
+}
+
+impl Abundance {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Scarce => Self::Normal,
+            Self::Normal => Self::Abundant,
+            Self::Abundant => Self::Scarce,
+        }
+    }
+
+    /// Scales chances, widths and volumes.
+    #[must_use]
+    pub const fn factor(self) -> f32 {
+        match self {
+            Self::Scarce => 0.5,
+            Self::Normal => 1.0,
+            Self::Abundant => 1.75,
+        }
+    }
+
+    /// Added to noise thresholds; below zero more of the noise field passes.
+    #[must_use]
+    pub const fn threshold_shift(self) -> f64 {
+        match self {
+            Self::Scarce => 0.08,
+            Self::Normal => 0.0,
+            Self::Abundant => -0.08,
+        }
+    }
+
+    #[must_use]
+    pub const fn lang_key(self) -> &'static str {
+        match self {
+            Self::Scarce => "settings.abundance.scarce",
+            Self::Normal => "settings.abundance.normal",
+            Self::Abundant => "settings.abundance.abundant",
+        }
+    }
+}
+
+/// Options picked on the new-game screen. The defaults reproduce worlds
+/// made before the options existed, so older saves load unchanged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldSettings {
+    pub ore_richness: Abundance,
+    pub cave_density: Abundance,
+    /// Aquifers, water pockets, rivers and lava.
+    pub liquid_abundance: Abundance,
+    /// Block Y below which the world is solid bedrock.
+    pub depth_limit: i32,
+    pub starting_money: i32,
+}
+
+impl Default for WorldSettings {
+    fn default() -> Self {
+        Self {
+            ore_richness: Abundance::Normal,
+            cave_density: Abundance::Normal,
+            liquid_abundance: Abundance::Normal,
+            depth_limit: 1000,
+            starting_money: 0,
+        }
+    }
+}
+
+/// A row of the new-game screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorldOption {
+    OreRichness,
+    CaveDensity,
+    LiquidAbundance,
+    DepthLimit,
+    StartingMoney,
+}
+
+impl WorldOption {
+    pub const ALL: [Self; 5] = [
+        Self::OreRichness,
+        Self::CaveDensity,
+        Self::LiquidAbundance,
+        Self::DepthLimit,
+        Self::StartingMoney,
+    ];
+
+    #[must_use]
+    pub const fn lang_key(self) -> &'static str {
+        match self {
+            Self::OreRichness => "settings.ore_richness",
+            Self::CaveDensity => "settings.cave_density",
+            Self::LiquidAbundance => "settings.liquid_abundance",
+            Self::DepthLimit => "settings.depth_limit",
+            Self::StartingMoney => "settings.starting_money",
+        }
+    }
+}
+
+impl WorldSettings {
+    /// Moves `option` on to its next preset.
+    pub fn cycle(&mut self, option: WorldOption) {
+        match option {
+            WorldOption::OreRichness => self.ore_richness = self.ore_richness.next(),
+            WorldOption::CaveDensity => self.cave_density = self.cave_density.next(),
+            WorldOption::LiquidAbundance => self.liquid_abundance = self.liquid_abundance.next(),
+            WorldOption::DepthLimit => {
+                self.depth_limit = next_preset(&DEPTH_LIMIT_PRESETS, self.depth_limit);
+            }
+            WorldOption::StartingMoney => {
+                self.starting_money = next_preset(&STARTING_MONEY_PRESETS, self.starting_money);
+            }
+        }
+    }
+
+    /// Value of `option` for display, as a lang key where it has one.
+    #[must_use]
+    pub fn value_label(&self, option: WorldOption) -> String {
+        match option {
+            WorldOption::OreRichness => self.ore_richness.lang_key().to_string(),
+            WorldOption::CaveDensity => self.cave_density.lang_key().to_string(),
+            WorldOption::LiquidAbundance => self.liquid_abundance.lang_key().to_string(),
+            WorldOption::DepthLimit => self.depth_limit.to_string(),
+            WorldOption::StartingMoney => format!("${}", self.starting_money),
+        }
+    }
+}
+
+/// The preset after `current`, wrapping around; values off the list go back to the first.
+#[must_use]
+pub fn next_preset(presets: &[i32], current: i32) -> i32 {
+    presets
+        .iter()
+        .position(|&p| p == current)
+        .and_then(|i| presets.get(i + 1))
+        .or_else(|| presets.first())
+        .copied()
+        .unwrap_or(current)
+}
+
+/// Turns a seed typed by the player into the main and ore seeds (64-bit FNV-1a, split).
+#[must_use]
+pub fn seeds_from_text(text: &str) -> (u32, u32) {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let [a, b, c, d, e, f, g, h] = hash.to_le_bytes();
+    (
+        u32::from_le_bytes([a, b, c, d]),
+        u32::from_le_bytes([e, f, g, h]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_split_the_fnv_1a_hash_of_the_text() {
+        // Published FNV-1a 64-bit test vectors.
+        assert_eq!(seeds_from_text(""), (0x8422_2325, 0xcbf2_9ce4));
+        assert_eq!(seeds_from_text("a"), (0x8601_ec8c, 0xaf63_dc4c));
+        assert_eq!(seeds_from_text("foobar"), (0xf739_67e8, 0x8594_4171));
+    }
+
+    #[test]
+    fn seeds_depend_on_every_character() {
+        assert_eq!(seeds_from_text("digging"), seeds_from_text("digging"));
+        assert_ne!(seeds_from_text("digging"), seeds_from_text("Digging"));
+        assert_ne!(seeds_from_text("digging"), seeds_from_text("digging "));
+    }
+}
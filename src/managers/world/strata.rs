@@ -0,0 +1,252 @@
+use super::generation::WorldGenerator;
+use super::plates::PlateSample;
+use crate::components::BlockType;
+use crate::utils::{hash_coords, hash_to_unit};
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::LazyLock;
+
+const STRATA_PATH: &str = "data/worldgen/strata.json";
+const SALT_FAULT: u32 = 0x57A7_0001;
+
+// Offsets along the unused noise axis so each field is independent.
+const FOLD_Z: f64 = 59.9;
+const BEDDING_Z: f64 = 71.3;
+const ORE_Z: f64 = 87.7;
+
+pub static STRATA_CONFIG: LazyLock<StrataConfig> = LazyLock::new(StrataConfig::load);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FoldConfig {
+    pub noise_scale: f64,
+    pub amplitude_blocks: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FaultConfig {
+    /// Average horizontal distance between fault planes.
+    pub spacing_blocks: f32,
+    /// Horizontal shift of a fault plane per block of depth.
+    pub dip: f32,
+    pub max_throw_blocks: f32,
+    /// Plate stress or shear below this leaves the strata unbroken.
+    pub min_stress: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RockEntry {
+    pub block: u32,
+    pub weight: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OreEntry {
+    pub block: u32,
+    pub noise_scale: f64,
+    pub threshold: f64,
+    #[serde(default)]
+    pub min_depth: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StrataLayer {
+    pub key: String,
+    /// Thickness in blocks; `None` extends the layer down to bedrock.
+    pub thickness: Option<i32>,
+    pub hardness_multiplier: f64,
+    /// Additional hardness per block of depth inside this layer.
+    pub depth_hardness: f64,
+    pub rocks: Vec<RockEntry>,
+    #[serde(default)]
+    pub ores: Vec<OreEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StrataConfig {
+    pub fold: FoldConfig,
+    pub faults: FaultConfig,
+    pub bedding_noise_scale: f64,
+    pub layers: Vec<StrataLayer>,
+}
+
+impl StrataConfig {
+    fn load() -> Self {
+        fs::read_to_string(STRATA_PATH)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_else(Self::fallback)
+    }
+
+    /// Plain stone everywhere, used when the data file is missing or invalid.
+    fn fallback() -> Self {
+        Self {
+            fold: FoldConfig {
+                noise_scale: 0.0,
+                amplitude_blocks: 0.0,
+            },
+            faults: FaultConfig {
+                spacing_blocks: 1.0,
+                dip: 0.0,
+                max_throw_blocks: 0.0,
+                min_stress: 1.0,
+            },
+            bedding_noise_scale: 0.0,
+            layers: vec![StrataLayer {
+                key: "stone".to_string(),
+                thickness: None,
+                hardness_multiplier: 1.0,
+                depth_hardness: crate::constants::HARDNESS_DEPTH_MULTIPLIER,
+                rocks: vec![RockEntry {
+                    block: BlockType::STONE.to_id(),
+                    weight: 1.0,
+                }],
+                ores: Vec::new(),
+            }],
+        }
+    }
+}
+
+/// Result of a strata lookup for one block.
+#[derive(Clone, Copy, Debug)]
+pub struct StrataSample {
+    pub block_type: BlockType,
+    pub layer_index: usize,
+    /// Factor applied to the block's base hardness.
+    pub hardness_multiplier: f64,
+}
+
+/// How strongly the crust at a plate sample is faulted, in `[0, 1]`.
+#[must_use]
+pub fn fault_strength(plate: &PlateSample) -> f32 {
+    let faults = &STRATA_CONFIG.faults;
+    ((plate.geological_stress.abs().max(plate.shear) - faults.min_stress)
+        / (1.0 - faults.min_stress).max(f32::EPSILON))
+    .clamp(0.0, 1.0)
+}
+
+impl WorldGenerator {
+    /// Position across the fault planes, in fault spacings. Planes sit at whole numbers
+    /// and are inclined, so the value depends on depth too.
+    fn fault_coordinate(bx: i32, by: i32) -> f32 {
+        let faults = &STRATA_CONFIG.faults;
+        by.to_f32()
+            .unwrap_or(0.0)
+            .mul_add(faults.dip, bx.to_f32().unwrap_or(0.0))
+            / faults.spacing_blocks
+    }
+
+    /// Horizontal distance in blocks from `(bx, by)` to the nearest fault plane.
+    #[must_use]
+    pub fn fault_distance(bx: i32, by: i32) -> f32 {
+        let u = Self::fault_coordinate(bx, by);
+        (u - u.round()).abs() * STRATA_CONFIG.faults.spacing_blocks
+    }
+
+    /// Index of the fault block containing `(bx, by)`; it changes across every fault plane.
+    #[must_use]
+    pub fn fault_block(bx: i32, by: i32) -> i32 {
+        Self::fault_coordinate(bx, by).floor().to_i32().unwrap_or(0)
+    }
+
+    /// Vertical displacement of the fault block containing `(bx, by)` (TGIP Phase 7).
+    /// Only plate boundaries are faulted; interiors return 0.
+    fn fault_throw(&self, bx: i32, by: i32, plate: &PlateSample) -> f32 {
+        let strength = fault_strength(plate);
+        if strength <= 0.0 {
+            return 0.0;
+        }
+        let fault_block = Self::fault_block(bx, by);
+        let offset = hash_to_unit(hash_coords(self.seed ^ SALT_FAULT, fault_block, 0)) - 0.5;
+        offset * 2.0 * STRATA_CONFIG.faults.max_throw_blocks * strength
+    }
+
+    /// Picks the rock, ore and hardness for a block `depth` blocks below the local surface.
+    #[must_use]
+    pub fn strata_at(&self, bx: i32, by: i32, depth: i32, plate: &PlateSample) -> StrataSample {
+        let config = &*STRATA_CONFIG;
+        let x = f64::from(bx);
+        let y = f64::from(by);
+
+        let fold = self.noise_main.get([
+            x * config.fold.noise_scale,
+            y * config.fold.noise_scale * 0.5,
+            FOLD_Z,
+        ]);
+        let strat_depth = depth.to_f32().unwrap_or(0.0)
+            + fold.to_f32().unwrap_or(0.0) * config.fold.amplitude_blocks
+            + self.fault_throw(bx, by, plate);
+        let strat_depth = strat_depth.round().to_i32().unwrap_or(0).max(0);
+
+        let mut layer_index = config.layers.len().saturating_sub(1);
+        let mut layer_top = 0;
+        let mut top = 0;
+        for (i, layer) in config.layers.iter().enumerate() {
+            layer_top = top;
+            match layer.thickness {
+                Some(thickness) if strat_depth >= top + thickness => top += thickness,
+                _ => {
+                    layer_index = i;
+                    break;
+                }
+            }
+        }
+        let Some(layer) = config.layers.get(layer_index) else {
+            return StrataSample {
+                block_type: BlockType::Stone,
+                layer_index: 0,
+                hardness_multiplier: 1.0,
+            };
+        };
+
+        let mut block_type = Self::pick_rock(
+            &layer.rocks,
+            self.noise_main.get([
+                x * config.bedding_noise_scale,
+                f64::from(strat_depth) * config.bedding_noise_scale * 4.0,
+                BEDDING_Z,
+            ]),
+        );
+
+        for (i, ore) in layer.ores.iter().enumerate() {
+            if depth < ore.min_depth {
+                continue;
+            }
+            let ore_val = self.noise_ore.get([
+                x * ore.noise_scale,
+                y * ore.noise_scale,
+                ORE_Z + i.to_f64().unwrap_or(0.0) * 64.0,
+            ]);
+            if ore_val >= ore.threshold + self.settings.ore_richness.threshold_shift() {
+                block_type = BlockType::from_id(ore.block);
+                break;
+            }
+        }
+
+        let depth_in_layer = f64::from((strat_depth - layer_top).max(0));
+        StrataSample {
+            block_type,
+            layer_index,
+            hardness_multiplier: layer.hardness_multiplier
+                * depth_in_layer.mul_add(layer.depth_hardness, 1.0),
+        }
+    }
+
+    fn pick_rock(rocks: &[RockEntry], bedding: f64) -> BlockType {
+        let total: f32 = rocks.iter().map(|r| r.weight).sum();
+        let mut roll = ((bedding * 0.5 + 0.5).clamp(0.0, 0.999))
+            .to_f32()
+            .unwrap_or(0.0)
+            * total;
+        for rock in rocks {
+            if roll < rock.weight {
+                return BlockType::from_id(rock.block);
+            }
+            roll -= rock.weight;
+        }
+        rocks
+            .last()
+            .map_or(BlockType::Stone, |r| BlockType::from_id(r.block))
+    }
+}
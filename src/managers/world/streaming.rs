@@ -0,0 +1,127 @@
+use super::WorldManager;
+use crate::components::BlockPos;
+use crate::constants::{
+    BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, PLAYER_SYNC_MARGIN_BLOCKS,
+    PREFETCH_LOOKAHEAD_FRAMES, PREFETCH_MARGIN_CHUNKS, SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use macroquad::prelude::*;
+use num_traits::ToPrimitive;
+use std::collections::HashSet;
+
+/// Chunk coordinates covered by a world-space rectangle, as inclusive start and end.
+fn chunks_in_rect(rect: Rect) -> (BlockPos, BlockPos) {
+    let chunk_w = CHUNK_SIZE_X_BLOCKS.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
+    let chunk_h = CHUNK_SIZE_Y_BLOCKS.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
+    (
+        BlockPos::new(
+            (rect.x / chunk_w).floor().to_i32().unwrap_or(0),
+            (rect.y / chunk_h).floor().to_i32().unwrap_or(0),
+        ),
+        BlockPos::new(
+            ((rect.x + rect.w) / chunk_w).floor().to_i32().unwrap_or(0),
+            ((rect.y + rect.h) / chunk_h).floor().to_i32().unwrap_or(0),
+        ),
+    )
+}
+
+impl WorldManager {
+    /// Queues chunk `(cx, cy)` on the generation workers if it is not generated yet.
+    /// Loaded chunks count as accessed, which keeps them from being unloaded.
+    pub fn request_chunk(&mut self, cx: i32, cy: i32, is_urgent: bool) {
+        if self.is_chunk_generated(cx, cy) {
+            self.chunk_access
+                .insert(BlockPos::new(cx, cy), self.access_clock);
+        } else {
+            self.workers.request(BlockPos::new(cx, cy), is_urgent);
+        }
+    }
+
+    /// Whether chunk `(cx, cy)` is still being generated; it is drawn as a placeholder.
+    #[must_use]
+    pub fn is_chunk_pending(&self, cx: i32, cy: i32) -> bool {
+        self.workers.is_pending(BlockPos::new(cx, cy))
+    }
+
+    /// Installs the chunks the generation workers have finished.
+    pub fn receive_generated_chunks(&mut self) {
+        for chunk in self.workers.drain() {
+            self.install_chunk(chunk);
+        }
+    }
+
+    /// Starts a frame of chunk requests: advances the access clock, so chunks not asked
+    /// for again can be unloaded, and installs the chunks the workers have finished.
+    /// Called by the game while playing and by the map while it is open.
+    pub fn begin_frame(&mut self) {
+        self.access_clock += 1;
+        self.receive_generated_chunks();
+    }
+
+    /// Streams terrain for one frame. Chunks the player touches are generated at once so
+    /// collisions never miss; chunks in view are queued first and the area the player is
+    /// heading for, judged from `velocity`, is prefetched.
+    pub fn stream_chunks(
+        &mut self,
+        camera_x: f32,
+        camera_y: f32,
+        player_rect: Rect,
+        velocity: Vec2,
+    ) {
+        self.begin_frame();
+
+        let margin = PLAYER_SYNC_MARGIN_BLOCKS * BLOCK_SIZE;
+        let (start, end) = chunks_in_rect(Rect::new(
+            player_rect.x - margin,
+            player_rect.y - margin,
+            margin.mul_add(2.0, player_rect.w),
+            margin.mul_add(2.0, player_rect.h),
+        ));
+        for cx in start.x..=end.x {
+            for cy in start.y..=end.y {
+                self.ensure_chunk_exists_and_generated(cx, cy);
+            }
+        }
+
+        let mut wanted = HashSet::new();
+        let (start, end) =
+            chunks_in_rect(Rect::new(camera_x, camera_y, SCREEN_WIDTH, SCREEN_HEIGHT));
+        for cx in start.x..=end.x {
+            for cy in start.y..=end.y {
+                self.request_chunk(cx, cy, true);
+                self.visited_chunks.insert(BlockPos::new(cx, cy));
+                wanted.insert(BlockPos::new(cx, cy));
+            }
+        }
+
+        let ahead = velocity * PREFETCH_LOOKAHEAD_FRAMES;
+        let (start, end) = chunks_in_rect(Rect::new(
+            camera_x + ahead.x.min(0.0),
+            camera_y + ahead.y.min(0.0),
+            SCREEN_WIDTH + ahead.x.abs(),
+            SCREEN_HEIGHT + ahead.y.abs(),
+        ));
+        for cx in start.x - PREFETCH_MARGIN_CHUNKS..=end.x + PREFETCH_MARGIN_CHUNKS {
+            for cy in start.y - PREFETCH_MARGIN_CHUNKS..=end.y + PREFETCH_MARGIN_CHUNKS {
+                self.request_chunk(cx, cy, false);
+                wanted.insert(BlockPos::new(cx, cy));
+            }
+        }
+
+        // Prefetches for a direction the player has turned away from are dropped.
+        self.workers.cancel_unwanted(|pos| wanted.contains(&pos));
+
+        // The surface the HUD and renderer read is eroded off the game thread before the
+        // player gets there.
+        let chunk_w = CHUNK_SIZE_X_BLOCKS.to_i32().unwrap_or(1);
+        self.request_erosion(
+            (start.x - PREFETCH_MARGIN_CHUNKS) * chunk_w,
+            (end.x + PREFETCH_MARGIN_CHUNKS + 1) * chunk_w - 1,
+        );
+
+        let player_chunk = crate::utils::world_to_chunk_coords(
+            player_rect.x + player_rect.w / 2.0,
+            player_rect.y + player_rect.h / 2.0,
+        );
+        self.evict_chunks(player_chunk);
+    }
+}
@@ -0,0 +1,93 @@
+use super::WorldManager;
+use super::generation::WorldGenerator;
+use crate::constants::{
+    BLOCK_SIZE, CLIFF_STEP_BLOCKS, CLIFF_THRESHOLD, HILL_AMPLITUDE_BLOCKS, HILL_OCTAVES,
+    NOISE_SCALE_CLIFF, NOISE_SCALE_HILLS, NOISE_SCALE_VALLEY, PLAYER_INITIAL_X,
+    SPAWN_FLAT_BLEND_BLOCKS, SPAWN_FLAT_RADIUS_BLOCKS, SURFACE_Y_LEVEL, VALLEY_DEPTH_BLOCKS,
+};
+use noise::NoiseFn;
+use num_traits::ToPrimitive;
+
+// Offsets along the unused noise axis so each 1D profile is independent. Perlin repeats
+// every 256 units and is degenerate on half-integers, so offsets stay below 256 and off .5.
+const HILLS_Z: f64 = 11.3;
+const VALLEY_Z: f64 = 27.7;
+const CLIFF_Z: f64 = 43.1;
+
+impl WorldGenerator {
+    /// Block Y of the topmost ground block in a column after erosion has worn it down.
+    /// Smaller values are higher up, matching world coordinates.
+    #[must_use]
+    pub fn surface_height_at(&self, bx: i32) -> i32 {
+        self.uneroded_surface_height_at(bx) + self.erosion_at(bx).surface_offset()
+    }
+
+    /// Block Y of the ground as the relief leaves it, before erosion (TGIP Phase 2).
+    #[must_use]
+    pub fn uneroded_surface_height_at(&self, bx: i32) -> i32 {
+        let x = f64::from(bx);
+        let plate = self.plates.sample(bx, SURFACE_Y_LEVEL);
+
+        // Fractal hills, rougher near plate boundaries and calmer in plate interiors.
+        let mut hills = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = NOISE_SCALE_HILLS;
+        for _ in 0..HILL_OCTAVES {
+            hills += self.noise_main.get([x * frequency, 0.0, HILLS_Z]) * amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        let roughness = 0.5 + plate.geological_stress.abs().min(1.0);
+        let mut height = hills.to_f32().unwrap_or(0.0) * HILL_AMPLITUDE_BLOCKS * roughness;
+
+        // Valleys follow the zero crossings of a ridged noise.
+        let ridge = 1.0
+            - self
+                .noise_main
+                .get([x * NOISE_SCALE_VALLEY, 0.0, VALLEY_Z])
+                .abs();
+        height -= ridge.powi(6).to_f32().unwrap_or(0.0) * VALLEY_DEPTH_BLOCKS;
+
+        height += plate.uplift;
+
+        // Terraced stretches snap to steps, which leaves sheer cliff faces between them.
+        let cliff = self.noise_main.get([x * NOISE_SCALE_CLIFF, 0.0, CLIFF_Z]);
+        if cliff > CLIFF_THRESHOLD {
+            height = (height / CLIFF_STEP_BLOCKS).round() * CLIFF_STEP_BLOCKS;
+        }
+
+        let relief = height * spawn_flatten_factor(bx);
+        SURFACE_Y_LEVEL - relief.round().to_i32().unwrap_or(0)
+    }
+}
+
+impl WorldManager {
+    /// Block Y of the ground surface in block column `bx`. Until the workers have eroded
+    /// the column it is the height before erosion, so the game thread never runs the
+    /// erosion simulation itself.
+    #[must_use]
+    pub fn surface_height_at(&self, bx: i32) -> i32 {
+        if self.generator.is_eroded(bx) {
+            return self.generator.surface_height_at(bx);
+        }
+        self.request_erosion(bx, bx);
+        self.generator.uneroded_surface_height_at(bx)
+    }
+
+    /// Blocks between the ground surface and world position `(x, y)`, 0 above ground.
+    /// This is the depth the HUD shows.
+    #[must_use]
+    pub fn depth_below_surface(&self, x: f32, y: f32) -> i32 {
+        let column = (x / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
+        let row = (y / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
+        (row - self.surface_height_at(column)).max(0)
+    }
+}
+
+/// 0.0 at the home gate, rising smoothly to 1.0 so the spawn area stays flat.
+pub(super) fn spawn_flatten_factor(bx: i32) -> f32 {
+    let spawn_x = PLAYER_INITIAL_X / BLOCK_SIZE;
+    let dist = (bx.to_f32().unwrap_or(0.0) - spawn_x).abs() - SPAWN_FLAT_RADIUS_BLOCKS;
+    let t = (dist / SPAWN_FLAT_BLEND_BLOCKS).clamp(0.0, 1.0);
+    t * t * 2.0f32.mul_add(-t, 3.0)
+}
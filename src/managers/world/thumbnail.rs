@@ -0,0 +1,38 @@
+use super::WorldManager;
+use crate::constants::{THUMBNAIL_HEIGHT, THUMBNAIL_STEP_BLOCKS, THUMBNAIL_WIDTH};
+use num_traits::ToPrimitive;
+
+const UNLOADED_RGB: [u8; 3] = [10, 10, 10];
+const EMPTY_RGB: [u8; 3] = [20, 20, 40];
+
+impl WorldManager {
+    /// A tiny minimap centred on block `(center_bx, center_by)` for the save-select
+    /// screen: `THUMBNAIL_WIDTH` by `THUMBNAIL_HEIGHT` RGB pixels, row by row, in the
+    /// colours of the full map. Unloaded blocks come out dark.
+    #[must_use]
+    pub fn thumbnail(&self, center_bx: i32, center_by: i32) -> Vec<u8> {
+        let half_w = THUMBNAIL_WIDTH.to_i32().unwrap_or(0) / 2;
+        let half_h = THUMBNAIL_HEIGHT.to_i32().unwrap_or(0) / 2;
+        let mut pixels = Vec::with_capacity(THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT * 3);
+        for py in -half_h..half_h {
+            for px in -half_w..half_w {
+                let rgb = self
+                    .get_block(
+                        center_bx + px * THUMBNAIL_STEP_BLOCKS,
+                        center_by + py * THUMBNAIL_STEP_BLOCKS,
+                    )
+                    .map_or(UNLOADED_RGB, |block| {
+                        if block.is_broken() {
+                            EMPTY_RGB
+                        } else {
+                            let color = block.block_type.get_map_color();
+                            [color.r, color.g, color.b]
+                                .map(|c| (c * 255.0).round().to_u8().unwrap_or(0))
+                        }
+                    });
+                pixels.extend_from_slice(&rgb);
+            }
+        }
+        pixels
+    }
+}
@@ -2,7 +2,6 @@ use super::WorldManager;
 use crate::components::{Block, BlockType};
 use crate::constants::{
     BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, SCREEN_HEIGHT, SCREEN_WIDTH,
-    SURFACE_Y_LEVEL,
 };
 use crate::utils::chunk_to_macrogrid_coords;
 use macroquad::prelude::*;
@@ -36,8 +35,7 @@ impl WorldManager {
         }
     }
 
-    pub fn get_active_blocks_in_view(&mut self, camera_x: f32, camera_y: f32) -> Vec<&Block> {
-        let mut blocks = Vec::new();
+    pub fn get_active_blocks_in_view(&mut self, camera_x: f32, camera_y: f32) -> Vec<Block> {
         let view_rect = Rect::new(
             camera_x - BLOCK_SIZE,
             camera_y - BLOCK_SIZE,
@@ -64,48 +62,17 @@ impl WorldManager {
             .to_i32()
             .unwrap_or(0);
 
+        // Missing chunks are generated off-thread and show up on a later frame.
         for cx in start_cx..=end_cx {
             for cy in start_cy..=end_cy {
-                self.ensure_chunk_exists_and_generated(cx, cy);
+                self.request_chunk(cx, cy, true);
             }
         }
 
-        for cx in start_cx..=end_cx {
-            for cy in start_cy..=end_cy {
-                let (mg_coords, rel_coords) = chunk_to_macrogrid_coords(cx, cy);
-                if let Some(macrogrid) = self.macrogrids.get(&(mg_coords))
-                    && let Some(chunk) = macrogrid.chunks.get(&rel_coords)
-                    && chunk.is_generated
-                {
-                    for row in &chunk.blocks {
-                        for block in row {
-                            // Include block if:
-                            // 1. It's not broken
-                            // 2. It has a background to show (back_type != Air)
-                            // 3. It's underground (so we can draw black background)
-                            let is_underground =
-                                block.y >= SURFACE_Y_LEVEL.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
-                            let should_render = (!block.is_broken)
-                                || (block.back_type != BlockType::Air)
-                                || is_underground;
-
-                            if should_render
-                                && block.x + BLOCK_SIZE > view_rect.x
-                                && block.x < view_rect.x + view_rect.w
-                                && block.y + BLOCK_SIZE > view_rect.y
-                                && block.y < view_rect.y + view_rect.h
-                            {
-                                blocks.push(block);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        blocks
+        self.get_active_blocks_in_view_immutable(camera_x, camera_y)
     }
 
-    pub fn get_active_blocks_in_view_immutable(&self, camera_x: f32, camera_y: f32) -> Vec<&Block> {
+    pub fn get_active_blocks_in_view_immutable(&self, camera_x: f32, camera_y: f32) -> Vec<Block> {
         let mut blocks = Vec::new();
         let view_rect = Rect::new(
             camera_x - BLOCK_SIZE,
@@ -140,26 +107,32 @@ impl WorldManager {
                     && let Some(chunk) = macrogrid.chunks.get(&rel_coords)
                     && chunk.is_generated
                 {
-                    for row in &chunk.blocks {
-                        for block in row {
-                            // Include block if:
-                            // 1. It's not broken
-                            // 2. It has a background to show (back_type != Air)
-                            // 3. It's underground (so we can draw black background)
-                            let is_underground =
-                                block.y >= SURFACE_Y_LEVEL.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
-                            let should_render = (!block.is_broken)
-                                || (block.back_type != BlockType::Air)
-                                || is_underground;
+                    let mut column = None;
+                    let mut surface_world_y = 0.0;
+                    for block in chunk.blocks() {
+                        // Blocks come column by column, so the surface is looked up once per column.
+                        let bx = (block.x / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
+                        if column != Some(bx) {
+                            column = Some(bx);
+                            surface_world_y =
+                                self.surface_height_at(bx).to_f32().unwrap_or(0.0) * BLOCK_SIZE;
+                        }
+                        // Include block if:
+                        // 1. It's not broken
+                        // 2. It has a background to show (back_type != Air)
+                        // 3. It's underground (so we can draw black background)
+                        let is_underground = block.y >= surface_world_y;
+                        let should_render = (!block.is_broken())
+                            || (block.back_type != BlockType::Air)
+                            || is_underground;
 
-                            if should_render
-                                && block.x + BLOCK_SIZE > view_rect.x
-                                && block.x < view_rect.x + view_rect.w
-                                && block.y + BLOCK_SIZE > view_rect.y
-                                && block.y < view_rect.y + view_rect.h
-                            {
-                                blocks.push(block);
-                            }
+                        if should_render
+                            && block.x + BLOCK_SIZE > view_rect.x
+                            && block.x < view_rect.x + view_rect.w
+                            && block.y + BLOCK_SIZE > view_rect.y
+                            && block.y < view_rect.y + view_rect.h
+                        {
+                            blocks.push(block);
                         }
                     }
                 }
@@ -0,0 +1,171 @@
+use super::generation::{self, WorldGenerator};
+use crate::components::{BlockPos, Chunk};
+use crate::constants::GENERATION_WORKERS_MAX;
+use std::collections::{HashSet, VecDeque};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread;
+
+#[derive(Default)]
+struct JobQueue {
+    jobs: VecDeque<BlockPos>,
+    /// Macrogrid columns to erode, taken before any chunk.
+    erosion: VecDeque<i32>,
+    /// Columns queued for erosion or being eroded, so none is asked for twice.
+    eroding: HashSet<i32>,
+    is_closed: bool,
+}
+
+enum Job {
+    Chunk(BlockPos),
+    Erosion(i32),
+}
+
+/// Generates chunk blocks and erodes macrogrid columns on background threads so new
+/// terrain never stalls a frame. Erosion comes first, starting with the land around the
+/// home gate; among chunks, urgent requests jump ahead of prefetches.
+pub struct GenerationWorkers {
+    queue: Arc<(Mutex<JobQueue>, Condvar)>,
+    results: Receiver<Chunk>,
+    pending: HashSet<BlockPos>,
+}
+
+impl GenerationWorkers {
+    #[must_use]
+    pub fn new(generator: &Arc<WorldGenerator>) -> Self {
+        let spawn_columns = WorldGenerator::spawn_erosion_columns();
+        let queue = Arc::new((
+            Mutex::new(JobQueue {
+                erosion: spawn_columns.clone().collect(),
+                eroding: spawn_columns.collect(),
+                ..JobQueue::default()
+            }),
+            Condvar::new(),
+        ));
+        let (sender, results) = mpsc::channel();
+
+        let count = thread::available_parallelism()
+            .map_or(1, |n| n.get().saturating_sub(1))
+            .clamp(1, GENERATION_WORKERS_MAX);
+        for _ in 0..count {
+            let queue = Arc::clone(&queue);
+            let generator = Arc::clone(generator);
+            let sender = sender.clone();
+            thread::spawn(move || run_worker(&queue, &generator, &sender));
+        }
+
+        Self {
+            queue,
+            results,
+            pending: HashSet::new(),
+        }
+    }
+
+    /// Queues `pos` for generation unless it is already on its way.
+    pub fn request(&mut self, pos: BlockPos, is_urgent: bool) {
+        let (lock, ready) = &*self.queue;
+        let mut queue = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.pending.insert(pos) {
+            if is_urgent {
+                queue.jobs.push_front(pos);
+            } else {
+                queue.jobs.push_back(pos);
+            }
+            ready.notify_one();
+        } else if is_urgent && let Some(index) = queue.jobs.iter().position(|&p| p == pos) {
+            // A prefetch that is now on screen moves to the front.
+            queue.jobs.remove(index);
+            queue.jobs.push_front(pos);
+        }
+    }
+
+    /// Queues macrogrid column `mx` for erosion unless it is already on its way.
+    pub fn request_erosion(&self, mx: i32) {
+        let (lock, ready) = &*self.queue;
+        let mut queue = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        if queue.eroding.insert(mx) {
+            queue.erosion.push_back(mx);
+            ready.notify_one();
+        }
+    }
+
+    #[must_use]
+    pub fn is_pending(&self, pos: BlockPos) -> bool {
+        self.pending.contains(&pos)
+    }
+
+    /// Drops queued jobs that `keep` no longer wants. Jobs already being generated finish.
+    pub fn cancel_unwanted(&mut self, keep: impl Fn(BlockPos) -> bool) {
+        let (lock, _) = &*self.queue;
+        let mut queue = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let pending = &mut self.pending;
+        queue.jobs.retain(|&pos| {
+            let is_wanted = keep(pos);
+            if !is_wanted {
+                pending.remove(&pos);
+            }
+            is_wanted
+        });
+    }
+
+    /// Chunks finished since the last call.
+    pub fn drain(&mut self) -> Vec<Chunk> {
+        let finished: Vec<Chunk> = self.results.try_iter().collect();
+        for chunk in &finished {
+            self.pending.remove(&BlockPos::new(chunk.cx, chunk.cy));
+        }
+        finished
+    }
+}
+
+impl Drop for GenerationWorkers {
+    fn drop(&mut self) {
+        let (lock, ready) = &*self.queue;
+        lock.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_closed = true;
+        ready.notify_all();
+    }
+}
+
+fn run_worker(
+    queue: &(Mutex<JobQueue>, Condvar),
+    generator: &WorldGenerator,
+    sender: &Sender<Chunk>,
+) {
+    let (lock, ready) = queue;
+    loop {
+        let job = {
+            let mut queue = lock.lock().unwrap_or_else(PoisonError::into_inner);
+            loop {
+                if queue.is_closed {
+                    return;
+                }
+                if let Some(mx) = queue.erosion.pop_front() {
+                    break Job::Erosion(mx);
+                }
+                if let Some(pos) = queue.jobs.pop_front() {
+                    break Job::Chunk(pos);
+                }
+                queue = ready.wait(queue).unwrap_or_else(PoisonError::into_inner);
+            }
+        };
+        match job {
+            Job::Erosion(mx) => {
+                generator.prepare_erosion(mx);
+                lock.lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .eroding
+                    .remove(&mx);
+            }
+            Job::Chunk(pos) => {
+                if sender
+                    .send(generation::generate_chunk(pos.x, pos.y, generator))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    }
+}
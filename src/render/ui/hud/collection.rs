@@ -0,0 +1,81 @@
+use crate::Game;
+use crate::components::BlockType;
+use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::managers::collection::COLLECTION_SETS;
+use crate::render::ui::common::MenuRenderContext;
+use macroquad::prelude::*;
+
+pub fn draw_collection(game: &Game, ctx: &mut MenuRenderContext) {
+    let (mw, mh) = (130.0 * ctx.scale, (SCREEN_HEIGHT - 20.0) * ctx.scale);
+    let (mx, my) = (
+        ctx.offset_x + ((SCREEN_WIDTH - 130.0) / 2.0).floor() * ctx.scale,
+        ctx.offset_y + 10.0 * ctx.scale,
+    );
+    draw_rectangle(mx, my, mw, mh, Color::new(0.2, 0.2, 0.2, 0.9));
+    draw_rectangle_lines(mx, my, mw, mh, 1.0, WHITE);
+
+    let mini_font_size = (6.0 * ctx.scale) as u16;
+    let mut cur_y = (my + 10.0 * ctx.scale).floor();
+
+    draw_text_ex(
+        &game.lang_manager.get_string("collection.title"),
+        mx + 10.0 * ctx.scale,
+        cur_y,
+        TextParams {
+            font_size: ctx.font_size,
+            font: ctx.font,
+            color: YELLOW,
+            ..Default::default()
+        },
+    );
+    cur_y += 12.0 * ctx.scale;
+
+    let collection = &game.player_manager.player.collection;
+    for set in COLLECTION_SETS.iter() {
+        let is_complete = set.is_complete(collection);
+        draw_text_ex(
+            &format!(
+                "{} {}/{} (+${})",
+                game.lang_manager
+                    .get_string(&format!("collection.{}.name", set.key)),
+                set.found_count(collection),
+                set.items.len(),
+                set.bonus
+            ),
+            mx + 10.0 * ctx.scale,
+            cur_y,
+            TextParams {
+                font_size: mini_font_size,
+                font: ctx.font,
+                color: if is_complete { GOLD } else { SKYBLUE },
+                ..Default::default()
+            },
+        );
+        cur_y += 9.0 * ctx.scale;
+
+        for &id in &set.items {
+            // Finds stay hidden until the player has dug one up.
+            let (label, color) = match BlockType::from_id(id).get_data() {
+                Some(data) if collection.contains(&id) => (
+                    game.lang_manager
+                        .get_string(&format!("block.{}.name", data.key)),
+                    WHITE,
+                ),
+                _ => (game.lang_manager.get_string("collection.unknown"), GRAY),
+            };
+            draw_text_ex(
+                &label,
+                mx + 16.0 * ctx.scale,
+                cur_y,
+                TextParams {
+                    font_size: mini_font_size,
+                    font: ctx.font,
+                    color,
+                    ..Default::default()
+                },
+            );
+            cur_y += 8.0 * ctx.scale;
+        }
+        cur_y += 4.0 * ctx.scale;
+    }
+}
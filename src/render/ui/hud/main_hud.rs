@@ -1,12 +1,10 @@
 use crate::Game;
 use crate::components::{BlockPos, ChunkRelPos};
-use crate::constants::{
-    BLOCK_SIZE, SCREEN_HEIGHT, SCREEN_WIDTH, SURFACE_Y_LEVEL, TEMPERATURE_DEBUFF_THRESHOLD,
-};
+use crate::constants::{BLOCK_SIZE, SCREEN_HEIGHT, SCREEN_WIDTH, TEMPERATURE_DEBUFF_THRESHOLD};
 use crate::events::GameEvent;
 use crate::game::UIOverlay;
 use crate::render::ui::common::{ButtonParams, MenuRenderContext, draw_button};
-use crate::utils::{get_temperature, world_to_chunk_coords, world_to_relative_in_chunk_coords};
+use crate::utils::{world_to_chunk_coords, world_to_relative_in_chunk_coords};
 use macroquad::prelude::*;
 use num_traits::ToPrimitive;
 
@@ -65,13 +63,15 @@ pub fn draw_hud(game: &Game, ctx: &mut MenuRenderContext) {
         },
     );
 
-    let depth = (player.y / BLOCK_SIZE).floor().to_i32().unwrap_or(0) - SURFACE_Y_LEVEL;
+    let column = ((player.x + player.width / 2.0) / BLOCK_SIZE)
+        .floor()
+        .to_i32()
+        .unwrap_or(0);
+    let depth = game
+        .world_manager
+        .depth_below_surface(player.x + player.width / 2.0, player.y);
     draw_text_ex(
-        &format!(
-            "{}: {}m",
-            game.lang_manager.get_string("hud.depth"),
-            depth.max(0)
-        ),
+        &format!("{}: {}m", game.lang_manager.get_string("hud.depth"), depth),
         ctx.offset_x + (SCREEN_WIDTH - 45.0) * ctx.scale,
         hud_y + 12.0 * ctx.scale,
         TextParams {
@@ -82,7 +82,10 @@ pub fn draw_hud(game: &Game, ctx: &mut MenuRenderContext) {
         },
     );
 
-    let temp = get_temperature(player.y);
+    let temp = game.world_manager.temperature_at(
+        column,
+        (player.y / BLOCK_SIZE).floor().to_i32().unwrap_or(0),
+    );
     let temp_color = if temp >= TEMPERATURE_DEBUFF_THRESHOLD {
         RED
     } else {
@@ -109,10 +112,8 @@ pub fn draw_hud(game: &Game, ctx: &mut MenuRenderContext) {
             .filter(|c| c.is_generated)
         {
             let ChunkRelPos { x: rx, y: ry } = world_to_relative_in_chunk_coords(bx, by);
-            if rx < chunk.blocks.len() && ry < chunk.blocks[0].len() {
-                let block = &chunk.blocks[rx][ry];
-                let block_name = block
-                    .block_type
+            if let Some(block_type) = chunk.block_type(rx, ry) {
+                let block_name = block_type
                     .get_data()
                     .map(|d| {
                         game.lang_manager
@@ -179,7 +180,7 @@ pub fn draw_hud(game: &Game, ctx: &mut MenuRenderContext) {
                     atlas_tex,
                     sel_x + 2.0 * ctx.scale,
                     sel_y + 2.0 * ctx.scale,
-                    WHITE,
+                    crate::utils::get_item_tint(&item.item_type),
                     DrawTextureParams {
                         source: Some(sprite_rect),
                         dest_size: Some(vec2(8.0 * ctx.scale, 8.0 * ctx.scale)),
@@ -1,8 +1,10 @@
+pub mod collection;
 pub mod inventory;
 pub mod main_hud;
 pub mod shop;
 pub mod warehouse;
 
+pub use collection::draw_collection;
 pub use inventory::draw_inventory;
 pub use main_hud::draw_hud;
 pub use shop::draw_shop;
@@ -1,5 +1,5 @@
 use crate::Game;
-use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::constants::{PUMP_COST, SCREEN_HEIGHT, SCREEN_WIDTH};
 use crate::events::GameEvent;
 use crate::render::ui::common::{ButtonParams, MenuRenderContext, draw_button};
 use macroquad::prelude::*;
@@ -161,13 +161,15 @@ pub fn draw_shop(game: &Game, ctx: &mut MenuRenderContext) {
     }
     cur_y += 12.0 * ctx.scale;
 
+    // Items to buy share the last row.
+    let half_w = (mw - 15.0 * ctx.scale) / 2.0;
     let wg_name = game.lang_manager.get_string("shop.buy.warpgate");
     let wg_label = format!("{wg_name} ($500)");
     if draw_button(
         ButtonParams {
             x: mx + 5.0 * ctx.scale,
             y: cur_y,
-            w: mw - 10.0 * ctx.scale,
+            w: half_w,
             h: 10.0 * ctx.scale,
             text_key: &wg_label,
             press_key: &purchase_label,
@@ -178,4 +180,22 @@ pub fn draw_shop(game: &Game, ctx: &mut MenuRenderContext) {
     ) {
         ctx.events.push(GameEvent::BuyWarpGate);
     }
+
+    let pump_name = game.lang_manager.get_string("shop.buy.pump");
+    let pump_label = format!("{pump_name} (${PUMP_COST})");
+    if draw_button(
+        ButtonParams {
+            x: mx + 10.0 * ctx.scale + half_w,
+            y: cur_y,
+            w: half_w,
+            h: 10.0 * ctx.scale,
+            text_key: &pump_label,
+            press_key: &purchase_label,
+            lang: &game.lang_manager,
+            font_size: ctx.font_size,
+        },
+        ctx.font,
+    ) {
+        ctx.events.push(GameEvent::BuyPump);
+    }
 }
@@ -50,6 +50,7 @@ impl UIRenderer {
                         UIOverlay::Inventory => hud::draw_inventory(game, &mut ctx),
                         UIOverlay::Warehouse => hud::draw_warehouse(game, &mut ctx),
                         UIOverlay::Map => screens::draw_map_screen(game, &mut ctx),
+                        UIOverlay::Collection => hud::draw_collection(game, &mut ctx),
                         UIOverlay::PauseMenu => screens::draw_pause_menu(game, &mut ctx),
                         UIOverlay::None => {}
                     }
@@ -6,6 +6,7 @@ use crate::constants::{
 use crate::render::ui::common::MenuRenderContext;
 use macroquad::prelude::*;
 use num_traits::ToPrimitive;
+use std::collections::HashSet;
 
 pub fn draw_terrain(
     game: &mut Game,
@@ -14,7 +15,8 @@ pub fn draw_terrain(
     view_y: f32,
     blocks_per_pixel: f32,
 ) {
-    // 1. Pre-load/ensure visited chunks in view
+    // 1. Queue visited chunks in view; ones still generating are drawn as placeholders
+    game.world_manager.begin_frame();
     let half_w_blocks = (SCREEN_WIDTH / 2.0) * blocks_per_pixel;
     let half_h_blocks = (SCREEN_HEIGHT / 2.0) * blocks_per_pixel;
 
@@ -56,22 +58,32 @@ pub fn draw_terrain(
     .to_i32()
     .unwrap_or(0);
 
+    // Unexplored chunks bordering explored ones hint at the aquifers they hold.
+    let mut aquifer_hints = HashSet::new();
     for cx in start_cx..=end_cx {
         for cy in start_cy..=end_cy {
-            if game
-                .world_manager
-                .visited_chunks
-                .contains(&BlockPos::new(cx, cy))
+            let visited = &game.world_manager.visited_chunks;
+            if visited.contains(&BlockPos::new(cx, cy)) {
+                game.world_manager.request_chunk(cx, cy, true);
+            } else if [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .iter()
+                .any(|&(dx, dy)| visited.contains(&BlockPos::new(cx + dx, cy + dy)))
+                && game.world_manager.chunk_has_aquifer(cx, cy)
             {
-                game.world_manager.ensure_chunk_exists_and_generated(cx, cy);
+                aquifer_hints.insert(BlockPos::new(cx, cy));
             }
         }
     }
 
+    // Browsing far afield unloads chunks the map and player have left behind.
+    game.world_manager
+        .evict_chunks(crate::utils::world_to_chunk_coords(view_x, view_y));
+
     // 2. Render pixels
     let mut last_cx = i32::MAX;
     let mut last_cy = i32::MAX;
     let mut last_visited = false;
+    let mut last_hinted = false;
     let mut last_chunk: Option<&crate::components::Chunk> = None;
 
     for py_idx in 0..SCREEN_HEIGHT.to_i32().unwrap_or(0) {
@@ -96,8 +108,11 @@ pub fn draw_terrain(
                     .world_manager
                     .visited_chunks
                     .contains(&BlockPos::new(cx, cy));
+                last_hinted = aquifer_hints.contains(&BlockPos::new(cx, cy));
                 last_chunk = if last_visited {
-                    game.world_manager.get_chunk(cx, cy)
+                    game.world_manager
+                        .get_chunk(cx, cy)
+                        .filter(|chunk| chunk.is_generated)
                 } else {
                     None
                 };
@@ -110,18 +125,17 @@ pub fn draw_terrain(
                     |chunk| {
                         let ChunkRelPos { x: rel_x, y: rel_y } =
                             crate::utils::world_to_relative_in_chunk_coords(world_x, world_y);
-                        if rel_x < CHUNK_SIZE_X_BLOCKS && rel_y < CHUNK_SIZE_Y_BLOCKS {
-                            let block = &chunk.blocks[rel_x][rel_y];
-                            if block.is_broken {
+                        chunk.get_block(rel_x, rel_y).map_or(BLACK, |block| {
+                            if block.is_broken() {
                                 Color::from_rgba(20, 20, 40, 255)
                             } else {
                                 block.block_type.get_map_color()
                             }
-                        } else {
-                            BLACK
-                        }
+                        })
                     },
                 )
+            } else if last_hinted {
+                Color::from_rgba(15, 30, 60, 255) // Damp ground under the fog
             } else {
                 BLACK // Fog of war
             };
@@ -1,37 +1,122 @@
 use crate::Game;
 use crate::constants::SCREEN_WIDTH;
 use crate::events::GameEvent;
+use crate::managers::world::settings::WorldOption;
 use crate::render::ui::common::{ButtonParams, MenuRenderContext, draw_button};
 use macroquad::prelude::*;
 
+const FIELD_X: f32 = 10.0;
+const FIELD_H: f32 = 10.0;
+const OPTION_TOP: f32 = 48.0;
+const OPTION_STEP: f32 = 11.0;
+const OPTION_BUTTON_X: f32 = 90.0;
+
 pub fn draw_new_game_input_screen(game: &Game, ctx: &mut MenuRenderContext) {
-    draw_text_ex(
+    let mini_font_size = (6.0 * ctx.scale) as u16;
+
+    draw_label(
+        ctx,
         &game.lang_manager.get_string("menu.enter_filename"),
-        ctx.offset_x + 10.0 * ctx.scale,
-        ctx.offset_y + 30.0 * ctx.scale,
-        TextParams {
+        8.0,
+        mini_font_size,
+    );
+    if draw_text_field(ctx, 11.0, &game.input_buffer, !game.is_editing_seed) {
+        ctx.events.push(GameEvent::FocusSeedInput(false));
+    }
+
+    draw_label(
+        ctx,
+        &game.lang_manager.get_string("settings.seed"),
+        29.0,
+        mini_font_size,
+    );
+    if draw_text_field(ctx, 32.0, &game.seed_input, game.is_editing_seed) {
+        ctx.events.push(GameEvent::FocusSeedInput(true));
+    }
+
+    let mut row_y = OPTION_TOP;
+    for option in WorldOption::ALL {
+        draw_label(
+            ctx,
+            &game.lang_manager.get_string(option.lang_key()),
+            row_y + 6.5,
+            mini_font_size,
+        );
+        let value = game.new_world_settings.value_label(option);
+        if draw_button(
+            ButtonParams {
+                x: ctx.offset_x + OPTION_BUTTON_X * ctx.scale,
+                y: ctx.offset_y + row_y * ctx.scale,
+                w: (SCREEN_WIDTH - OPTION_BUTTON_X - FIELD_X) * ctx.scale,
+                h: 9.0 * ctx.scale,
+                text_key: &value,
+                press_key: &value,
+                lang: &game.lang_manager,
+                font_size: mini_font_size,
+            },
+            ctx.font,
+        ) {
+            ctx.events.push(GameEvent::CycleWorldOption(option));
+        }
+        row_y += OPTION_STEP;
+    }
+
+    if draw_button(
+        ButtonParams {
+            x: ctx.offset_x + FIELD_X * ctx.scale,
+            y: ctx.offset_y + 106.0 * ctx.scale,
+            w: 60.0 * ctx.scale,
+            h: 10.0 * ctx.scale,
+            text_key: "button.confirm",
+            press_key: "button.confirm",
+            lang: &game.lang_manager,
             font_size: ctx.font_size,
+        },
+        ctx.font,
+    ) {
+        ctx.events
+            .push(GameEvent::ConfirmNewGame(game.input_buffer.clone()));
+    }
+}
+
+fn draw_label(ctx: &MenuRenderContext, text: &str, y: f32, font_size: u16) {
+    draw_text_ex(
+        text,
+        ctx.offset_x + FIELD_X * ctx.scale,
+        ctx.offset_y + y * ctx.scale,
+        TextParams {
+            font_size,
             font: ctx.font,
             color: WHITE,
             ..Default::default()
         },
     );
-    draw_rectangle(
-        ctx.offset_x + 10.0 * ctx.scale,
-        ctx.offset_y + 40.0 * ctx.scale,
-        (SCREEN_WIDTH - 20.0) * ctx.scale,
-        12.0 * ctx.scale,
-        DARKGRAY,
+}
+
+/// Draws a one-line text box at virtual `y`; returns whether it was clicked.
+fn draw_text_field(ctx: &MenuRenderContext, y: f32, text: &str, is_focused: bool) -> bool {
+    let (x, y) = (
+        ctx.offset_x + FIELD_X * ctx.scale,
+        ctx.offset_y + y * ctx.scale,
+    );
+    let (w, h) = (
+        (SCREEN_WIDTH - 2.0 * FIELD_X) * ctx.scale,
+        FIELD_H * ctx.scale,
     );
-    let cur = if (get_time() * 2.0) as i32 % 2 == 0 {
+    draw_rectangle(x, y, w, h, DARKGRAY);
+    if is_focused {
+        draw_rectangle_lines(x, y, w, h, 1.0, WHITE);
+    }
+
+    let cur = if is_focused && (get_time() * 2.0) as i32 % 2 == 0 {
         "|"
     } else {
         ""
     };
     draw_text_ex(
-        &format!("{}{}", game.input_buffer, cur),
-        ctx.offset_x + 12.0 * ctx.scale,
-        ctx.offset_y + 49.0 * ctx.scale,
+        &format!("{text}{cur}"),
+        x + 2.0 * ctx.scale,
+        y + 8.0 * ctx.scale,
         TextParams {
             font_size: ctx.font_size,
             font: ctx.font,
@@ -39,20 +124,7 @@ pub fn draw_new_game_input_screen(game: &Game, ctx: &mut MenuRenderContext) {
             ..Default::default()
         },
     );
-    if draw_button(
-        ButtonParams {
-            x: ctx.offset_x + 10.0 * ctx.scale,
-            y: ctx.offset_y + 60.0 * ctx.scale,
-            w: 60.0 * ctx.scale,
-            h: 10.0 * ctx.scale,
-            text_key: "button.confirm",
-            press_key: "button.confirm",
-            lang: &game.lang_manager,
-            font_size: ctx.font_size,
-        },
-        ctx.font,
-    ) {
-        ctx.events
-            .push(GameEvent::ConfirmNewGame(game.input_buffer.clone()));
-    }
+
+    let (mx, my) = mouse_position();
+    is_mouse_button_released(MouseButton::Left) && mx >= x && mx < x + w && my >= y && my < y + h
 }
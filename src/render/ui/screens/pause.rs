@@ -5,10 +5,10 @@ use crate::render::ui::common::{ButtonParams, MenuRenderContext, draw_button};
 use macroquad::prelude::*;
 
 pub fn draw_pause_menu(game: &Game, ctx: &mut MenuRenderContext) {
-    let (mw, mh) = (80.0 * ctx.scale, 75.0 * ctx.scale);
+    let (mw, mh) = (80.0 * ctx.scale, 87.0 * ctx.scale);
     let (mx, my) = (
         ctx.offset_x + ((SCREEN_WIDTH - 80.0) / 2.0).floor() * ctx.scale,
-        ctx.offset_y + ((SCREEN_HEIGHT - 75.0) / 2.0).floor() * ctx.scale,
+        ctx.offset_y + ((SCREEN_HEIGHT - 87.0) / 2.0).floor() * ctx.scale,
     );
     draw_rectangle(mx, my, mw, mh, LIGHTGRAY);
     draw_rectangle_lines(mx, my, mw, mh, 1.0, BLACK);
@@ -46,6 +46,35 @@ pub fn draw_pause_menu(game: &Game, ctx: &mut MenuRenderContext) {
         ctx.events.push(GameEvent::SaveGame);
     }
     cur_y += 12.0 * ctx.scale;
+    let autosave = if game.autosave_minutes > 0 {
+        format!(
+            "{} {}m",
+            game.lang_manager.get_string("menu.autosave"),
+            game.autosave_minutes
+        )
+    } else {
+        format!(
+            "{} {}",
+            game.lang_manager.get_string("menu.autosave"),
+            game.lang_manager.get_string("menu.autosave.off")
+        )
+    };
+    if draw_button(
+        ButtonParams {
+            x: mx + 5.0 * ctx.scale,
+            y: cur_y,
+            w: mw - 10.0 * ctx.scale,
+            h: 10.0 * ctx.scale,
+            text_key: &autosave,
+            press_key: &autosave,
+            lang: &game.lang_manager,
+            font_size: ctx.font_size,
+        },
+        ctx.font,
+    ) {
+        ctx.events.push(GameEvent::CycleAutosave);
+    }
+    cur_y += 12.0 * ctx.scale;
     if draw_button(
         ButtonParams {
             x: mx + 5.0 * ctx.scale,
@@ -1,14 +1,27 @@
 use crate::Game;
-use crate::constants::SCREEN_WIDTH;
+use crate::constants::{SCREEN_HEIGHT, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH};
 use crate::events::GameEvent;
+use crate::managers::persistence::SaveSlot;
 use crate::render::ui::common::{ButtonParams, MenuRenderContext, draw_button};
 use macroquad::prelude::*;
+use num_traits::ToPrimitive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const LIST_X: f32 = 5.0;
+const LIST_W: f32 = 75.0;
+const LIST_TOP: f32 = 38.0;
+const ROW_STEP: f32 = 11.0;
+const PANEL_X: f32 = 84.0;
+const PANEL_W: f32 = 71.0;
+const PANEL_BUTTON_W: f32 = 34.0;
 
 pub fn draw_save_select_screen(game: &Game, ctx: &mut MenuRenderContext) {
+    let mini_font_size = (6.0 * ctx.scale) as u16;
+
     draw_text_ex(
         &game.lang_manager.get_string("menu.select_save"),
-        ctx.offset_x + 10.0 * ctx.scale,
-        ctx.offset_y + 20.0 * ctx.scale,
+        ctx.offset_x + 36.0 * ctx.scale,
+        ctx.offset_y + 10.0 * ctx.scale,
         TextParams {
             font_size: ctx.font_size,
             font: ctx.font,
@@ -16,54 +29,357 @@ pub fn draw_save_select_screen(game: &Game, ctx: &mut MenuRenderContext) {
             ..Default::default()
         },
     );
-    let mut cy = ctx.offset_y + 35.0 * ctx.scale;
-    if draw_button(
-        ButtonParams {
-            x: ctx.offset_x + 10.0 * ctx.scale,
-            y: cy,
-            w: (SCREEN_WIDTH - 20.0) * ctx.scale,
-            h: 10.0 * ctx.scale,
-            text_key: "button.menu.new_game.default",
-            press_key: "button.menu.new_game.pressed",
-            lang: &game.lang_manager,
-            font_size: ctx.font_size,
-        },
-        ctx.font,
+    if button(
+        game,
+        ctx,
+        (2.0, 2.0, 30.0),
+        "button.menu.return",
+        ctx.font_size,
+    ) {
+        ctx.events.push(GameEvent::ReturnToTitleFromSaveSelect);
+    }
+    if button(
+        game,
+        ctx,
+        (LIST_X, 22.0, LIST_W),
+        "button.menu.new_game",
+        ctx.font_size,
     ) {
         ctx.events.push(GameEvent::StartNewGameSetup);
     }
+    let sort = format!(
+        "{} {}",
+        game.lang_manager.get_string("save_select.sort"),
+        game.lang_manager.get_string(game.save_sort.lang_key())
+    );
     if draw_button(
         ButtonParams {
-            x: ctx.offset_x + 2.0 * ctx.scale,
-            y: ctx.offset_y + 2.0 * ctx.scale,
-            w: 30.0 * ctx.scale,
+            x: ctx.offset_x + PANEL_X * ctx.scale,
+            y: ctx.offset_y + 22.0 * ctx.scale,
+            w: PANEL_W * ctx.scale,
             h: 10.0 * ctx.scale,
-            text_key: "button.menu.return.default",
-            press_key: "button.menu.return.pressed",
+            text_key: &sort,
+            press_key: &sort,
             lang: &game.lang_manager,
-            font_size: ctx.font_size,
+            font_size: mini_font_size,
         },
         ctx.font,
     ) {
-        ctx.events.push(GameEvent::ReturnToTitleFromSaveSelect);
+        ctx.events.push(GameEvent::CycleSaveSort);
+    }
+
+    let selected = game
+        .save_files
+        .iter()
+        .position(|slot| game.selected_save.as_ref() == Some(&slot.name));
+    draw_slot_list(game, ctx, selected, mini_font_size);
+    if let Some(slot) = selected.and_then(|i| game.save_files.get(i)) {
+        draw_slot_details(game, ctx, slot, mini_font_size);
+    }
+}
+
+/// The slots that fit, scrolled to keep the selection in view; the mouse wheel moves
+/// the selection.
+fn draw_slot_list(
+    game: &Game,
+    ctx: &mut MenuRenderContext,
+    selected: Option<usize>,
+    font_size: u16,
+) {
+    let rows = ((SCREEN_HEIGHT - LIST_TOP) / ROW_STEP)
+        .floor()
+        .to_usize()
+        .unwrap_or(1);
+    let selected_index = selected.unwrap_or(0);
+    let first = (selected_index + 1).saturating_sub(rows);
+
+    let wheel = mouse_wheel().1;
+    if wheel != 0.0 && !game.save_files.is_empty() {
+        let next = if wheel < 0.0 {
+            (selected_index + 1).min(game.save_files.len() - 1)
+        } else {
+            selected_index.saturating_sub(1)
+        };
+        ctx.events
+            .push(GameEvent::SelectSave(game.save_files[next].name.clone()));
     }
-    cy += 15.0 * ctx.scale;
-    for file in &game.save_files {
+
+    let mut y = LIST_TOP;
+    for (i, slot) in game.save_files.iter().enumerate().skip(first).take(rows) {
+        if selected == Some(i) {
+            draw_rectangle_lines(
+                ctx.offset_x + (LIST_X - 1.0) * ctx.scale,
+                ctx.offset_y + (y - 1.0) * ctx.scale,
+                (LIST_W + 2.0) * ctx.scale,
+                12.0 * ctx.scale,
+                1.0,
+                YELLOW,
+            );
+        }
         if draw_button(
             ButtonParams {
-                x: ctx.offset_x + 10.0 * ctx.scale,
-                y: cy,
-                w: (SCREEN_WIDTH - 20.0) * ctx.scale,
+                x: ctx.offset_x + LIST_X * ctx.scale,
+                y: ctx.offset_y + y * ctx.scale,
+                w: LIST_W * ctx.scale,
                 h: 10.0 * ctx.scale,
-                text_key: file,
-                press_key: file,
+                text_key: &slot.meta.display_name,
+                press_key: &slot.meta.display_name,
                 lang: &game.lang_manager,
-                font_size: ctx.font_size,
+                font_size,
             },
             ctx.font,
         ) {
-            ctx.events.push(GameEvent::LoadSave(file.clone()));
+            ctx.events.push(GameEvent::SelectSave(slot.name.clone()));
+        }
+        y += ROW_STEP;
+    }
+}
+
+fn draw_slot_details(game: &Game, ctx: &mut MenuRenderContext, slot: &SaveSlot, font_size: u16) {
+    let meta = &slot.meta;
+    draw_thumbnail(ctx, &meta.thumbnail, PANEL_X, LIST_TOP);
+
+    let lang = &game.lang_manager;
+    let has_meta = !meta.thumbnail.is_empty();
+    let lines = if has_meta {
+        vec![
+            format!("${}", meta.money),
+            format!(
+                "{} {}m",
+                lang.get_string("save_select.depth"),
+                meta.max_depth
+            ),
+            format!(
+                "{} {}",
+                lang.get_string("save_select.time"),
+                format_play_time(meta.play_time_seconds)
+            ),
+            format!(
+                "{} {}",
+                lang.get_string("save_select.seed"),
+                meta.world_seed_main
+            ),
+            format_age(meta.last_saved, lang),
+        ]
+    } else {
+        vec![format_age(meta.last_saved, lang)]
+    };
+    let thumb_w = THUMBNAIL_WIDTH.to_f32().unwrap_or(0.0);
+    let thumb_h = THUMBNAIL_HEIGHT.to_f32().unwrap_or(0.0);
+    let mut y = LIST_TOP + 6.0;
+    for (i, line) in lines.iter().enumerate() {
+        // The first two lines sit beside the thumbnail, the rest below it.
+        let x = if i < 2 {
+            PANEL_X + thumb_w + 3.0
+        } else {
+            PANEL_X
+        };
+        if i == 2 {
+            y = LIST_TOP + thumb_h + 6.0;
+        }
+        draw_text_ex(
+            line,
+            ctx.offset_x + x * ctx.scale,
+            ctx.offset_y + y * ctx.scale,
+            TextParams {
+                font_size,
+                font: ctx.font,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
+        y += 7.0;
+    }
+
+    let top = LIST_TOP + thumb_h + 27.0;
+    let right = PANEL_X + PANEL_W - PANEL_BUTTON_W;
+    if game.is_renaming_save {
+        draw_name_field(ctx, &game.input_buffer, top, font_size);
+        if button(
+            game,
+            ctx,
+            (PANEL_X, top + 11.0, PANEL_BUTTON_W),
+            "button.confirm",
+            font_size,
+        ) {
+            ctx.events
+                .push(GameEvent::ConfirmRenameSave(game.input_buffer.clone()));
+        }
+        if button(
+            game,
+            ctx,
+            (right, top + 11.0, PANEL_BUTTON_W),
+            "button.save_select.cancel",
+            font_size,
+        ) {
+            ctx.events.push(GameEvent::CancelRenameSave);
         }
-        cy += 12.0 * ctx.scale;
+        return;
+    }
+
+    if button(
+        game,
+        ctx,
+        (PANEL_X, top, PANEL_BUTTON_W),
+        "button.menu.load",
+        font_size,
+    ) {
+        ctx.events.push(GameEvent::LoadSave(slot.name.clone()));
+    }
+    if button(
+        game,
+        ctx,
+        (right, top, PANEL_BUTTON_W),
+        "button.save_select.rename",
+        font_size,
+    ) {
+        ctx.events.push(GameEvent::StartRenameSave);
+    }
+    if button(
+        game,
+        ctx,
+        (PANEL_X, top + 11.0, PANEL_BUTTON_W),
+        "button.save_select.copy",
+        font_size,
+    ) {
+        ctx.events.push(GameEvent::DuplicateSave);
+    }
+    let delete_key = if game.is_confirming_delete {
+        "button.save_select.confirm_delete"
+    } else {
+        "button.save_select.delete"
+    };
+    if button(
+        game,
+        ctx,
+        (right, top + 11.0, PANEL_BUTTON_W),
+        delete_key,
+        font_size,
+    ) {
+        ctx.events.push(GameEvent::DeleteSave);
+    }
+    // A damaged world gets a button to bring back its last good backup.
+    if game.is_selected_save_damaged
+        && button(
+            game,
+            ctx,
+            (PANEL_X, top + 22.0, PANEL_W),
+            "button.menu.restore_backup",
+            font_size,
+        )
+    {
+        ctx.events.push(GameEvent::RestoreBackup(slot.name.clone()));
+    }
+}
+
+/// A 10-high button at virtual `(x, y)` of width `w`, labelled from `key`'s `.default`
+/// and `.pressed` strings where it has them.
+fn button(
+    game: &Game,
+    ctx: &MenuRenderContext,
+    (x, y, w): (f32, f32, f32),
+    key: &str,
+    font_size: u16,
+) -> bool {
+    let (text_key, press_key) = if key.starts_with("button.menu.") {
+        (format!("{key}.default"), format!("{key}.pressed"))
+    } else {
+        (key.to_string(), key.to_string())
+    };
+    draw_button(
+        ButtonParams {
+            x: ctx.offset_x + x * ctx.scale,
+            y: ctx.offset_y + y * ctx.scale,
+            w: w * ctx.scale,
+            h: 10.0 * ctx.scale,
+            text_key: &text_key,
+            press_key: &press_key,
+            lang: &game.lang_manager,
+            font_size,
+        },
+        ctx.font,
+    )
+}
+
+fn draw_thumbnail(ctx: &MenuRenderContext, pixels: &[u8], x: f32, y: f32) {
+    let (w, h) = (
+        THUMBNAIL_WIDTH.to_f32().unwrap_or(0.0),
+        THUMBNAIL_HEIGHT.to_f32().unwrap_or(0.0),
+    );
+    draw_rectangle(
+        ctx.offset_x + x * ctx.scale,
+        ctx.offset_y + y * ctx.scale,
+        w * ctx.scale,
+        h * ctx.scale,
+        BLACK,
+    );
+    for (i, rgb) in pixels
+        .chunks_exact(3)
+        .take(THUMBNAIL_WIDTH * THUMBNAIL_HEIGHT)
+        .enumerate()
+    {
+        let px = (i % THUMBNAIL_WIDTH).to_f32().unwrap_or(0.0);
+        let py = (i / THUMBNAIL_WIDTH).to_f32().unwrap_or(0.0);
+        draw_rectangle(
+            ctx.offset_x + (x + px) * ctx.scale,
+            ctx.offset_y + (y + py) * ctx.scale,
+            ctx.scale,
+            ctx.scale,
+            Color::from_rgba(rgb[0], rgb[1], rgb[2], 255),
+        );
     }
+    draw_rectangle_lines(
+        ctx.offset_x + x * ctx.scale,
+        ctx.offset_y + y * ctx.scale,
+        w * ctx.scale,
+        h * ctx.scale,
+        1.0,
+        GRAY,
+    );
+}
+
+fn draw_name_field(ctx: &MenuRenderContext, text: &str, y: f32, font_size: u16) {
+    let (x, y) = (
+        ctx.offset_x + PANEL_X * ctx.scale,
+        ctx.offset_y + y * ctx.scale,
+    );
+    draw_rectangle(x, y, PANEL_W * ctx.scale, 10.0 * ctx.scale, DARKGRAY);
+    draw_rectangle_lines(x, y, PANEL_W * ctx.scale, 10.0 * ctx.scale, 1.0, WHITE);
+    let cur = if (get_time() * 2.0) as i32 % 2 == 0 {
+        "|"
+    } else {
+        ""
+    };
+    draw_text_ex(
+        &format!("{text}{cur}"),
+        x + 2.0 * ctx.scale,
+        y + 7.5 * ctx.scale,
+        TextParams {
+            font_size,
+            font: ctx.font,
+            color: WHITE,
+            ..Default::default()
+        },
+    );
+}
+
+/// Play time as hours and minutes, e.g. `1h05m`.
+fn format_play_time(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).floor().to_u64().unwrap_or(0);
+    format!("{}h{:02}m", minutes / 60, minutes % 60)
+}
+
+/// How long ago a Unix timestamp was, in the largest whole unit.
+fn format_age(timestamp: u64, lang: &crate::managers::LanguageManager) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let age = now.saturating_sub(timestamp);
+    let amount = match age {
+        0..60 => return lang.get_string("save_select.just_now"),
+        60..3600 => format!("{}m", age / 60),
+        3600..86_400 => format!("{}h", age / 3600),
+        _ => format!("{}d", age / 86_400),
+    };
+    format!("{amount} {}", lang.get_string("save_select.ago"))
 }
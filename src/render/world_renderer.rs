@@ -1,10 +1,12 @@
 use crate::Game;
 use crate::constants::{
-    BLOCK_SIZE, SPRITE_BREAK_ANIM_U, SPRITE_BREAK_ANIM_V_START, SURFACE_Y_LEVEL,
+    BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, SCREEN_HEIGHT, SCREEN_WIDTH,
+    SPRITE_BREAK_ANIM_U, SPRITE_BREAK_ANIM_V_START,
 };
 use crate::managers::block::BlockType;
 use macroquad::prelude::*;
 use num_traits::ToPrimitive;
+use std::collections::HashMap;
 
 pub struct WorldRenderer;
 
@@ -26,8 +28,8 @@ impl WorldRenderer {
                     continue;
                 }
 
-                if let Some(nb) = world_manager.get_block_ref(bx + dx, by + dy)
-                    && !nb.is_broken
+                if let Some(nb) = world_manager.get_block(bx + dx, by + dy)
+                    && !nb.is_broken()
                     && nb.block_type.is_solid()
                 {
                     min_dist = dist;
@@ -59,13 +61,52 @@ impl WorldRenderer {
         (brightness, final_type)
     }
 
+    /// Fills chunks still being generated with a dim placeholder until their blocks arrive.
+    fn draw_pending_chunks(world_manager: &crate::managers::world::WorldManager, cx: f32, cy: f32) {
+        let chunk_w = CHUNK_SIZE_X_BLOCKS.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
+        let chunk_h = CHUNK_SIZE_Y_BLOCKS.to_f32().unwrap_or(0.0) * BLOCK_SIZE;
+        let start_cx = (cx / chunk_w).floor().to_i32().unwrap_or(0);
+        let start_cy = (cy / chunk_h).floor().to_i32().unwrap_or(0);
+        let end_cx = ((cx + SCREEN_WIDTH) / chunk_w)
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+        let end_cy = ((cy + SCREEN_HEIGHT) / chunk_h)
+            .floor()
+            .to_i32()
+            .unwrap_or(0);
+
+        for chunk_x in start_cx..=end_cx {
+            for chunk_y in start_cy..=end_cy {
+                if world_manager.is_chunk_pending(chunk_x, chunk_y) {
+                    draw_rectangle(
+                        chunk_x
+                            .to_f32()
+                            .unwrap_or(0.0)
+                            .mul_add(chunk_w, -cx)
+                            .round(),
+                        chunk_y
+                            .to_f32()
+                            .unwrap_or(0.0)
+                            .mul_add(chunk_h, -cy)
+                            .round(),
+                        chunk_w,
+                        chunk_h,
+                        Color::from_rgba(20, 20, 24, 255),
+                    );
+                }
+            }
+        }
+    }
+
     pub fn draw(game: &mut Game, atlas: Option<&Texture2D>) {
         let alpha = game.alpha;
         let cx = game.camera.old_x + (game.camera.x - game.camera.old_x) * alpha;
         let cy = game.camera.old_y + (game.camera.y - game.camera.old_y) * alpha;
 
-        // Ensure chunks are generated (mutable borrow)
+        // Queue missing chunks (mutable borrow)
         game.world_manager.get_active_blocks_in_view(cx, cy);
+        Self::draw_pending_chunks(&game.world_manager, cx, cy);
 
         // Re-borrow world_manager immutably for the rest of the drawing
         let world_manager = &game.world_manager;
@@ -73,26 +114,43 @@ impl WorldRenderer {
             .world_manager
             .get_active_blocks_in_view_immutable(cx, cy);
 
+        // Surface height per block column, shared by every block drawn in that column
+        let mut surface_cache: HashMap<i32, f32> = HashMap::new();
+
         for block in blocks {
             let draw_x = (block.x - cx).round();
             let draw_y = (block.y - cy).round();
 
             // Draw black background if underground
-            if block.y >= SURFACE_Y_LEVEL.to_f32().unwrap_or(0.0) * BLOCK_SIZE {
+            let column = (block.x / BLOCK_SIZE).floor().to_i32().unwrap_or(0);
+            let surface_world_y = *surface_cache.entry(column).or_insert_with(|| {
+                world_manager
+                    .surface_height_at(column)
+                    .to_f32()
+                    .unwrap_or(0.0)
+                    * BLOCK_SIZE
+            });
+            if block.y >= surface_world_y {
                 draw_rectangle(draw_x, draw_y, BLOCK_SIZE, BLOCK_SIZE, BLACK);
             }
 
-            if block.is_broken {
+            if block.is_broken() {
                 // Render back wall if broken
-                let (brightness, back_type) = Self::get_background_info(world_manager, block);
+                let (brightness, back_type) = Self::get_background_info(world_manager, &block);
                 if brightness > 0.0
                     && let (Some(rect), Some(atlas_tex)) = (back_type.get_sprite(), atlas)
                 {
+                    let tint = back_type.get_tint();
                     draw_texture_ex(
                         atlas_tex,
                         draw_x,
                         draw_y,
-                        Color::new(brightness, brightness, brightness, 1.0),
+                        Color::new(
+                            tint.r * brightness,
+                            tint.g * brightness,
+                            tint.b * brightness,
+                            1.0,
+                        ),
                         DrawTextureParams {
                             source: Some(rect),
                             ..Default::default()
@@ -101,15 +159,21 @@ impl WorldRenderer {
                 }
             } else if block.block_type.is_liquid() {
                 // Render back wall first for liquids
-                let (brightness, back_type) = Self::get_background_info(world_manager, block);
+                let (brightness, back_type) = Self::get_background_info(world_manager, &block);
                 if brightness > 0.0
                     && let (Some(rect), Some(atlas_tex)) = (back_type.get_sprite(), atlas)
                 {
+                    let tint = back_type.get_tint();
                     draw_texture_ex(
                         atlas_tex,
                         draw_x,
                         draw_y,
-                        Color::new(brightness, brightness, brightness, 1.0),
+                        Color::new(
+                            tint.r * brightness,
+                            tint.g * brightness,
+                            tint.b * brightness,
+                            1.0,
+                        ),
                         DrawTextureParams {
                             source: Some(rect),
                             ..Default::default()
@@ -117,7 +181,7 @@ impl WorldRenderer {
                     );
                 }
 
-                if let (Some(rect), Some(atlas_tex)) = (block.sprite_rect, atlas) {
+                if let (Some(rect), Some(atlas_tex)) = (block.sprite_rect(), atlas) {
                     let level = f32::from(block.liquid_level.clamp(1, 8));
                     let height_ratio = level / 8.0;
                     let draw_height = BLOCK_SIZE * height_ratio;
@@ -126,11 +190,12 @@ impl WorldRenderer {
                     let mut source_rect = rect;
                     source_rect.h *= height_ratio;
 
+                    let tint = block.block_type.get_tint();
                     draw_texture_ex(
                         atlas_tex,
                         draw_x,
                         draw_y + offset_y,
-                        Color::new(1.0, 1.0, 1.0, 0.4),
+                        Color::new(tint.r, tint.g, tint.b, 0.4),
                         DrawTextureParams {
                             source: Some(source_rect),
                             dest_size: Some(vec2(BLOCK_SIZE, draw_height)),
@@ -138,12 +203,12 @@ impl WorldRenderer {
                         },
                     );
                 }
-            } else if let (Some(rect), Some(atlas_tex)) = (block.sprite_rect, atlas) {
+            } else if let (Some(rect), Some(atlas_tex)) = (block.sprite_rect(), atlas) {
                 draw_texture_ex(
                     atlas_tex,
                     draw_x,
                     draw_y,
-                    WHITE,
+                    block.block_type.get_tint(),
                     DrawTextureParams {
                         source: Some(rect),
                         ..Default::default()
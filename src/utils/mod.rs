@@ -1,21 +1,13 @@
 use crate::components::{BlockPos, ChunkRelPos};
 use crate::constants::{
     BLOCK_SIZE, CHUNK_SIZE_X_BLOCKS, CHUNK_SIZE_Y_BLOCKS, MACROGRID_SIZE_CHUNKS, SCREEN_HEIGHT,
-    SCREEN_WIDTH, SURFACE_TEMPERATURE, SURFACE_Y_LEVEL, TEMPERATURE_GRADIENT,
+    SCREEN_WIDTH,
 };
 use macroquad::prelude::*;
 use num_traits::ToPrimitive;
 
 pub mod icon;
 
-#[must_use]
-pub fn get_temperature(y: f32) -> f32 {
-    let depth = (y / BLOCK_SIZE).floor() - SURFACE_Y_LEVEL.to_f32().unwrap_or(0.0);
-    depth
-        .max(0.0)
-        .mul_add(TEMPERATURE_GRADIENT, SURFACE_TEMPERATURE)
-}
-
 #[must_use]
 pub fn get_render_dimensions() -> (f32, f32, f32, f32) {
     let target_aspect = SCREEN_WIDTH / SCREEN_HEIGHT;
@@ -110,3 +102,30 @@ pub fn get_item_sprite(item_type: &str) -> Rect {
         .and_then(|bt| crate::managers::block::BLOCK_MANAGER.get_sprite(&bt))
         .unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0))
 }
+
+#[must_use]
+pub fn get_item_tint(item_type: &str) -> Color {
+    crate::managers::block::BLOCK_MANAGER
+        .get_by_item_type(item_type)
+        .map_or(WHITE, |bt| {
+            crate::managers::block::BLOCK_MANAGER.get_tint(&bt)
+        })
+}
+
+/// Deterministic integer hash of a lattice coordinate, used by world generation
+/// wherever a value has to be reproducible from the seed alone.
+#[must_use]
+pub const fn hash_coords(seed: u32, x: i32, y: i32) -> u32 {
+    let mut h = seed as u64 ^ 0x9E37_79B9_7F4A_7C15;
+    h ^= (x as u32 as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 31)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^= (y as u32 as u64).wrapping_mul(0xD6E8_FEB8_6659_FD93);
+    h = (h ^ (h >> 29)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    (h ^ (h >> 32)) as u32
+}
+
+/// Maps a hash to `[0.0, 1.0)`.
+#[must_use]
+pub fn hash_to_unit(h: u32) -> f32 {
+    (h >> 8).to_f32().unwrap_or(0.0) / 16_777_216.0
+}